@@ -0,0 +1,264 @@
+use std::cmp::Ordering;
+
+use num_traits::{Float, Signed};
+use point2d::Point2d;
+use vector2d::Vector2d;
+
+/// A circle given by its center and radius.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BoundingCircle<T> {
+    pub center: Point2d<T>,
+    pub radius: T,
+}
+
+/// A rectangle rotated so that its first side runs along `axis`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct OrientedBox<T> {
+    pub center: Point2d<T>,
+    /// Unit direction of the first side; the second side runs along its left perpendicular.
+    pub axis: Vector2d<T>,
+    /// Half the side lengths along `axis` and along its perpendicular.
+    pub half_extents: (T, T),
+}
+
+fn vector<T: Float + Signed>(from: &Point2d<T>, to: &Point2d<T>) -> Vector2d<T> {
+    Vector2d::new(to.x() - from.x(), to.y() - from.y())
+}
+
+fn cross<T: Float + Signed>(o: &Point2d<T>, a: &Point2d<T>, b: &Point2d<T>) -> T {
+    vector(o, a).cross(&vector(o, b))
+}
+
+impl<T: Float + Signed> BoundingCircle<T> {
+    pub fn new(center: Point2d<T>, radius: T) -> Self {
+        BoundingCircle { center, radius }
+    }
+    /// Returns true if `point` lies in the circle, allowing for a relative rounding error.
+    pub fn contains(&self, point: &Point2d<T>) -> bool {
+        let slack = T::from(1e-12).unwrap_or(T::zero()) * (self.radius + self.center.x().abs() + self.center.y().abs());
+        self.center.distance_to(point) <= self.radius + slack
+    }
+    fn from_diameter(a: &Point2d<T>, b: &Point2d<T>) -> Self {
+        let two = T::from(2.0).unwrap();
+        let center = Point2d::new((a.x() + b.x()) / two, (a.y() + b.y()) / two);
+        BoundingCircle { center, radius: center.distance_to(a).max(center.distance_to(b)) }
+    }
+    /// Returns the circle through three points, or the circle over the two farthest apart
+    /// if they are collinear.
+    fn from_triangle(a: &Point2d<T>, b: &Point2d<T>, c: &Point2d<T>) -> Self {
+        let (ab, ac) = (vector(a, b), vector(a, c));
+        let d = ab.cross(&ac) * T::from(2.0).unwrap();
+        if d == T::zero() {
+            let pairs = [Self::from_diameter(a, b), Self::from_diameter(a, c), Self::from_diameter(b, c)];
+            return pairs.into_iter().fold(pairs[0], |best, circle| if circle.radius > best.radius { circle } else { best });
+        }
+        let (lb, lc) = (ab.length_squared(), ac.length_squared());
+        let ux = (ac.y * lb - ab.y * lc) / d;
+        let uy = (ab.x * lc - ac.x * lb) / d;
+        let center = Point2d::new(a.x() + ux, a.y() + uy);
+        let radius = center.distance_to(a).max(center.distance_to(b)).max(center.distance_to(c));
+        BoundingCircle { center, radius }
+    }
+}
+
+/// Returns the smallest circle containing every point, using Welzl's algorithm.
+///
+/// The points are visited in a fixed pseudo-random order, which gives expected linear time.
+pub fn min_enclosing_circle<T: Float + Signed>(points: &[Point2d<T>]) -> Option<BoundingCircle<T>> {
+    let mut points = points.to_vec();
+    // Fisher-Yates shuffle with a fixed seed so results are reproducible
+    let mut state: u64 = 0x853c_49e6_748f_ea9b;
+    for i in (1..points.len()).rev() {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        points.swap(i, (state >> 33) as usize % (i + 1));
+    }
+    let mut circle = BoundingCircle::new(*points.first()?, T::zero());
+    for i in 1..points.len() {
+        if circle.contains(&points[i]) {
+            continue;
+        }
+        // points[i] is on the boundary of the circle around the first i + 1 points
+        circle = BoundingCircle::new(points[i], T::zero());
+        for j in 0..i {
+            if circle.contains(&points[j]) {
+                continue;
+            }
+            circle = BoundingCircle::from_diameter(&points[i], &points[j]);
+            for k in 0..j {
+                if !circle.contains(&points[k]) {
+                    circle = BoundingCircle::from_triangle(&points[i], &points[j], &points[k]);
+                }
+            }
+        }
+    }
+    Some(circle)
+}
+
+/// Returns the convex hull in counter-clockwise order, without collinear points.
+pub fn convex_hull<T: Float + Signed>(points: &[Point2d<T>]) -> Vec<Point2d<T>> {
+    let mut sorted = points.to_vec();
+    let order = |a: T, b: T| a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+    sorted.sort_by(|a, b| order(a.x(), b.x()).then(order(a.y(), b.y())));
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return sorted;
+    }
+    // Andrew's monotone chain: lower hull left to right, then upper hull right to left
+    let mut hull: Vec<Point2d<T>> = Vec::with_capacity(sorted.len() + 1);
+    for pass in 0..2 {
+        let start = hull.len();
+        for point in sorted.iter() {
+            while hull.len() >= start + 2 && cross(&hull[hull.len() - 2], &hull[hull.len() - 1], point) <= T::zero() {
+                hull.pop();
+            }
+            hull.push(*point);
+        }
+        hull.pop();
+        if pass == 0 {
+            sorted.reverse();
+        }
+    }
+    hull
+}
+
+/// Returns the convex hull, or None if it has no area.
+fn polygon_hull<T: Float + Signed>(points: &[Point2d<T>]) -> Option<Vec<Point2d<T>>> {
+    let hull = convex_hull(points);
+    if hull.len() >= 3 { Some(hull) } else { None }
+}
+
+/// Returns the two points farthest apart and their distance, found with rotating calipers
+/// over the convex hull.
+pub fn diameter<T: Float + Signed>(points: &[Point2d<T>]) -> Option<(T, [Point2d<T>; 2])> {
+    let Some(hull) = polygon_hull(points) else {
+        let hull = convex_hull(points);
+        let (first, last) = (*hull.first()?, *hull.last()?);
+        return Some((first.distance_to(&last), [first, last]));
+    };
+    let n = hull.len();
+    let mut best = (T::zero(), [hull[0], hull[0]]);
+    let mut j = 1;
+    for i in 0..n {
+        let (a, b) = (hull[i], hull[(i + 1) % n]);
+        // Advance to the vertex farthest from edge a-b
+        while cross(&a, &b, &hull[(j + 1) % n]) > cross(&a, &b, &hull[j]) {
+            j = (j + 1) % n;
+        }
+        // With an edge parallel to a-b both of its ends are antipodal to a and b
+        for candidate in [a, b] {
+            for other in [hull[j], hull[(j + 1) % n]] {
+                let distance = candidate.distance_to(&other);
+                if distance > best.0 {
+                    best = (distance, [candidate, other]);
+                }
+            }
+        }
+    }
+    Some(best)
+}
+
+/// Returns the smallest distance between two parallel lines enclosing every point, and
+/// the unit normal of those lines. Collinear points have zero width.
+pub fn width<T: Float + Signed>(points: &[Point2d<T>]) -> Option<(T, Vector2d<T>)> {
+    let Some(hull) = polygon_hull(points) else {
+        let hull = convex_hull(points);
+        let (first, last) = (*hull.first()?, *hull.last()?);
+        let normal = if first == last {
+            Vector2d::new(T::zero(), T::one())
+        } else {
+            vector(&first, &last).perpendicular().normalized()?.to_vector()
+        };
+        return Some((T::zero(), normal));
+    };
+    let n = hull.len();
+    let mut best: Option<(T, Vector2d<T>)> = None;
+    let mut j = 1;
+    for i in 0..n {
+        let (a, b) = (hull[i], hull[(i + 1) % n]);
+        while cross(&a, &b, &hull[(j + 1) % n]) > cross(&a, &b, &hull[j]) {
+            j = (j + 1) % n;
+        }
+        let edge = vector(&a, &b);
+        let distance = cross(&a, &b, &hull[j]) / edge.length();
+        if best.is_none_or(|(width, _)| distance < width) {
+            best = Some((distance, edge.perpendicular().normalized()?.to_vector()));
+        }
+    }
+    best
+}
+
+/// Returns the smallest-area rectangle containing every point, found with rotating calipers.
+///
+/// One side of the optimal rectangle always lies on a hull edge, so each edge direction is
+/// tried while the extreme points along it are advanced around the hull.
+pub fn min_area_box<T: Float + Signed>(points: &[Point2d<T>]) -> Option<OrientedBox<T>> {
+    let two = T::from(2.0).unwrap();
+    let Some(hull) = polygon_hull(points) else {
+        let hull = convex_hull(points);
+        let (first, last) = (*hull.first()?, *hull.last()?);
+        let axis = if first == last { Vector2d::new(T::one(), T::zero()) } else { vector(&first, &last).normalized()?.to_vector() };
+        let center = Point2d::new((first.x() + last.x()) / two, (first.y() + last.y()) / two);
+        return Some(OrientedBox { center, axis, half_extents: (first.distance_to(&last) / two, T::zero()) });
+    };
+    let n = hull.len();
+    let along = |k: usize, axis: &Vector2d<T>| hull[k].to_vector().dot(axis);
+    // Moves a caliper on while the projection does not decrease. Ties hold all the way round
+    // on a nearly flat hull, so a caliper goes round at most once.
+    let advance = |mut k: usize, projection: &dyn Fn(usize) -> T| {
+        for _ in 0..n {
+            if projection((k + 1) % n) < projection(k) {
+                break;
+            }
+            k = (k + 1) % n;
+        }
+        k
+    };
+    let mut best: Option<(T, OrientedBox<T>)> = None;
+    let (mut right, mut top, mut left) = (0, 0, 0);
+    for i in 0..n {
+        let axis = vector(&hull[i], &hull[(i + 1) % n]).normalized()?.to_vector();
+        let normal = axis.perpendicular();
+        if i == 0 {
+            right = (0..n).fold(0, |best, k| if along(k, &axis) > along(best, &axis) { k } else { best });
+            top = (0..n).fold(0, |best, k| if along(k, &normal) > along(best, &normal) { k } else { best });
+            left = (0..n).fold(0, |best, k| if along(k, &axis) < along(best, &axis) { k } else { best });
+        }
+        right = advance(right, &|k| along(k, &axis));
+        top = advance(top, &|k| along(k, &normal));
+        left = advance(left, &|k| -along(k, &axis));
+        let (min_u, max_u) = (along(left, &axis), along(right, &axis));
+        let (min_v, max_v) = (along(i, &normal), along(top, &normal));
+        let area = (max_u - min_u) * (max_v - min_v);
+        if best.is_none_or(|(best_area, _)| area < best_area) {
+            let (mid_u, mid_v) = ((min_u + max_u) / two, (min_v + max_v) / two);
+            let center = Point2d::new(axis.x * mid_u + normal.x * mid_v, axis.y * mid_u + normal.y * mid_v);
+            let half_extents = ((max_u - min_u) / two, (max_v - min_v) / two);
+            best = Some((area, OrientedBox { center, axis, half_extents }));
+        }
+    }
+    best.map(|(_, bounds)| bounds)
+}
+
+impl<T: Float + Signed> OrientedBox<T> {
+    /// Returns the rotation of `axis` from the x axis, in radians.
+    pub fn angle(&self) -> T {
+        self.axis.y.atan2(self.axis.x)
+    }
+    pub fn area(&self) -> T {
+        T::from(4.0).unwrap() * self.half_extents.0 * self.half_extents.1
+    }
+    /// Returns the corners in counter-clockwise order.
+    pub fn corners(&self) -> [Point2d<T>; 4] {
+        let u = self.axis.scale(self.half_extents.0);
+        let v = self.axis.perpendicular().scale(self.half_extents.1);
+        [(-T::one(), -T::one()), (T::one(), -T::one()), (T::one(), T::one()), (-T::one(), T::one())]
+            .map(|(s, t)| Point2d::new(self.center.x() + u.x * s + v.x * t, self.center.y() + u.y * s + v.y * t))
+    }
+    /// Returns true if `point` lies in the box, allowing for a relative rounding error.
+    pub fn contains(&self, point: &Point2d<T>) -> bool {
+        let offset = vector(&self.center, point);
+        let slack = T::from(1e-12).unwrap_or(T::zero()) * (T::one() + self.half_extents.0 + self.half_extents.1);
+        offset.projection_length(&self.axis).abs() <= self.half_extents.0 + slack
+            && offset.projection_length(&self.axis.perpendicular()).abs() <= self.half_extents.1 + slack
+    }
+}
@@ -0,0 +1,361 @@
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+
+use point2d::Point2d;
+use predicates2d::{incircle, orient2d};
+
+use crate::TriangleMesh;
+
+const NONE: usize = usize::MAX;
+
+fn next(e: usize) -> usize {
+    if e % 3 == 2 { e - 2 } else { e + 1 }
+}
+
+fn prev(e: usize) -> usize {
+    if e.is_multiple_of(3) { e + 2 } else { e - 1 }
+}
+
+fn opposite_signs(s: f64, t: f64) -> bool {
+    (s > 0.0 && t < 0.0) || (s < 0.0 && t > 0.0)
+}
+
+/// Half-edge triangulation under construction.
+///
+/// Half-edge `e` runs from `triangles[e]` to `triangles[next(e)]` and belongs to triangle
+/// `e / 3`. Edges are looked up by their end points, since flips move half-edges between slots.
+pub(crate) struct Builder {
+    points: Vec<Point2d<f64>>,
+    /// Maps every point to the first point with the same coordinates.
+    canonical: Vec<usize>,
+    triangles: Vec<usize>,
+    halfedges: Vec<usize>,
+    constrained: Vec<bool>,
+    /// Some half-edge leaving each vertex, or `NONE` for points not in the mesh.
+    vertex_edge: Vec<usize>,
+}
+
+impl Builder {
+    /// Builds the Delaunay triangulation by sweeping the points in lexicographic order,
+    /// connecting each one to the hull edges it sees and restoring the Delaunay property
+    /// with edge flips.
+    pub(crate) fn new(points: &[Point2d<f64>]) -> Builder {
+        let n = points.len();
+        let mut order: Vec<usize> = (0..n).collect();
+        let by_coordinates = |a: &Point2d<f64>, b: &Point2d<f64>| {
+            a.x().partial_cmp(&b.x()).unwrap_or(Ordering::Equal).then(a.y().partial_cmp(&b.y()).unwrap_or(Ordering::Equal))
+        };
+        order.sort_by(|&i, &j| by_coordinates(&points[i], &points[j]));
+        let mut canonical: Vec<usize> = (0..n).collect();
+        let mut unique: Vec<usize> = Vec::with_capacity(n);
+        for &i in &order {
+            match unique.last() {
+                Some(&last) if points[last] == points[i] => canonical[i] = last,
+                _ => unique.push(i),
+            }
+        }
+        let mut builder = Builder {
+            points: points.to_vec(),
+            canonical,
+            triangles: Vec::new(),
+            halfedges: Vec::new(),
+            constrained: Vec::new(),
+            vertex_edge: vec![NONE; n],
+        };
+        if unique.len() < 3 {
+            return builder;
+        }
+        // Leading collinear points are fanned out to the first point off their line
+        let (first, second) = (points[unique[0]], points[unique[1]]);
+        let Some(k) = (2..unique.len()).find(|&k| orient2d(&first, &second, &points[unique[k]]) != 0.0) else {
+            return builder;
+        };
+        let apex = unique[k];
+        let ccw = orient2d(&first, &second, &points[apex]) > 0.0;
+        for pair in unique[..k].windows(2) {
+            if ccw {
+                builder.add_triangle(pair[0], pair[1], apex);
+            } else {
+                builder.add_triangle(pair[1], pair[0], apex);
+            }
+        }
+        // Counter-clockwise hull as a linked ring
+        let mut hull: Vec<usize> = unique[..=k].to_vec();
+        if !ccw {
+            hull.reverse();
+        }
+        let mut hull_next = vec![NONE; n];
+        let mut hull_prev = vec![NONE; n];
+        for i in 0..hull.len() {
+            let j = (i + 1) % hull.len();
+            hull_next[hull[i]] = hull[j];
+            hull_prev[hull[j]] = hull[i];
+        }
+        // The last inserted point is always on the hull and sees the new point
+        let mut last = apex;
+        for &p in &unique[k + 1..] {
+            let point = points[p];
+            let mut stack = Vec::new();
+            let mut right = last;
+            while orient2d(&points[right], &points[hull_next[right]], &point) < 0.0 {
+                let after = hull_next[right];
+                builder.add_triangle(after, right, p);
+                stack.push((after, right));
+                right = after;
+            }
+            let mut left = last;
+            while orient2d(&points[hull_prev[left]], &points[left], &point) < 0.0 {
+                let before = hull_prev[left];
+                builder.add_triangle(left, before, p);
+                stack.push((left, before));
+                left = before;
+            }
+            hull_next[left] = p;
+            hull_prev[p] = left;
+            hull_next[p] = right;
+            hull_prev[right] = p;
+            builder.legalize(stack);
+            last = p;
+        }
+        builder
+    }
+
+    /// Adds a counter-clockwise triangle and links it to the triangles already sharing its edges.
+    fn add_triangle(&mut self, a: usize, b: usize, c: usize) {
+        let t = self.triangles.len();
+        self.triangles.extend([a, b, c]);
+        self.halfedges.extend([NONE; 3]);
+        self.constrained.extend([false; 3]);
+        for e in t..t + 3 {
+            if let Some(twin) = self.find_edge(self.triangles[next(e)], self.triangles[e]) {
+                self.halfedges[e] = twin;
+                self.halfedges[twin] = e;
+            }
+        }
+        for e in t..t + 3 {
+            self.vertex_edge[self.triangles[e]] = e;
+        }
+    }
+
+    /// Finds the half-edge from `u` to `v` by turning around `u`.
+    fn find_edge(&self, u: usize, v: usize) -> Option<usize> {
+        self.edges_around(u).find(|&e| self.triangles[next(e)] == v)
+    }
+
+    /// Iterates over every half-edge leaving `u`, walking the mesh without collecting them.
+    fn edges_around(&self, u: usize) -> impl Iterator<Item = usize> + '_ {
+        let start = self.vertex_edge[u];
+        let back = |e: usize| {
+            let twin = self.halfedges[e];
+            (twin != NONE).then(|| next(twin))
+        };
+        let mut current = (start != NONE).then_some(start);
+        let mut backwards = false;
+        std::iter::from_fn(move || {
+            let e = current?;
+            current = if backwards {
+                back(e)
+            } else {
+                match self.halfedges[prev(e)] {
+                    f if f == start => None,
+                    // `u` is on the boundary, so also turn the other way
+                    NONE => {
+                        backwards = true;
+                        back(start)
+                    }
+                    f => Some(f),
+                }
+            };
+            Some(e)
+        })
+    }
+
+    /// Replaces the diagonal `u -> v` of the two triangles `u, v, p` and `v, u, q` with `p -> q`.
+    fn flip(&mut self, e: usize) {
+        let f = self.halfedges[e];
+        let (t0, t1) = (e - e % 3, f - f % 3);
+        let (u, v, p, q) = (self.triangles[e], self.triangles[next(e)], self.triangles[prev(e)], self.triangles[prev(f)]);
+        let outer = [prev(e), next(f), prev(f), next(e)].map(|s| (self.halfedges[s], self.constrained[s]));
+        self.triangles[t0..t0 + 3].copy_from_slice(&[p, u, q]);
+        self.triangles[t1..t1 + 3].copy_from_slice(&[q, v, p]);
+        for (slot, (twin, constrained)) in [t0, t0 + 1, t1, t1 + 1].into_iter().zip(outer) {
+            self.halfedges[slot] = twin;
+            if twin != NONE {
+                self.halfedges[twin] = slot;
+            }
+            self.constrained[slot] = constrained;
+        }
+        self.halfedges[t0 + 2] = t1 + 2;
+        self.halfedges[t1 + 2] = t0 + 2;
+        self.constrained[t0 + 2] = false;
+        self.constrained[t1 + 2] = false;
+        for slot in (t0..t0 + 3).chain(t1..t1 + 3) {
+            self.vertex_edge[self.triangles[slot]] = slot;
+        }
+    }
+
+    /// Flips edges that fail the empty circle test until none is left, starting from `stack`.
+    /// Constraint edges are never flipped.
+    fn legalize(&mut self, mut stack: Vec<(usize, usize)>) {
+        while let Some((u, v)) = stack.pop() {
+            let Some(e) = self.find_edge(u, v) else {
+                continue; // Already flipped away
+            };
+            let f = self.halfedges[e];
+            if f == NONE || self.constrained[e] {
+                continue;
+            }
+            let (p, q) = (self.triangles[prev(e)], self.triangles[prev(f)]);
+            if incircle(&self.points[u], &self.points[v], &self.points[p], &self.points[q]) > 0.0 {
+                self.flip(e);
+                stack.extend([(p, u), (u, q), (q, v), (v, p)]);
+            }
+        }
+    }
+
+    /// Walks from `a` towards `b` and returns the edges crossed up to the first vertex on the
+    /// segment, together with that vertex. Returns None if a constraint edge is crossed.
+    fn crossings(&self, a: usize, b: usize) -> Option<(usize, Vec<(usize, usize)>)> {
+        let (pa, pb) = (self.points[a], self.points[b]);
+        let on_segment = |v: usize| {
+            let p = self.points[v];
+            orient2d(&pa, &pb, &p) == 0.0 && (p.x() - pa.x()) * (pb.x() - pa.x()) + (p.y() - pa.y()) * (pb.y() - pa.y()) > 0.0
+        };
+        let mut crossing = None;
+        for e in self.edges_around(a) {
+            let (v1, v2) = (self.triangles[next(e)], self.triangles[prev(e)]);
+            if let Some(v) = [v1, v2].into_iter().find(|&v| on_segment(v)) {
+                return Some((v, Vec::new()));
+            }
+            if orient2d(&pa, &pb, &self.points[v1]) < 0.0 && orient2d(&pa, &pb, &self.points[v2]) > 0.0 {
+                crossing = Some(next(e));
+                break;
+            }
+        }
+        // `h` is the crossed half-edge, running from the right of the segment to its left
+        let mut h = crossing?;
+        let mut crossed = Vec::new();
+        loop {
+            if self.constrained[h] {
+                return None;
+            }
+            crossed.push((self.triangles[h], self.triangles[next(h)]));
+            let g = self.halfedges[h];
+            let w = self.triangles[prev(g)];
+            let side = orient2d(&pa, &pb, &self.points[w]);
+            if w == b || side == 0.0 {
+                return Some((w, crossed));
+            }
+            h = if side > 0.0 { next(g) } else { prev(g) };
+        }
+    }
+
+    /// Makes the segment between points `a` and `b` part of the mesh as constraint edges.
+    /// Returns None if it crosses an existing constraint edge.
+    pub(crate) fn insert_constraint(&mut self, a: usize, b: usize) -> Option<()> {
+        let (mut a, b) = (self.canonical[a], self.canonical[b]);
+        if self.vertex_edge[a] == NONE || self.vertex_edge[b] == NONE {
+            return Some(()); // Empty mesh
+        }
+        while a != b {
+            let (end, crossed) = self.crossings(a, b)?;
+            let new_edges = self.recover_edge(a, end, crossed);
+            let e = self.find_edge(a, end)?;
+            self.constrained[e] = true;
+            let twin = self.halfedges[e];
+            if twin != NONE {
+                self.constrained[twin] = true;
+            }
+            self.legalize(new_edges);
+            a = end;
+        }
+        Some(())
+    }
+
+    /// Flips the crossed edges until the edge `a - b` appears, and returns the edges created on the way.
+    fn recover_edge(&mut self, a: usize, b: usize, crossed: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+        let (pa, pb) = (self.points[a], self.points[b]);
+        let mut queue: VecDeque<(usize, usize)> = crossed.into();
+        let mut new_edges = Vec::new();
+        while let Some((u, v)) = queue.pop_front() {
+            let Some(e) = self.find_edge(u, v) else {
+                continue;
+            };
+            let f = self.halfedges[e];
+            let (p, q) = (self.triangles[prev(e)], self.triangles[prev(f)]);
+            let (pp, pq) = (self.points[p], self.points[q]);
+            if !opposite_signs(orient2d(&pp, &pq, &self.points[u]), orient2d(&pp, &pq, &self.points[v])) {
+                // The two triangles do not form a convex quadrilateral yet
+                queue.push_back((u, v));
+                continue;
+            }
+            self.flip(e);
+            if opposite_signs(orient2d(&pa, &pb, &pp), orient2d(&pa, &pb, &pq)) {
+                queue.push_back((p, q));
+            } else {
+                new_edges.push((p, q));
+            }
+        }
+        new_edges
+    }
+
+    /// Marks the triangles that lie inside an odd number of constraint rings.
+    pub(crate) fn inside_constraints(&self) -> Vec<bool> {
+        let count = self.triangles.len() / 3;
+        let mut depth = vec![usize::MAX; count];
+        let mut queue = VecDeque::new();
+        for e in 0..self.triangles.len() {
+            if self.halfedges[e] == NONE {
+                let d = usize::from(self.constrained[e]);
+                if d < depth[e / 3] {
+                    depth[e / 3] = d;
+                    queue.push_back(e / 3);
+                }
+            }
+        }
+        // Breadth-first search where crossing a constraint edge costs one
+        while let Some(t) = queue.pop_front() {
+            for e in 3 * t..3 * t + 3 {
+                let twin = self.halfedges[e];
+                if twin == NONE {
+                    continue;
+                }
+                let d = depth[t] + usize::from(self.constrained[e]);
+                if d < depth[twin / 3] {
+                    depth[twin / 3] = d;
+                    if self.constrained[e] {
+                        queue.push_back(twin / 3);
+                    } else {
+                        queue.push_front(twin / 3);
+                    }
+                }
+            }
+        }
+        depth.into_iter().map(|d| d % 2 == 1).collect()
+    }
+
+    /// Converts to a mesh, keeping only the triangles marked in `keep` if given.
+    pub(crate) fn into_mesh(self, points: &[Point2d<f64>], keep: Option<&[bool]>) -> TriangleMesh {
+        let count = self.triangles.len() / 3;
+        let mut index = vec![NONE; count];
+        let mut kept = 0;
+        for (t, slot) in index.iter_mut().enumerate() {
+            if keep.is_none_or(|keep| keep[t]) {
+                *slot = kept;
+                kept += 1;
+            }
+        }
+        let neighbor = |e: usize| {
+            let twin = self.halfedges[e];
+            if twin == NONE || index[twin / 3] == NONE { None } else { Some(index[twin / 3]) }
+        };
+        let mut mesh = TriangleMesh { points: points.to_vec(), ..TriangleMesh::default() };
+        for t in (0..count).filter(|&t| index[t] != NONE) {
+            let corners = [3 * t, 3 * t + 1, 3 * t + 2];
+            mesh.triangles.push(corners.map(|e| self.triangles[e]));
+            mesh.neighbors.push(corners.map(neighbor));
+            mesh.constrained.push(corners.map(|e| self.constrained[e]));
+        }
+        mesh
+    }
+}
@@ -0,0 +1,104 @@
+use point2d::Point2d;
+use polygon2d::{MultiPolygon2d, Polygon2d};
+
+mod builder;
+use builder::Builder;
+
+/// A triangle mesh with counter-clockwise triangles and triangle adjacency.
+///
+/// `points` holds every input point, so indices given to the constructors stay valid.
+/// Points that repeat an earlier point are not used by any triangle.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct TriangleMesh {
+    points: Vec<Point2d<f64>>,
+    triangles: Vec<[usize; 3]>,
+    neighbors: Vec<[Option<usize>; 3]>,
+    constrained: Vec<[bool; 3]>,
+}
+
+impl TriangleMesh {
+    /// Returns the Delaunay triangulation of `points`, covering their convex hull.
+    ///
+    /// The mesh is empty if all points are collinear.
+    pub fn delaunay(points: &[Point2d<f64>]) -> TriangleMesh {
+        let builder = Builder::new(points);
+        builder.into_mesh(points, None)
+    }
+    /// Returns the constrained Delaunay triangulation of `points` in which every pair of
+    /// point indices in `edges` is connected by mesh edges.
+    ///
+    /// Constraint edges that run through other points are split at those points.
+    /// Returns None if an index is out of range or two constraint edges cross.
+    pub fn constrained(points: &[Point2d<f64>], edges: &[[usize; 2]]) -> Option<TriangleMesh> {
+        let mut builder = Builder::new(points);
+        for &[a, b] in edges {
+            if a >= points.len() || b >= points.len() {
+                return None;
+            }
+            builder.insert_constraint(a, b)?;
+        }
+        Some(builder.into_mesh(points, None))
+    }
+    /// Triangulates the inside of a polygon, keeping its edges and leaving its holes empty.
+    ///
+    /// Returns None if the rings cross each other or themselves.
+    pub fn from_polygon(polygon: &Polygon2d<f64>) -> Option<TriangleMesh> {
+        Self::from_polygons(&MultiPolygon2d::new(vec![polygon.clone()]))
+    }
+    /// Triangulates the inside of a set of polygons; see `from_polygon`.
+    pub fn from_polygons(polygons: &MultiPolygon2d<f64>) -> Option<TriangleMesh> {
+        let mut points = Vec::new();
+        let mut edges = Vec::new();
+        for ring in polygons.polygons().iter().flat_map(|polygon| polygon.rings()) {
+            let start = points.len();
+            points.extend_from_slice(ring);
+            edges.extend((0..ring.len()).map(|i| [start + i, start + (i + 1) % ring.len()]));
+        }
+        let mut builder = Builder::new(&points);
+        for &[a, b] in &edges {
+            builder.insert_constraint(a, b)?;
+        }
+        let inside = builder.inside_constraints();
+        Some(builder.into_mesh(&points, Some(&inside)))
+    }
+    pub fn points(&self) -> &[Point2d<f64>] {
+        &self.points
+    }
+    /// Returns the point indices of every triangle, in counter-clockwise order.
+    pub fn triangles(&self) -> &[[usize; 3]] {
+        &self.triangles
+    }
+    /// Returns for every triangle the neighbors across its edges: entry `i` is the
+    /// triangle sharing the edge from corner `i` to corner `(i + 1) % 3`.
+    pub fn neighbors(&self) -> &[[Option<usize>; 3]] {
+        &self.neighbors
+    }
+    pub fn len(&self) -> usize {
+        self.triangles.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.triangles.is_empty()
+    }
+    /// Returns the corners of triangle `index`.
+    pub fn triangle(&self, index: usize) -> [Point2d<f64>; 3] {
+        self.triangles[index].map(|i| self.points[i])
+    }
+    /// Returns true if the edge from corner `corner` of triangle `index` to the next
+    /// corner is a constraint edge.
+    pub fn is_constrained(&self, index: usize, corner: usize) -> bool {
+        self.constrained[index][corner]
+    }
+    /// Returns every edge once, as point index pairs.
+    pub fn edges(&self) -> Vec<[usize; 2]> {
+        let mut edges = Vec::new();
+        for (t, triangle) in self.triangles.iter().enumerate() {
+            for i in 0..3 {
+                // Inner edges are listed by the lower numbered of their two triangles
+                if self.neighbors[t][i].is_none_or(|other| other > t) {
+                    edges.push([triangle[i], triangle[(i + 1) % 3]]);
+                }
+            }
+        }
+        edges
+    }
+}
@@ -0,0 +1,208 @@
+use line2d::Line2d;
+use num_traits::Float;
+use point2d::Point2d;
+use vector2d::Vector2d;
+
+/// An ellipse given by its center, its radii along its own axes and the rotation of its
+/// first axis from the x-axis, in radians, as in the arcs of `path2d`.
+///
+/// Angles passed to `point_at_angle` and `tangent_at_angle` are parametric: the point at
+/// angle `t` is `(radii.x cos t, radii.y sin t)` in the ellipse's own axes.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Ellipse2d<T> {
+    pub center: Point2d<T>,
+    pub radii: Vector2d<T>,
+    pub rotation: T,
+}
+
+impl<T: Float> Ellipse2d<T> {
+    /// Returns the ellipse, or None unless both radii are positive and finite.
+    pub fn new(center: Point2d<T>, radii: Vector2d<T>, rotation: T) -> Option<Self> {
+        let valid = |r: T| r > T::zero() && r.is_finite();
+        (valid(radii.x) && valid(radii.y)).then_some(Ellipse2d { center, radii, rotation })
+    }
+    pub fn circle(center: Point2d<T>, radius: T) -> Option<Self> {
+        Self::new(center, Vector2d::new(radius, radius), T::zero())
+    }
+    pub fn semi_major(&self) -> T {
+        self.radii.x.max(self.radii.y)
+    }
+    pub fn semi_minor(&self) -> T {
+        self.radii.x.min(self.radii.y)
+    }
+    pub fn eccentricity(&self) -> T {
+        let ratio = self.semi_minor() / self.semi_major();
+        (T::one() - ratio * ratio).sqrt()
+    }
+    pub fn area(&self) -> T {
+        T::from(std::f64::consts::PI).unwrap() * self.radii.x * self.radii.y
+    }
+    /// Returns the perimeter to full precision, by the arithmetic-geometric mean.
+    pub fn perimeter(&self) -> T {
+        let two = T::one() + T::one();
+        let (a, b) = (self.semi_major(), self.semi_minor());
+        // P = 2 pi / agm(a, b) * (a^2 - sum 2^(n - 1) c_n^2), with c_0^2 = a^2 - b^2
+        let (mut an, mut bn) = (a, b);
+        let (mut sum, mut weight) = ((a * a - b * b) / two, T::one());
+        for _ in 0..64 {
+            let c = (an - bn) / two;
+            (an, bn) = ((an + bn) / two, (an * bn).sqrt());
+            sum = sum + weight * c * c;
+            weight = weight * two;
+            if c <= T::epsilon() * an {
+                break;
+            }
+        }
+        two * T::from(std::f64::consts::PI).unwrap() / an * (a * a - sum)
+    }
+    /// Returns Ramanujan's second approximation of the perimeter, whose relative error is
+    /// below 1e-9 up to a 2:1 ratio of the radii and at most about 4e-4 for a flat ellipse.
+    pub fn perimeter_ramanujan(&self) -> T {
+        let (a, b) = (self.radii.x, self.radii.y);
+        let h = ((a - b) / (a + b)).powi(2);
+        let three = T::from(3.0).unwrap();
+        let series = T::one() + three * h / (T::from(10.0).unwrap() + (T::from(4.0).unwrap() - three * h).sqrt());
+        T::from(std::f64::consts::PI).unwrap() * (a + b) * series
+    }
+    /// Returns the perimeter from the first `terms` terms of the Gauss-Kummer series
+    /// `pi (a + b) sum binomial(1/2, n)^2 h^n`, with `h = ((a - b) / (a + b))^2`. Few terms
+    /// suffice for a nearly circular ellipse; flat ones need many.
+    pub fn perimeter_series(&self, terms: usize) -> T {
+        let (a, b) = (self.radii.x, self.radii.y);
+        let h = ((a - b) / (a + b)).powi(2);
+        let half = T::from(0.5).unwrap();
+        let (mut sum, mut coefficient, mut power) = (T::zero(), T::one(), T::one());
+        for n in 0..terms {
+            if n > 0 {
+                let n = T::from(n).unwrap();
+                coefficient = coefficient * (half - (n - T::one())) / n;
+                power = power * h;
+            }
+            sum = sum + coefficient * coefficient * power;
+        }
+        T::from(std::f64::consts::PI).unwrap() * (a + b) * sum
+    }
+    /// Returns the foci, on the major axis on either side of the center.
+    pub fn foci(&self) -> [Point2d<T>; 2] {
+        let distance = (self.radii.x * self.radii.x - self.radii.y * self.radii.y).abs().sqrt();
+        let (sin, cos) = self.rotation.sin_cos();
+        // The major axis is the second one if that radius is larger
+        let (dx, dy) = if self.radii.x >= self.radii.y { (cos, sin) } else { (-sin, cos) };
+        let (dx, dy) = (dx * distance, dy * distance);
+        [
+            Point2d::new(self.center.x() - dx, self.center.y() - dy),
+            Point2d::new(self.center.x() + dx, self.center.y() + dy),
+        ]
+    }
+    /// Maps a point of the ellipse's own axes, relative to its center, to the plane.
+    fn in_plane(&self, x: T, y: T) -> Point2d<T> {
+        let (sin, cos) = self.rotation.sin_cos();
+        Point2d::new(self.center.x() + x * cos - y * sin, self.center.y() + x * sin + y * cos)
+    }
+    /// Maps a point of the plane to the ellipse's own axes, scaled so the ellipse becomes the
+    /// unit circle.
+    fn unit_coordinates(&self, point: &Point2d<T>) -> (T, T) {
+        let (sin, cos) = self.rotation.sin_cos();
+        let (dx, dy) = (point.x() - self.center.x(), point.y() - self.center.y());
+        ((dx * cos + dy * sin) / self.radii.x, (dy * cos - dx * sin) / self.radii.y)
+    }
+    pub fn point_at_angle(&self, angle: T) -> Point2d<T> {
+        let (sin, cos) = angle.sin_cos();
+        self.in_plane(self.radii.x * cos, self.radii.y * sin)
+    }
+    /// Returns the unit tangent at the given parametric angle, pointing counter-clockwise.
+    pub fn tangent_at_angle(&self, angle: T) -> Vector2d<T> {
+        let (sin, cos) = angle.sin_cos();
+        let (x, y) = (-self.radii.x * sin, self.radii.y * cos);
+        let length = x.hypot(y);
+        let (rs, rc) = self.rotation.sin_cos();
+        Vector2d::new((x * rc - y * rs) / length, (x * rs + y * rc) / length)
+    }
+    /// Returns true if the point is inside the ellipse or on its boundary.
+    pub fn contains(&self, point: &Point2d<T>) -> bool {
+        let (x, y) = self.unit_coordinates(point);
+        x * x + y * y <= T::one()
+    }
+    /// Returns where the infinite line through the segment crosses the ellipse, as the
+    /// parameters along the segment and the points, ordered along it. A tangent line gives one
+    /// point and a degenerate segment none.
+    pub fn line_intersections(&self, line: &Line2d<T>) -> Vec<(T, Point2d<T>)> {
+        // In the ellipse's scaled axes the line meets the unit circle: |p + t d|^2 = 1
+        let (px, py) = self.unit_coordinates(&line.pt1);
+        let (qx, qy) = self.unit_coordinates(&line.pt2);
+        let (dx, dy) = (qx - px, qy - py);
+        let a = dx * dx + dy * dy;
+        if a.is_zero() {
+            return Vec::new();
+        }
+        let half_b = px * dx + py * dy;
+        let discriminant = half_b * half_b - a * (px * px + py * py - T::one());
+        let at = |t: T| (t, line.point_at(t));
+        if discriminant < T::zero() {
+            Vec::new()
+        } else if discriminant.is_zero() {
+            vec![at(-half_b / a)]
+        } else {
+            let root = discriminant.sqrt();
+            vec![at((-half_b - root) / a), at((-half_b + root) / a)]
+        }
+    }
+    /// Returns where the segment crosses the ellipse, as for `line_intersections`.
+    pub fn intersections(&self, line: &Line2d<T>) -> Vec<(T, Point2d<T>)> {
+        let mut intersections = self.line_intersections(line);
+        intersections.retain(|(t, _)| *t >= T::zero() && *t <= T::one());
+        intersections
+    }
+    /// Returns the exact axis-aligned bounding box as `(min, max)`.
+    pub fn bounds(&self) -> (Point2d<T>, Point2d<T>) {
+        let (sin, cos) = self.rotation.sin_cos();
+        let half_width = (self.radii.x * cos).hypot(self.radii.y * sin);
+        let half_height = (self.radii.x * sin).hypot(self.radii.y * cos);
+        (
+            Point2d::new(self.center.x() - half_width, self.center.y() - half_height),
+            Point2d::new(self.center.x() + half_width, self.center.y() + half_height),
+        )
+    }
+    /// Returns the coefficients `[a, b, c, d, e, f]` of the implicit form
+    /// `a x^2 + b xy + c y^2 + d x + e y + f = 0`, scaled so that `a + c = 1`; the left side
+    /// is negative inside the ellipse.
+    pub fn to_conic(&self) -> [T; 6] {
+        let two = T::one() + T::one();
+        let (sin, cos) = self.rotation.sin_cos();
+        let (u, v) = ((self.radii.x * self.radii.x).recip(), (self.radii.y * self.radii.y).recip());
+        let (a, b, c) = (cos * cos * u + sin * sin * v, two * cos * sin * (u - v), sin * sin * u + cos * cos * v);
+        let (x, y) = (self.center.x(), self.center.y());
+        let conic = [a, b, c, -two * a * x - b * y, -b * x - two * c * y, a * x * x + b * x * y + c * y * y - T::one()];
+        conic.map(|value| value / (u + v))
+    }
+    /// Returns the ellipse with the given implicit form, with the major axis first, or None if
+    /// the conic is not a real ellipse. The coefficients may have any scale.
+    ///
+    /// The implicit form itself is ill-conditioned for very flat ellipses that are turned or
+    /// far from the origin: rounding the coefficients then moves the long radius noticeably.
+    pub fn from_conic(conic: [T; 6]) -> Option<Self> {
+        let two = T::one() + T::one();
+        let scale = conic[0] + conic[2];
+        if scale.is_zero() {
+            return None;
+        }
+        let [a, b, c, d, e, f] = conic.map(|value| value / scale);
+        let determinant = T::from(4.0).unwrap() * a * c - b * b;
+        if determinant <= T::zero() {
+            return None;
+        }
+        let center = Point2d::new((b * e - two * c * d) / determinant, (b * d - two * a * e) / determinant);
+        let value = (d * center.x() + e * center.y()) / two + f;
+        // Eigenvalues of [[a, b / 2], [b / 2, c]]; the major axis belongs to the smaller
+        let mean = (a + c) / two;
+        let radius = ((a - c) / two).hypot(b / two);
+        // The product of the eigenvalues is determinant / 4, which gives the smaller one
+        // without the cancellation of mean - radius for flat ellipses
+        let large = mean + radius;
+        let small = determinant / (T::from(4.0).unwrap() * large);
+        let radii = Vector2d::new((-value / small).sqrt(), (-value / large).sqrt());
+        // The eigenvector of the larger eigenvalue is at half the angle of (a - c, b)
+        let rotation = b.atan2(a - c) / two + T::from(std::f64::consts::FRAC_PI_2).unwrap();
+        Self::new(center, radii, rotation)
+    }
+}
@@ -0,0 +1,79 @@
+use num_traits::Num;
+use point2d::Point2d;
+use vector2d::Vector2d;
+
+use crate::{Line2d, LineIntersection};
+
+// Methods built only from ring operations and division, so they give exact results for
+// rational components such as `BigRational`. They make no allowance for rounding; with
+// floats, use `closest_point` and `intersection` instead.
+impl<T: Clone + Num + PartialOrd> Line2d<T> {
+    fn direction(&self) -> Vector2d<T> {
+        &self.pt2.0 - &self.pt1.0
+    }
+    fn at(&self, t: &T) -> Point2d<T> {
+        Point2d(&self.pt1.0 + &(&self.direction() * t.clone()))
+    }
+    fn in_unit_range(t: &T) -> bool {
+        *t >= T::zero() && *t <= T::one()
+    }
+    /// Returns the parameter `t` in `[0, 1]` of the point of the segment closest to `point`,
+    /// together with that point, without rounding.
+    pub fn exact_closest_point(&self, point: &Point2d<T>) -> (T, Point2d<T>) {
+        let direction = self.direction();
+        let length_squared = direction.length_squared();
+        if length_squared.is_zero() {
+            return (T::zero(), self.pt1.clone());
+        }
+        let t = (&point.0 - &self.pt1.0).dot(&direction) / length_squared;
+        let t = if t < T::zero() { T::zero() } else if t > T::one() { T::one() } else { t };
+        let closest = self.at(&t);
+        (t, closest)
+    }
+    /// Returns the single point where both segments meet, or `None` if they do not meet or
+    /// overlap along a common stretch.
+    ///
+    /// Unlike `intersection` there is no tolerance: segments that are parallel by even the
+    /// smallest margin still get their exact crossing point.
+    pub fn exact_intersection(&self, other: &Line2d<T>) -> Option<LineIntersection<T>> {
+        let (r, s) = (self.direction(), other.direction());
+        let q = &other.pt1.0 - &self.pt1.0;
+        let denom = r.cross(&s);
+        if !denom.is_zero() {
+            let t = q.cross(&s) / denom.clone();
+            let u = q.cross(&r) / denom;
+            if !Self::in_unit_range(&t) || !Self::in_unit_range(&u) {
+                return None;
+            }
+            let point = self.at(&t);
+            return Some(LineIntersection { t, u, point });
+        }
+        if !q.cross(&r).is_zero() || !q.cross(&s).is_zero() {
+            return None; // Parallel on distinct lines
+        }
+        // Collinear, or one of them is a single point: collect the end points lying on the
+        // other segment and accept them only if they are all the same point
+        let on = |segment: &Line2d<T>, point: &Point2d<T>| {
+            let (t, closest) = segment.exact_closest_point(point);
+            (closest == *point).then_some(t)
+        };
+        let mut shared: Vec<(T, T, Point2d<T>)> = Vec::new();
+        for point in [&self.pt1, &self.pt2] {
+            if let Some(u) = on(other, point) {
+                let t = if *point == self.pt1 { T::zero() } else { T::one() };
+                shared.push((t, u, point.clone()));
+            }
+        }
+        for point in [&other.pt1, &other.pt2] {
+            if let Some(t) = on(self, point) {
+                let u = if *point == other.pt1 { T::zero() } else { T::one() };
+                shared.push((t, u, point.clone()));
+            }
+        }
+        let (t, u, point) = shared.first()?.clone();
+        if shared.iter().any(|(_, _, p)| *p != point) {
+            return None;
+        }
+        Some(LineIntersection { t, u, point })
+    }
+}
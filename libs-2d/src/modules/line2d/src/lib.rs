@@ -2,10 +2,24 @@ use point2d::Point2d;
 use num_traits::{Float, Zero};
 use vector2d::Vector2d;
 
+mod exact;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Line2d<T> {
     pub pt1: Point2d<T>,
     pub pt2: Point2d<T>,
 }
+/// A point where two segments meet.
+///
+/// `t` is the parameter along the first segment and `u` the parameter along
+/// the second one, both in `[0, 1]`, so that `first.point_at(t)` and
+/// `second.point_at(u)` both give `point`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LineIntersection<T> {
+    pub t: T,
+    pub u: T,
+    pub point: Point2d<T>,
+}
 impl<T: Copy + Clone + Zero + Float> Line2d<T> {
     pub fn new(pt1: Point2d<T>, pt2: Point2d<T>) -> Self {
         Line2d { pt1, pt2 }
@@ -369,6 +383,117 @@ where T: Copy + Clone + Zero + Float,
         }
         degrees
     }
+    /// Returns the parameter `t` in `[0, 1]` of the point of the segment closest to `point`,
+    /// together with that point.
+    pub fn closest_point(&self, point: &Point2d<T>) -> (T, Point2d<T>) {
+        let length_squared = self.length_squared();
+        if length_squared.is_zero() {
+            return (T::zero(), self.pt1); // The segment is a single point
+        }
+        let t = ((point.x() - self.x1()) * self.dx() + (point.y() - self.y1()) * self.dy()) / length_squared;
+        let t = t.max(T::zero()).min(T::one());
+        (t, Point2d::new(self.x1() + self.dx() * t, self.y1() + self.dy() * t))
+    }
+    /// Returns the distance from `point` to the closest point of the segment.
+    pub fn distance_to_point(&self, point: &Point2d<T>) -> T {
+        let (_, closest) = self.closest_point(point);
+        closest.distance_to(point)
+    }
+    /// Returns the intersection of the infinite lines through both segments,
+    /// or `None` if they are parallel.
+    ///
+    /// The parameters of the returned intersection are not limited to `[0, 1]`.
+    pub fn line_intersection(&self, other: &Line2d<T>) -> Option<LineIntersection<T>> {
+        let denom = self.cross(other);
+        let epsilon = T::from(1e-10).unwrap_or(T::zero());
+        if denom.abs() <= epsilon * self.length() * other.length() {
+            return None; // Parallel or degenerate lines
+        }
+        let qx = other.x1() - self.x1();
+        let qy = other.y1() - self.y1();
+        let t = (qx * other.dy() - qy * other.dx()) / denom;
+        let u = (qx * self.dy() - qy * self.dx()) / denom;
+        Some(LineIntersection {
+            t,
+            u,
+            point: Point2d::new(self.x1() + self.dx() * t, self.y1() + self.dy() * t),
+        })
+    }
+    /// Returns the single point where both segments cross, or `None` if they do
+    /// not meet or overlap along a common stretch.
+    pub fn intersection(&self, other: &Line2d<T>) -> Option<LineIntersection<T>> {
+        let intersections = self.intersections(other);
+        if intersections.len() == 1 {
+            Some(intersections[0])
+        } else {
+            None
+        }
+    }
+    /// Returns every point shared by both segments.
+    ///
+    /// The result is empty if the segments are disjoint, holds one entry if they
+    /// cross or touch, and holds the two end points of the shared stretch
+    /// (ordered along `self`) if they are collinear and overlap.
+    pub fn intersections(&self, other: &Line2d<T>) -> Vec<LineIntersection<T>> {
+        let epsilon = T::from(1e-10).unwrap_or(T::zero());
+        let scale = self.length().max(other.length()).max(T::one());
+        let tolerance = epsilon * scale;
+        if self.is_point() || other.is_point() {
+            // Degenerate segments reduce to a point-on-segment test
+            let (point, segment, point_is_self) = if self.is_point() {
+                (self.pt1, other, true)
+            } else {
+                (other.pt1, self, false)
+            };
+            let (s, closest) = segment.closest_point(&point);
+            if closest.distance_to(&point) > tolerance {
+                return Vec::new();
+            }
+            let (t, u) = if point_is_self { (T::zero(), s) } else { (s, T::zero()) };
+            return vec![LineIntersection { t, u, point }];
+        }
+        if let Some(hit) = self.line_intersection(other) {
+            let t_tolerance = tolerance / self.length();
+            let u_tolerance = tolerance / other.length();
+            if hit.t < -t_tolerance || hit.t > T::one() + t_tolerance
+                || hit.u < -u_tolerance || hit.u > T::one() + u_tolerance {
+                return Vec::new();
+            }
+            let t = hit.t.max(T::zero()).min(T::one());
+            let u = hit.u.max(T::zero()).min(T::one());
+            return vec![LineIntersection { t, u, point: self.point_at(t) }];
+        }
+        // Parallel segments only meet if they lie on the same line
+        if other.distance_to_line(&self.pt1) > tolerance {
+            return Vec::new();
+        }
+        let length_squared = self.length_squared();
+        let project = |p: &Point2d<T>| ((p.x() - self.x1()) * self.dx() + (p.y() - self.y1()) * self.dy()) / length_squared;
+        let t_a = project(&other.pt1);
+        let t_b = project(&other.pt2);
+        let t_start = t_a.min(t_b).max(T::zero());
+        let t_end = t_a.max(t_b).min(T::one());
+        if t_start > t_end + tolerance / self.length() {
+            return Vec::new();
+        }
+        let to_intersection = |t: T| {
+            let point = self.point_at(t.max(T::zero()).min(T::one()));
+            let (u, _) = other.closest_point(&point);
+            LineIntersection { t, u, point }
+        };
+        if t_end - t_start <= tolerance / self.length() {
+            return vec![to_intersection(t_start)];
+        }
+        vec![to_intersection(t_start), to_intersection(t_end)]
+    }
+    /// Returns the distance from `point` to the infinite line through the segment.
+    pub fn distance_to_line(&self, point: &Point2d<T>) -> T {
+        let length = self.length();
+        if length.is_zero() {
+            return self.pt1.distance_to(point);
+        }
+        ((point.x() - self.x1()) * self.dy() - (point.y() - self.y1()) * self.dx()).abs() / length
+    }
     pub fn angle_to_line(&self, other: &Line2d<T>) -> (T, bool) {
         let angle_self = self.angle();
         let angle_other = other.angle();
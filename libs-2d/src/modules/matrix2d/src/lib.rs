@@ -0,0 +1,197 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+use num_traits::Float;
+use vector2d::Vector2d;
+
+pub mod linalg;
+
+/// A 2x2 matrix `[[m11, m12], [m21, m22]]`, acting on column vectors.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Matrix2<T> {
+    pub m11: T,
+    pub m12: T,
+    pub m21: T,
+    pub m22: T,
+}
+
+/// Eigenvalues of a symmetric matrix, the larger first, with the matching unit eigenvectors
+/// as the columns of a rotation.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SymmetricEigen<T> {
+    pub values: [T; 2],
+    pub vectors: Matrix2<T>,
+}
+
+/// Singular value decomposition `m = u * diag(singular_values) * v.transpose()`, with the
+/// larger singular value first and both non-negative.
+///
+/// `v` is a rotation; `u` is a rotation if the determinant of `m` is non-negative and a
+/// reflection otherwise.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Svd<T> {
+    pub u: Matrix2<T>,
+    pub singular_values: [T; 2],
+    pub v: Matrix2<T>,
+}
+
+impl<T: Float> Matrix2<T> {
+    pub fn new(m11: T, m12: T, m21: T, m22: T) -> Self {
+        Matrix2 { m11, m12, m21, m22 }
+    }
+    pub fn from_rows(row1: Vector2d<T>, row2: Vector2d<T>) -> Self {
+        Matrix2::new(row1.x, row1.y, row2.x, row2.y)
+    }
+    pub fn from_columns(column1: Vector2d<T>, column2: Vector2d<T>) -> Self {
+        Matrix2::new(column1.x, column2.x, column1.y, column2.y)
+    }
+    pub fn identity() -> Self {
+        Matrix2::diagonal(T::one(), T::one())
+    }
+    pub fn zero() -> Self {
+        Matrix2::diagonal(T::zero(), T::zero())
+    }
+    pub fn diagonal(m11: T, m22: T) -> Self {
+        Matrix2::new(m11, T::zero(), T::zero(), m22)
+    }
+    /// Returns the counter-clockwise rotation by `angle` radians.
+    pub fn rotation(angle: T) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Matrix2::new(cos, -sin, sin, cos)
+    }
+    pub fn row(&self, index: usize) -> Vector2d<T> {
+        if index == 0 { Vector2d::new(self.m11, self.m12) } else { Vector2d::new(self.m21, self.m22) }
+    }
+    pub fn column(&self, index: usize) -> Vector2d<T> {
+        if index == 0 { Vector2d::new(self.m11, self.m21) } else { Vector2d::new(self.m12, self.m22) }
+    }
+    pub fn determinant(&self) -> T {
+        self.m11 * self.m22 - self.m12 * self.m21
+    }
+    pub fn trace(&self) -> T {
+        self.m11 + self.m22
+    }
+    pub fn transpose(&self) -> Self {
+        Matrix2::new(self.m11, self.m21, self.m12, self.m22)
+    }
+    /// Returns the inverse, or None if the matrix is singular.
+    pub fn inverse(&self) -> Option<Self> {
+        let determinant = self.determinant();
+        if determinant.is_zero() {
+            return None;
+        }
+        let inverse = Matrix2::new(self.m22, -self.m12, -self.m21, self.m11) * determinant.recip();
+        inverse.is_finite().then_some(inverse)
+    }
+    pub fn is_finite(&self) -> bool {
+        self.m11.is_finite() && self.m12.is_finite() && self.m21.is_finite() && self.m22.is_finite()
+    }
+    pub fn mul_vector(&self, vector: &Vector2d<T>) -> Vector2d<T> {
+        Vector2d::new(self.m11 * vector.x + self.m12 * vector.y, self.m21 * vector.x + self.m22 * vector.y)
+    }
+    /// Returns `x` with `self * x = rhs`, or None if the matrix is singular.
+    pub fn solve(&self, rhs: &Vector2d<T>) -> Option<Vector2d<T>> {
+        // Gaussian elimination, pivoting on the row with the larger first entry
+        let (pivot, other, b1, b2) = if self.m21.abs() > self.m11.abs() {
+            (self.row(1), self.row(0), rhs.y, rhs.x)
+        } else {
+            (self.row(0), self.row(1), rhs.x, rhs.y)
+        };
+        if pivot.x.is_zero() {
+            return None;
+        }
+        let factor = other.x / pivot.x;
+        let remaining = other.y - factor * pivot.y;
+        if remaining.is_zero() {
+            return None;
+        }
+        let y = (b2 - factor * b1) / remaining;
+        let x = (b1 - pivot.y * y) / pivot.x;
+        (x.is_finite() && y.is_finite()).then(|| Vector2d::new(x, y))
+    }
+    /// Returns the eigen-decomposition of the symmetric part `(m + m.transpose()) / 2`.
+    pub fn symmetric_eigen(&self) -> SymmetricEigen<T> {
+        let two = T::from(2.0).unwrap();
+        let mean = (self.m11 + self.m22) / two;
+        let (half_difference, off_diagonal) = ((self.m11 - self.m22) / two, (self.m12 + self.m21) / two);
+        let radius = half_difference.hypot(off_diagonal);
+        // The first eigenvector is at half the angle of (half_difference, off_diagonal)
+        let angle = off_diagonal.atan2(half_difference) / two;
+        SymmetricEigen { values: [mean + radius, mean - radius], vectors: Matrix2::rotation(angle) }
+    }
+    /// Returns the singular value decomposition.
+    pub fn svd(&self) -> Svd<T> {
+        // The matrix splits into a scaled rotation [[e, -h], [h, e]] and a scaled reflection
+        // [[f, g], [g, -f]]; their magnitudes add up to the singular values
+        let two = T::from(2.0).unwrap();
+        let (e, f) = ((self.m11 + self.m22) / two, (self.m11 - self.m22) / two);
+        let (g, h) = ((self.m21 + self.m12) / two, (self.m21 - self.m12) / two);
+        let (q, r) = (e.hypot(h), f.hypot(g));
+        let (a1, a2) = (g.atan2(f), h.atan2(e));
+        let (theta, phi) = ((a2 - a1) / two, (a2 + a1) / two);
+        let mut u = Matrix2::rotation(phi);
+        let v = Matrix2::rotation(-theta);
+        let second = q - r;
+        if second < T::zero() {
+            u.m12 = -u.m12;
+            u.m22 = -u.m22;
+        }
+        Svd { u, singular_values: [q + r, second.abs()], v }
+    }
+    /// Returns the polar decomposition `self = rotation * stretch`, where `stretch` is
+    /// symmetric positive semi-definite and `rotation` is orthogonal: a rotation if the
+    /// determinant is non-negative and a reflection otherwise.
+    pub fn polar(&self) -> (Self, Self) {
+        let Svd { u, singular_values: [s1, s2], v } = self.svd();
+        let stretch = v * Matrix2::diagonal(s1, s2) * v.transpose();
+        // Rounding leaves the off-diagonal entries a unit apart; average them
+        let off_diagonal = (stretch.m12 + stretch.m21) / T::from(2.0).unwrap();
+        (u * v.transpose(), Matrix2::new(stretch.m11, off_diagonal, off_diagonal, stretch.m22))
+    }
+}
+
+impl<T: Float> Add for Matrix2<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Matrix2::new(self.m11 + rhs.m11, self.m12 + rhs.m12, self.m21 + rhs.m21, self.m22 + rhs.m22)
+    }
+}
+
+impl<T: Float> Sub for Matrix2<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Matrix2::new(self.m11 - rhs.m11, self.m12 - rhs.m12, self.m21 - rhs.m21, self.m22 - rhs.m22)
+    }
+}
+
+impl<T: Float> Neg for Matrix2<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Matrix2::new(-self.m11, -self.m12, -self.m21, -self.m22)
+    }
+}
+
+impl<T: Float> Mul for Matrix2<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Matrix2::new(
+            self.m11 * rhs.m11 + self.m12 * rhs.m21,
+            self.m11 * rhs.m12 + self.m12 * rhs.m22,
+            self.m21 * rhs.m11 + self.m22 * rhs.m21,
+            self.m21 * rhs.m12 + self.m22 * rhs.m22,
+        )
+    }
+}
+
+impl<T: Float> Mul<T> for Matrix2<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self {
+        Matrix2::new(self.m11 * rhs, self.m12 * rhs, self.m21 * rhs, self.m22 * rhs)
+    }
+}
+
+impl<T: Float> Mul<Vector2d<T>> for Matrix2<T> {
+    type Output = Vector2d<T>;
+    fn mul(self, rhs: Vector2d<T>) -> Vector2d<T> {
+        self.mul_vector(&rhs)
+    }
+}
@@ -0,0 +1,137 @@
+//! Small dense systems and eigenproblems, on fixed-size arrays of rows.
+
+use std::cmp::Ordering;
+
+use num_traits::Float;
+
+/// Solves `a x = b` by Gaussian elimination with partial pivoting, or returns None if `a` is
+/// singular.
+pub fn solve3<T: Float>(mut a: [[T; 3]; 3], mut b: [T; 3]) -> Option<[T; 3]> {
+    for column in 0..3 {
+        let size = |i: usize| a[i][column].abs();
+        let pivot = (column..3).max_by(|&i, &j| size(i).partial_cmp(&size(j)).unwrap_or(Ordering::Equal))?;
+        a.swap(column, pivot);
+        b.swap(column, pivot);
+        if a[column][column].is_zero() {
+            return None;
+        }
+        for row in column + 1..3 {
+            let factor = a[row][column] / a[column][column];
+            let pivot_row = a[column];
+            for (value, pivot_value) in a[row].iter_mut().zip(pivot_row).skip(column) {
+                *value = *value - factor * pivot_value;
+            }
+            b[row] = b[row] - factor * b[column];
+        }
+    }
+    let mut x = [T::zero(); 3];
+    for row in (0..3).rev() {
+        let known = (row + 1..3).fold(T::zero(), |sum, k| sum + a[row][k] * x[k]);
+        x[row] = (b[row] - known) / a[row][row];
+    }
+    x.iter().all(|v| v.is_finite()).then_some(x)
+}
+
+/// Returns the eigenvalues of a symmetric matrix and the unit eigenvectors as the columns of
+/// the second result, using cyclic Jacobi rotations.
+pub fn symmetric_eigen<T: Float, const N: usize>(mut a: [[T; N]; N]) -> ([T; N], [[T; N]; N]) {
+    let mut vectors = [[T::zero(); N]; N];
+    for (i, row) in vectors.iter_mut().enumerate() {
+        row[i] = T::one();
+    }
+    let total: T = a.iter().flatten().fold(T::zero(), |sum, &x| sum + x * x);
+    for _ in 0..64 {
+        let off = a.iter().enumerate().flat_map(|(p, row)| &row[p + 1..]).fold(T::zero(), |sum, &x| sum + x * x);
+        if off <= T::epsilon() * T::epsilon() * total {
+            break;
+        }
+        for p in 0..N {
+            for q in p + 1..N {
+                if a[p][q].is_zero() {
+                    continue;
+                }
+                // The rotation by atan(t) in the p-q plane that zeroes a[p][q]
+                let theta = (a[q][q] - a[p][p]) / (a[p][q] + a[p][q]);
+                let t = theta.signum() / (theta.abs() + theta.hypot(T::one()));
+                let c = t.hypot(T::one()).recip();
+                let s = t * c;
+                for k in 0..N {
+                    let (kp, kq) = (a[k][p], a[k][q]);
+                    a[k][p] = c * kp - s * kq;
+                    a[k][q] = s * kp + c * kq;
+                    let (kp, kq) = (vectors[k][p], vectors[k][q]);
+                    vectors[k][p] = c * kp - s * kq;
+                    vectors[k][q] = s * kp + c * kq;
+                }
+                let (row_p, row_q) = (a[p], a[q]);
+                a[p] = std::array::from_fn(|k| c * row_p[k] - s * row_q[k]);
+                a[q] = std::array::from_fn(|k| s * row_p[k] + c * row_q[k]);
+            }
+        }
+    }
+    let mut values = [T::zero(); N];
+    for (i, value) in values.iter_mut().enumerate() {
+        *value = a[i][i];
+    }
+    (values, vectors)
+}
+
+/// Returns the unit vector `x` minimizing `x^T a x` for a symmetric positive semi-definite `a`.
+pub fn smallest_eigenvector<T: Float, const N: usize>(a: [[T; N]; N]) -> [T; N] {
+    let (values, vectors) = symmetric_eigen(a);
+    let smallest = (0..N).fold(0, |best, i| if values[i] < values[best] { i } else { best });
+    let mut x = [T::zero(); N];
+    for (k, value) in x.iter_mut().enumerate() {
+        *value = vectors[k][smallest];
+    }
+    x
+}
+
+/// Returns the real eigenvalues of `m` with a unit eigenvector for each.
+pub fn real_eigenvectors(m: [[f64; 3]; 3]) -> Vec<(f64, [f64; 3])> {
+    // The characteristic polynomial is l^3 - trace l^2 + minors l - det
+    let trace = m[0][0] + m[1][1] + m[2][2];
+    let minors = m[0][0] * m[1][1] - m[0][1] * m[1][0] + m[0][0] * m[2][2] - m[0][2] * m[2][0] + m[1][1] * m[2][2]
+        - m[1][2] * m[2][1];
+    let det = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1]) - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+    cubic_roots(-trace, minors, -det)
+        .into_iter()
+        .filter_map(|value| {
+            // The eigenvector is orthogonal to the rows of m - l I; take the longest cross
+            // product of two of them
+            let mut rows = m;
+            (0..3).for_each(|i| rows[i][i] -= value);
+            let cross = |a: [f64; 3], b: [f64; 3]| {
+                [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+            };
+            let norm = |v: &[f64; 3]| v.iter().map(|x| x * x).sum::<f64>().sqrt();
+            let vector = [cross(rows[0], rows[1]), cross(rows[0], rows[2]), cross(rows[1], rows[2])]
+                .into_iter()
+                .max_by(|a, b| norm(a).total_cmp(&norm(b)))?;
+            let length = norm(&vector);
+            (length > 0.0).then(|| (value, vector.map(|x| x / length)))
+        })
+        .collect()
+}
+
+/// Returns the real roots of `x^3 + a x^2 + b x + c`.
+fn cubic_roots(a: f64, b: f64, c: f64) -> Vec<f64> {
+    // Depress to t^3 + p t + q with x = t - a / 3
+    let shift = a / 3.0;
+    let p = b - a * shift;
+    let q = 2.0 * shift * shift * shift - b * shift + c;
+    let discriminant = (q / 2.0).powi(2) + (p / 3.0).powi(3);
+    let roots = if discriminant > 0.0 {
+        let root = discriminant.sqrt();
+        vec![(-q / 2.0 + root).cbrt() + (-q / 2.0 - root).cbrt()]
+    } else if p == 0.0 {
+        vec![0.0]
+    } else {
+        // Three real roots, by the trigonometric method
+        let r = (-p / 3.0).sqrt();
+        let angle = (3.0 * q / (2.0 * p * r)).clamp(-1.0, 1.0).acos() / 3.0;
+        (0..3).map(|k| 2.0 * r * (angle - 2.0 * std::f64::consts::PI * k as f64 / 3.0).cos()).collect()
+    };
+    roots.into_iter().map(|t| t - shift).collect()
+}
@@ -0,0 +1,426 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::num::FpCategory;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign};
+
+use num_traits::{Float, Num, NumCast, One, Signed, ToPrimitive, Zero};
+
+/// A dual number `value + derivative ε` with `ε² = 0`, which carries the derivative of a
+/// computation with respect to one input alongside its value (forward-mode automatic
+/// differentiation).
+///
+/// Comparisons look at the value only, so code that branches on values takes the same
+/// branch as it would for plain floats. Where a function is not differentiable, such as
+/// `abs` at zero, the derivative of one side is used; at a pole it is infinite or NaN.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Dual<T> {
+    pub value: T,
+    pub derivative: T,
+}
+
+impl<T: Float> Dual<T> {
+    pub fn new(value: T, derivative: T) -> Self {
+        Dual { value, derivative }
+    }
+    /// Returns a value that does not depend on the input.
+    pub fn constant(value: T) -> Self {
+        Dual { value, derivative: T::zero() }
+    }
+    /// Returns the input being differentiated for, whose derivative is one.
+    pub fn variable(value: T) -> Self {
+        Dual { value, derivative: T::one() }
+    }
+    /// Applies a function with the given value and slope at `self.value`.
+    fn chain(self, value: T, slope: T) -> Self {
+        Dual { value, derivative: self.derivative * slope }
+    }
+}
+
+/// Returns the value and derivative of `f` at `x`.
+pub fn derivative<T: Float>(f: impl Fn(Dual<T>) -> Dual<T>, x: T) -> (T, T) {
+    let result = f(Dual::variable(x));
+    (result.value, result.derivative)
+}
+
+/// Returns the value and gradient of the scalar function `f` at `at`.
+///
+/// `f` is evaluated once per input, each time differentiating for another one.
+pub fn gradient<T: Float>(f: impl Fn(&[Dual<T>]) -> Dual<T>, at: &[T]) -> (T, Vec<T>) {
+    let (values, jacobian) = jacobian(|inputs| vec![f(inputs)], at);
+    let value = values.first().copied().unwrap_or_else(T::nan);
+    (value, jacobian.into_iter().next().unwrap_or_default())
+}
+
+/// Returns the values of the vector function `f` at `at` and its Jacobian, with one row
+/// per output and one column per input.
+pub fn jacobian<T: Float>(f: impl Fn(&[Dual<T>]) -> Vec<Dual<T>>, at: &[T]) -> (Vec<T>, Vec<Vec<T>>) {
+    let mut inputs: Vec<Dual<T>> = at.iter().map(|&x| Dual::constant(x)).collect();
+    let mut values = Vec::new();
+    let mut rows: Vec<Vec<T>> = Vec::new();
+    if at.is_empty() {
+        values = f(&inputs).iter().map(|output| output.value).collect();
+        rows = vec![Vec::new(); values.len()];
+    }
+    for column in 0..at.len() {
+        inputs[column].derivative = T::one();
+        let outputs = f(&inputs);
+        inputs[column].derivative = T::zero();
+        if column == 0 {
+            values = outputs.iter().map(|output| output.value).collect();
+            rows = vec![Vec::with_capacity(at.len()); outputs.len()];
+        }
+        for (row, output) in rows.iter_mut().zip(&outputs) {
+            row.push(output.derivative);
+        }
+    }
+    (values, rows)
+}
+
+impl<T: fmt::Display> fmt::Display for Dual<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} + {}ε", self.value, self.derivative)
+    }
+}
+
+impl<T: PartialEq> PartialEq for Dual<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for Dual<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+impl<T: Float> Add for Dual<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Dual { value: self.value + rhs.value, derivative: self.derivative + rhs.derivative }
+    }
+}
+
+impl<T: Float> Sub for Dual<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Dual { value: self.value - rhs.value, derivative: self.derivative - rhs.derivative }
+    }
+}
+
+impl<T: Float> Mul for Dual<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Dual {
+            value: self.value * rhs.value,
+            derivative: self.derivative * rhs.value + self.value * rhs.derivative,
+        }
+    }
+}
+
+impl<T: Float> Div for Dual<T> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        Dual {
+            value: self.value / rhs.value,
+            derivative: (self.derivative * rhs.value - self.value * rhs.derivative) / (rhs.value * rhs.value),
+        }
+    }
+}
+
+impl<T: Float> Rem for Dual<T> {
+    type Output = Self;
+    fn rem(self, rhs: Self) -> Self {
+        // a % b = a - b trunc(a / b), where the truncated quotient is locally constant
+        let quotient = (self.value / rhs.value).trunc();
+        Dual { value: self.value % rhs.value, derivative: self.derivative - rhs.derivative * quotient }
+    }
+}
+
+impl<T: Float> Neg for Dual<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Dual { value: -self.value, derivative: -self.derivative }
+    }
+}
+
+impl<T: Float> AddAssign for Dual<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Float> SubAssign for Dual<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Float> MulAssign for Dual<T> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T: Float> DivAssign for Dual<T> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl<T: Float> RemAssign for Dual<T> {
+    fn rem_assign(&mut self, rhs: Self) {
+        *self = *self % rhs;
+    }
+}
+
+impl<T: Float> Zero for Dual<T> {
+    fn zero() -> Self {
+        Self::constant(T::zero())
+    }
+    fn is_zero(&self) -> bool {
+        self.value.is_zero()
+    }
+}
+
+impl<T: Float> One for Dual<T> {
+    fn one() -> Self {
+        Self::constant(T::one())
+    }
+}
+
+impl<T: Float> Num for Dual<T> {
+    type FromStrRadixErr = T::FromStrRadixErr;
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        T::from_str_radix(s, radix).map(Self::constant)
+    }
+}
+
+impl<T: Float> ToPrimitive for Dual<T> {
+    fn to_i64(&self) -> Option<i64> {
+        self.value.to_i64()
+    }
+    fn to_u64(&self) -> Option<u64> {
+        self.value.to_u64()
+    }
+    fn to_f64(&self) -> Option<f64> {
+        self.value.to_f64()
+    }
+}
+
+impl<T: Float> NumCast for Dual<T> {
+    fn from<N: ToPrimitive>(n: N) -> Option<Self> {
+        <T as NumCast>::from(n).map(Self::constant)
+    }
+}
+
+impl<T: Float> Signed for Dual<T> {
+    fn abs(&self) -> Self {
+        if self.value < T::zero() { -*self } else { *self }
+    }
+    fn abs_sub(&self, other: &Self) -> Self {
+        if self.value <= other.value { Self::zero() } else { *self - *other }
+    }
+    fn signum(&self) -> Self {
+        Self::constant(Float::signum(self.value))
+    }
+    fn is_positive(&self) -> bool {
+        self.value > T::zero()
+    }
+    fn is_negative(&self) -> bool {
+        self.value < T::zero()
+    }
+}
+
+impl<T: Float> Float for Dual<T> {
+    fn nan() -> Self {
+        Self::constant(T::nan())
+    }
+    fn infinity() -> Self {
+        Self::constant(T::infinity())
+    }
+    fn neg_infinity() -> Self {
+        Self::constant(T::neg_infinity())
+    }
+    fn neg_zero() -> Self {
+        Self::constant(T::neg_zero())
+    }
+    fn min_value() -> Self {
+        Self::constant(T::min_value())
+    }
+    fn min_positive_value() -> Self {
+        Self::constant(T::min_positive_value())
+    }
+    fn epsilon() -> Self {
+        Self::constant(T::epsilon())
+    }
+    fn max_value() -> Self {
+        Self::constant(T::max_value())
+    }
+    fn is_nan(self) -> bool {
+        self.value.is_nan()
+    }
+    fn is_infinite(self) -> bool {
+        self.value.is_infinite()
+    }
+    fn is_finite(self) -> bool {
+        self.value.is_finite()
+    }
+    fn is_normal(self) -> bool {
+        self.value.is_normal()
+    }
+    fn classify(self) -> FpCategory {
+        self.value.classify()
+    }
+    fn floor(self) -> Self {
+        Self::constant(self.value.floor())
+    }
+    fn ceil(self) -> Self {
+        Self::constant(self.value.ceil())
+    }
+    fn round(self) -> Self {
+        Self::constant(self.value.round())
+    }
+    fn trunc(self) -> Self {
+        Self::constant(self.value.trunc())
+    }
+    fn fract(self) -> Self {
+        Dual { value: self.value.fract(), derivative: self.derivative }
+    }
+    fn abs(self) -> Self {
+        Signed::abs(&self)
+    }
+    fn signum(self) -> Self {
+        Signed::signum(&self)
+    }
+    fn is_sign_positive(self) -> bool {
+        self.value.is_sign_positive()
+    }
+    fn is_sign_negative(self) -> bool {
+        self.value.is_sign_negative()
+    }
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        Dual {
+            value: self.value.mul_add(a.value, b.value),
+            derivative: self.derivative * a.value + self.value * a.derivative + b.derivative,
+        }
+    }
+    fn recip(self) -> Self {
+        let recip = self.value.recip();
+        self.chain(recip, -recip * recip)
+    }
+    fn powi(self, n: i32) -> Self {
+        if n == 0 {
+            return Self::one();
+        }
+        self.chain(self.value.powi(n), T::from(n).unwrap() * self.value.powi(n - 1))
+    }
+    fn powf(self, n: Self) -> Self {
+        let value = self.value.powf(n.value);
+        let mut derivative = n.value * self.value.powf(n.value - T::one()) * self.derivative;
+        // Only a varying exponent needs the logarithm, which is NaN for negative bases
+        if !n.derivative.is_zero() {
+            derivative = derivative + value * self.value.ln() * n.derivative;
+        }
+        Dual { value, derivative }
+    }
+    fn sqrt(self) -> Self {
+        let root = self.value.sqrt();
+        self.chain(root, (root + root).recip())
+    }
+    fn exp(self) -> Self {
+        let exp = self.value.exp();
+        self.chain(exp, exp)
+    }
+    fn exp2(self) -> Self {
+        let exp2 = self.value.exp2();
+        self.chain(exp2, exp2 * T::from(2).unwrap().ln())
+    }
+    fn ln(self) -> Self {
+        self.chain(self.value.ln(), self.value.recip())
+    }
+    fn log(self, base: Self) -> Self {
+        self.ln() / base.ln()
+    }
+    fn log2(self) -> Self {
+        self.chain(self.value.log2(), (self.value * T::from(2).unwrap().ln()).recip())
+    }
+    fn log10(self) -> Self {
+        self.chain(self.value.log10(), (self.value * T::from(10).unwrap().ln()).recip())
+    }
+    fn max(self, other: Self) -> Self {
+        if other.value > self.value || self.value.is_nan() { other } else { self }
+    }
+    fn min(self, other: Self) -> Self {
+        if other.value < self.value || self.value.is_nan() { other } else { self }
+    }
+    fn abs_sub(self, other: Self) -> Self {
+        Signed::abs_sub(&self, &other)
+    }
+    fn cbrt(self) -> Self {
+        let root = self.value.cbrt();
+        self.chain(root, (T::from(3).unwrap() * root * root).recip())
+    }
+    fn hypot(self, other: Self) -> Self {
+        let value = self.value.hypot(other.value);
+        Dual { value, derivative: (self.value * self.derivative + other.value * other.derivative) / value }
+    }
+    fn sin(self) -> Self {
+        self.chain(self.value.sin(), self.value.cos())
+    }
+    fn cos(self) -> Self {
+        self.chain(self.value.cos(), -self.value.sin())
+    }
+    fn tan(self) -> Self {
+        let tan = self.value.tan();
+        self.chain(tan, T::one() + tan * tan)
+    }
+    fn asin(self) -> Self {
+        self.chain(self.value.asin(), (T::one() - self.value * self.value).sqrt().recip())
+    }
+    fn acos(self) -> Self {
+        self.chain(self.value.acos(), -(T::one() - self.value * self.value).sqrt().recip())
+    }
+    fn atan(self) -> Self {
+        self.chain(self.value.atan(), (T::one() + self.value * self.value).recip())
+    }
+    fn atan2(self, other: Self) -> Self {
+        let (y, x) = (self, other);
+        Dual {
+            value: y.value.atan2(x.value),
+            derivative: (x.value * y.derivative - y.value * x.derivative) / (x.value * x.value + y.value * y.value),
+        }
+    }
+    fn sin_cos(self) -> (Self, Self) {
+        let (sin, cos) = self.value.sin_cos();
+        (self.chain(sin, cos), self.chain(cos, -sin))
+    }
+    fn exp_m1(self) -> Self {
+        self.chain(self.value.exp_m1(), self.value.exp())
+    }
+    fn ln_1p(self) -> Self {
+        self.chain(self.value.ln_1p(), (T::one() + self.value).recip())
+    }
+    fn sinh(self) -> Self {
+        self.chain(self.value.sinh(), self.value.cosh())
+    }
+    fn cosh(self) -> Self {
+        self.chain(self.value.cosh(), self.value.sinh())
+    }
+    fn tanh(self) -> Self {
+        let tanh = self.value.tanh();
+        self.chain(tanh, T::one() - tanh * tanh)
+    }
+    fn asinh(self) -> Self {
+        self.chain(self.value.asinh(), (self.value * self.value + T::one()).sqrt().recip())
+    }
+    fn acosh(self) -> Self {
+        self.chain(self.value.acosh(), (self.value * self.value - T::one()).sqrt().recip())
+    }
+    fn atanh(self) -> Self {
+        self.chain(self.value.atanh(), (T::one() - self.value * self.value).recip())
+    }
+    fn integer_decode(self) -> (u64, i16, i8) {
+        self.value.integer_decode()
+    }
+}
@@ -0,0 +1,503 @@
+use std::fmt;
+use std::num::FpCategory;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign};
+
+use num_traits::{Float, FloatErrorKind, Num, NumCast, One, ParseFloatError, Signed, ToPrimitive, Zero};
+
+use crate::q60;
+
+/// A binary fixed-point number in Q format: the integer `I` holds the value scaled by `2^FRAC`.
+///
+/// Every operation, square roots and trigonometry included, uses integer arithmetic only, so
+/// results are bit-identical on every machine. There is no NaN or infinity: arithmetic
+/// saturates at the ends of the range, which `infinity` and `neg_infinity` return, and
+/// operations that would give NaN for floats return zero.
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fixed<I, const FRAC: u32>(I);
+
+/// Q16.16: 16 integer and 16 fraction bits in an `i32`.
+pub type Fixed32 = Fixed<i32, 16>;
+/// Q32.32: 32 integer and 32 fraction bits in an `i64`.
+pub type Fixed64 = Fixed<i64, 32>;
+
+macro_rules! impl_fixed {
+    ($int:ty) => {
+        impl<const FRAC: u32> Fixed<$int, FRAC> {
+            /// Mask of the fraction bits.
+            const FRACTION: $int = <$int>::MAX >> (<$int>::BITS - 1 - FRAC);
+
+            /// Creates a value from its raw representation, `value * 2^FRAC`.
+            pub const fn from_bits(bits: $int) -> Self {
+                const { assert!(FRAC < <$int>::BITS, "FRAC must leave room for the sign bit") };
+                const { assert!(FRAC <= q60::BITS, "FRAC is limited by the internal precision") };
+                Fixed(bits)
+            }
+            pub const fn to_bits(self) -> $int {
+                self.0
+            }
+            /// Returns the nearest fixed-point value, or None if `value` is NaN or out of range.
+            pub fn from_f64(value: f64) -> Option<Self> {
+                let scaled = (value * (1u64 << FRAC) as f64).round();
+                if scaled.is_nan() || scaled < <$int>::MIN as f64 || scaled >= -(<$int>::MIN as f64) {
+                    return None;
+                }
+                Some(Self::from_bits(scaled as $int))
+            }
+            fn value(self) -> f64 {
+                self.0 as f64 / (1u64 << FRAC) as f64
+            }
+            fn saturate(bits: i128) -> Self {
+                Self::from_bits(bits.clamp(<$int>::MIN as i128, <$int>::MAX as i128) as $int)
+            }
+            /// Returns the value `m 2^e`, rounded to nearest and saturated.
+            fn from_scaled(m: i128, e: i128) -> Self {
+                let shift = e + FRAC as i128;
+                if m == 0 {
+                    Self::zero()
+                } else if shift >= 0 {
+                    if shift >= m.unsigned_abs().leading_zeros() as i128 - 1 {
+                        Self::saturate(m.signum() * i128::MAX)
+                    } else {
+                        Self::saturate(m << shift)
+                    }
+                } else {
+                    let half = 1i128.checked_shl((-shift - 1) as u32).unwrap_or(0);
+                    Self::saturate((m + half).checked_shr(-shift as u32).unwrap_or(0))
+                }
+            }
+            fn to_q(self) -> i128 {
+                (self.0 as i128) << (q60::BITS - FRAC)
+            }
+            fn from_q(q: i128) -> Self {
+                Self::from_scaled(q, -(q60::BITS as i128))
+            }
+            /// Returns `e^x` for `x` in the internal format, which beyond 256 in magnitude
+            /// saturates or vanishes in every format.
+            fn exp_q(x: i128) -> Self {
+                let limit = 256 * q60::ONE;
+                let (m, k) = q60::exp(x.clamp(-limit, limit));
+                Self::from_scaled(m, k - q60::BITS as i128)
+            }
+            fn ln_q(self) -> Option<i128> {
+                (self.0 > 0).then(|| q60::ln(self.0 as u128, FRAC))
+            }
+            /// Returns `sqrt(1 - x^2)` for `x` in the internal format with `|x| <= 1`.
+            fn cosine_of_sine(x: i128) -> i128 {
+                ((q60::ONE * q60::ONE - x * x) as u128).isqrt() as i128
+            }
+        }
+
+        impl<const FRAC: u32> fmt::Display for Fixed<$int, FRAC> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.value(), f)
+            }
+        }
+
+        impl<const FRAC: u32> fmt::Debug for Fixed<$int, FRAC> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Debug::fmt(&self.value(), f)
+            }
+        }
+
+        impl<const FRAC: u32> Add for Fixed<$int, FRAC> {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self {
+                Fixed(self.0.saturating_add(rhs.0))
+            }
+        }
+
+        impl<const FRAC: u32> Sub for Fixed<$int, FRAC> {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self {
+                Fixed(self.0.saturating_sub(rhs.0))
+            }
+        }
+
+        impl<const FRAC: u32> Mul for Fixed<$int, FRAC> {
+            type Output = Self;
+            fn mul(self, rhs: Self) -> Self {
+                let half = if FRAC > 0 { 1i128 << (FRAC - 1) } else { 0 };
+                Self::saturate((self.0 as i128 * rhs.0 as i128 + half) >> FRAC)
+            }
+        }
+
+        impl<const FRAC: u32> Div for Fixed<$int, FRAC> {
+            type Output = Self;
+            /// Divides, rounding to nearest; dividing a nonzero value by zero saturates.
+            fn div(self, rhs: Self) -> Self {
+                if rhs.0 == 0 {
+                    return Self::saturate(self.0.signum() as i128 * i128::MAX);
+                }
+                let (numerator, denominator) = ((self.0 as i128) << FRAC, rhs.0 as i128);
+                let (quotient, remainder) = (numerator / denominator, numerator % denominator);
+                // Half-way cases round away from zero
+                let away = 2 * remainder.abs() >= denominator.abs();
+                Self::saturate(quotient + if away { numerator.signum() * denominator.signum() } else { 0 })
+            }
+        }
+
+        impl<const FRAC: u32> Rem for Fixed<$int, FRAC> {
+            type Output = Self;
+            fn rem(self, rhs: Self) -> Self {
+                if rhs.0 == 0 { Self::zero() } else { Fixed(self.0.wrapping_rem(rhs.0)) }
+            }
+        }
+
+        impl<const FRAC: u32> Neg for Fixed<$int, FRAC> {
+            type Output = Self;
+            fn neg(self) -> Self {
+                Fixed(self.0.saturating_neg())
+            }
+        }
+
+        impl<const FRAC: u32> AddAssign for Fixed<$int, FRAC> {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<const FRAC: u32> SubAssign for Fixed<$int, FRAC> {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl<const FRAC: u32> MulAssign for Fixed<$int, FRAC> {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl<const FRAC: u32> DivAssign for Fixed<$int, FRAC> {
+            fn div_assign(&mut self, rhs: Self) {
+                *self = *self / rhs;
+            }
+        }
+
+        impl<const FRAC: u32> RemAssign for Fixed<$int, FRAC> {
+            fn rem_assign(&mut self, rhs: Self) {
+                *self = *self % rhs;
+            }
+        }
+
+        impl<const FRAC: u32> Zero for Fixed<$int, FRAC> {
+            fn zero() -> Self {
+                Self::from_bits(0)
+            }
+            fn is_zero(&self) -> bool {
+                self.0 == 0
+            }
+        }
+
+        impl<const FRAC: u32> One for Fixed<$int, FRAC> {
+            /// Returns one, or the largest value if one is out of range.
+            fn one() -> Self {
+                Self::saturate(1 << FRAC)
+            }
+        }
+
+        impl<const FRAC: u32> Num for Fixed<$int, FRAC> {
+            type FromStrRadixErr = ParseFloatError;
+            fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseFloatError> {
+                <f64 as Num>::from_str_radix(s, radix)
+                    .ok()
+                    .and_then(Self::from_f64)
+                    .ok_or(ParseFloatError { kind: FloatErrorKind::Invalid })
+            }
+        }
+
+        impl<const FRAC: u32> ToPrimitive for Fixed<$int, FRAC> {
+            fn to_i64(&self) -> Option<i64> {
+                i64::try_from(self.0 as i128 / (1i128 << FRAC)).ok()
+            }
+            fn to_u64(&self) -> Option<u64> {
+                u64::try_from(self.0 as i128 / (1i128 << FRAC)).ok()
+            }
+            fn to_f64(&self) -> Option<f64> {
+                Some(self.value())
+            }
+        }
+
+        impl<const FRAC: u32> NumCast for Fixed<$int, FRAC> {
+            fn from<N: ToPrimitive>(n: N) -> Option<Self> {
+                Self::from_f64(n.to_f64()?)
+            }
+        }
+
+        impl<const FRAC: u32> Signed for Fixed<$int, FRAC> {
+            fn abs(&self) -> Self {
+                Fixed(self.0.saturating_abs())
+            }
+            fn abs_sub(&self, other: &Self) -> Self {
+                if *self <= *other { Self::zero() } else { *self - *other }
+            }
+            fn signum(&self) -> Self {
+                match self.0.signum() {
+                    0 => Self::zero(),
+                    1 => Self::one(),
+                    _ => -Self::one(),
+                }
+            }
+            fn is_positive(&self) -> bool {
+                self.0 > 0
+            }
+            fn is_negative(&self) -> bool {
+                self.0 < 0
+            }
+        }
+
+        impl<const FRAC: u32> Float for Fixed<$int, FRAC> {
+            /// There is no NaN; returns zero.
+            fn nan() -> Self {
+                Self::zero()
+            }
+            /// Returns the largest value, which saturating operations return on overflow.
+            fn infinity() -> Self {
+                Self::max_value()
+            }
+            fn neg_infinity() -> Self {
+                Self::min_value()
+            }
+            fn neg_zero() -> Self {
+                Self::zero()
+            }
+            fn min_value() -> Self {
+                Self::from_bits(<$int>::MIN)
+            }
+            fn min_positive_value() -> Self {
+                Self::from_bits(1)
+            }
+            fn epsilon() -> Self {
+                Self::from_bits(1)
+            }
+            fn max_value() -> Self {
+                Self::from_bits(<$int>::MAX)
+            }
+            fn is_nan(self) -> bool {
+                false
+            }
+            fn is_infinite(self) -> bool {
+                false
+            }
+            fn is_finite(self) -> bool {
+                true
+            }
+            fn is_normal(self) -> bool {
+                self.0 != 0
+            }
+            fn classify(self) -> FpCategory {
+                if self.0 == 0 { FpCategory::Zero } else { FpCategory::Normal }
+            }
+            fn floor(self) -> Self {
+                Fixed(self.0 & !Self::FRACTION)
+            }
+            fn ceil(self) -> Self {
+                if self.0 & Self::FRACTION == 0 { self } else { self.floor() + Self::one() }
+            }
+            /// Rounds half-way cases away from zero.
+            fn round(self) -> Self {
+                let half = Fixed(Self::FRACTION / 2 + 1);
+                if FRAC == 0 {
+                    self
+                } else if self.0 >= 0 {
+                    (self + half).floor()
+                } else {
+                    -(-self + half).floor()
+                }
+            }
+            fn trunc(self) -> Self {
+                if self.0 >= 0 { self.floor() } else { self.ceil() }
+            }
+            fn fract(self) -> Self {
+                self - self.trunc()
+            }
+            fn abs(self) -> Self {
+                Signed::abs(&self)
+            }
+            fn signum(self) -> Self {
+                Signed::signum(&self)
+            }
+            fn is_sign_positive(self) -> bool {
+                self.0 >= 0
+            }
+            fn is_sign_negative(self) -> bool {
+                self.0 < 0
+            }
+            fn mul_add(self, a: Self, b: Self) -> Self {
+                self * a + b
+            }
+            fn recip(self) -> Self {
+                Self::one() / self
+            }
+            fn powi(self, n: i32) -> Self {
+                let (mut result, mut base, mut exponent) = (Self::one(), self, n.unsigned_abs());
+                while exponent > 0 {
+                    if exponent & 1 == 1 {
+                        result *= base;
+                    }
+                    base *= base;
+                    exponent >>= 1;
+                }
+                if n < 0 { result.recip() } else { result }
+            }
+            /// Negative bases only allow integer exponents; otherwise the result is zero.
+            fn powf(self, n: Self) -> Self {
+                if self.0 < 0 {
+                    return match (n.fract().0, n.to_i64().and_then(|n| i32::try_from(n).ok())) {
+                        (0, Some(n)) => self.powi(n),
+                        _ => Self::zero(),
+                    };
+                }
+                let Some(ln) = self.ln_q() else {
+                    return if n.0 > 0 { Self::zero() } else if n.0 == 0 { Self::one() } else { Self::max_value() };
+                };
+                // An overflowing exponent is beyond 128 in magnitude, which saturates or vanishes
+                match n.to_q().checked_mul(ln) {
+                    Some(product) => Self::exp_q(product >> q60::BITS),
+                    None if (n.0 > 0) == (ln > 0) => Self::max_value(),
+                    None => Self::zero(),
+                }
+            }
+            fn sqrt(self) -> Self {
+                if self.0 <= 0 {
+                    return Self::zero();
+                }
+                Fixed((((self.0 as u128) << FRAC).isqrt()) as $int)
+            }
+            fn exp(self) -> Self {
+                Self::exp_q(self.to_q())
+            }
+            fn exp2(self) -> Self {
+                let limit = 512 * q60::ONE;
+                Self::exp_q(q60::mul(self.to_q().clamp(-limit, limit), q60::LN2))
+            }
+            /// The logarithm of zero is the smallest value; of a negative value, zero.
+            fn ln(self) -> Self {
+                match self.ln_q() {
+                    Some(ln) => Self::from_q(ln),
+                    None if self.0 == 0 => Self::neg_infinity(),
+                    None => Self::zero(),
+                }
+            }
+            fn log(self, base: Self) -> Self {
+                match (self.ln_q(), base.ln_q()) {
+                    (Some(ln), Some(base)) if base != 0 => Self::from_q((ln << q60::BITS) / base),
+                    _ => Self::zero(),
+                }
+            }
+            fn log2(self) -> Self {
+                self.ln_q().map_or_else(|| self.ln(), |ln| Self::from_q(ln * q60::ONE / q60::LN2))
+            }
+            fn log10(self) -> Self {
+                self.ln_q().map_or_else(|| self.ln(), |ln| Self::from_q(ln * q60::ONE / q60::LN10))
+            }
+            fn max(self, other: Self) -> Self {
+                Ord::max(self, other)
+            }
+            fn min(self, other: Self) -> Self {
+                Ord::min(self, other)
+            }
+            fn abs_sub(self, other: Self) -> Self {
+                Signed::abs_sub(&self, &other)
+            }
+            fn cbrt(self) -> Self {
+                match Float::abs(self).ln_q() {
+                    Some(ln) => Self::exp_q(ln / 3) * Float::signum(self),
+                    None => Self::zero(),
+                }
+            }
+            fn hypot(self, other: Self) -> Self {
+                let (a, b) = (self.0 as i128, other.0 as i128);
+                Self::saturate(((a * a) as u128 + (b * b) as u128).isqrt() as i128)
+            }
+            fn sin(self) -> Self {
+                self.sin_cos().0
+            }
+            fn cos(self) -> Self {
+                self.sin_cos().1
+            }
+            fn tan(self) -> Self {
+                let (sin, cos) = q60::sin_cos(self.to_q());
+                if cos == 0 {
+                    return Self::saturate(sin.signum() * i128::MAX);
+                }
+                Self::from_q((sin << q60::BITS) / cos)
+            }
+            fn asin(self) -> Self {
+                let x = self.to_q();
+                if x.abs() > q60::ONE {
+                    return Self::zero();
+                }
+                Self::from_q(q60::atan2(x, Self::cosine_of_sine(x)))
+            }
+            fn acos(self) -> Self {
+                let x = self.to_q();
+                if x.abs() > q60::ONE {
+                    return Self::zero();
+                }
+                Self::from_q(q60::atan2(Self::cosine_of_sine(x), x))
+            }
+            fn atan(self) -> Self {
+                Self::from_q(q60::atan2(self.to_q(), q60::ONE))
+            }
+            fn atan2(self, other: Self) -> Self {
+                Self::from_q(q60::atan2(self.0 as i128, other.0 as i128))
+            }
+            fn sin_cos(self) -> (Self, Self) {
+                let (sin, cos) = q60::sin_cos(self.to_q());
+                (Self::from_q(sin), Self::from_q(cos))
+            }
+            fn exp_m1(self) -> Self {
+                self.exp() - Self::one()
+            }
+            fn ln_1p(self) -> Self {
+                (self + Self::one()).ln()
+            }
+            fn sinh(self) -> Self {
+                let x = self.to_q().abs().min(256 * q60::ONE);
+                let ((m, k), (n, j)) = (q60::exp(x), q60::exp(-x));
+                // (e^x - e^-x) / 2 with both terms brought to the exponent of e^x
+                let difference = m - n.checked_shr((k - j) as u32).unwrap_or(0);
+                Self::from_scaled(difference, k - 1 - q60::BITS as i128) * Float::signum(self)
+            }
+            fn cosh(self) -> Self {
+                let x = self.to_q().abs().min(256 * q60::ONE);
+                let ((m, k), (n, j)) = (q60::exp(x), q60::exp(-x));
+                Self::from_scaled(m + n.checked_shr((k - j) as u32).unwrap_or(0), k - 1 - q60::BITS as i128)
+            }
+            fn tanh(self) -> Self {
+                // tanh |x| = 1 - 2 / (e^2|x| + 1), which is one at this precision once e^2|x| > 2^62
+                let (m, k) = q60::exp(2 * self.to_q().abs().min(64 * q60::ONE));
+                if k >= 62 {
+                    return Float::signum(self);
+                }
+                let e = m << k;
+                Self::from_q(q60::ONE - ((2 * q60::ONE) << q60::BITS) / (e + q60::ONE)) * Float::signum(self)
+            }
+            fn asinh(self) -> Self {
+                let x = Float::abs(self);
+                (x + x.hypot(Self::one())).ln() * Float::signum(self)
+            }
+            fn acosh(self) -> Self {
+                if self < Self::one() {
+                    return Self::zero();
+                }
+                (self + (self - Self::one()).sqrt() * (self + Self::one()).sqrt()).ln()
+            }
+            fn atanh(self) -> Self {
+                let x = self.to_q();
+                if x.abs() >= q60::ONE {
+                    return if x.abs() == q60::ONE { Self::saturate(x.signum() * i128::MAX) } else { Self::zero() };
+                }
+                let ratio = ((q60::ONE + x) << q60::BITS) / (q60::ONE - x);
+                Self::from_q(q60::ln(ratio as u128, q60::BITS) / 2)
+            }
+            fn integer_decode(self) -> (u64, i16, i8) {
+                (self.0.unsigned_abs() as u64, -(FRAC as i16), if self.0 < 0 { -1 } else { 1 })
+            }
+        }
+    };
+}
+
+impl_fixed!(i32);
+impl_fixed!(i64);
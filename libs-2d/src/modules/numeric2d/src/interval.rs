@@ -0,0 +1,613 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::num::FpCategory;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign};
+
+use num_traits::{Float, Num, NumCast, One, Signed, ToPrimitive, Zero};
+
+/// A closed interval of floats that is guaranteed to contain the exact result of the
+/// computation that produced it.
+///
+/// Arithmetic and square roots round each bound outward to the next float only when the
+/// rounded result differs from the exact one, so point intervals of exactly representable
+/// values stay points. Other functions such as `sin` and `exp` rely on the platform's math
+/// library being accurate to within one unit in the last place and widen both bounds by
+/// one unit. A NaN bound marks a result outside the domain of the operation.
+///
+/// Comparisons only succeed when they hold for every value in the intervals: overlapping
+/// intervals are unordered unless they are the same interval. Two intervals are equal, and
+/// compare as equal, only when their bounds are.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Interval<F> {
+    lo: F,
+    hi: F,
+}
+
+pub type Interval32 = Interval<f32>;
+pub type Interval64 = Interval<f64>;
+
+/// The outcome of a test on an interval, which is only known if it holds for every value
+/// in the interval.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Certainty<T> {
+    Certain(T),
+    Uncertain,
+}
+
+impl<T> Certainty<T> {
+    /// Returns the outcome, or None if it is uncertain.
+    pub fn certain(self) -> Option<T> {
+        match self {
+            Certainty::Certain(value) => Some(value),
+            Certainty::Uncertain => None,
+        }
+    }
+}
+
+macro_rules! impl_interval {
+    ($float:ident) => {
+        impl Interval<$float> {
+            /// Returns the interval `[lo, hi]`, or None if a bound is NaN or `lo > hi`.
+            pub fn new(lo: $float, hi: $float) -> Option<Self> {
+                (lo <= hi).then_some(Interval { lo, hi })
+            }
+            /// Returns the interval holding only `value`.
+            pub const fn point(value: $float) -> Self {
+                Interval { lo: value, hi: value }
+            }
+            /// Returns the narrowest interval containing the exact value of pi.
+            pub fn pi() -> Self {
+                let pi = std::$float::consts::PI;
+                Interval { lo: pi.next_down(), hi: pi.next_up() }
+            }
+            pub const fn lo(&self) -> $float {
+                self.lo
+            }
+            pub const fn hi(&self) -> $float {
+                self.hi
+            }
+            pub fn midpoint(&self) -> $float {
+                self.lo / 2.0 + self.hi / 2.0
+            }
+            /// Returns an upper bound of the width.
+            pub fn width(&self) -> $float {
+                Self::add_bounds(self.hi, -self.lo).1
+            }
+            pub fn contains(&self, value: $float) -> bool {
+                self.lo <= value && value <= self.hi
+            }
+            /// Returns the sign shared by every value in the interval.
+            pub fn sign(&self) -> Certainty<Ordering> {
+                if self.lo > 0.0 {
+                    Certainty::Certain(Ordering::Greater)
+                } else if self.hi < 0.0 {
+                    Certainty::Certain(Ordering::Less)
+                } else if self.lo == 0.0 && self.hi == 0.0 {
+                    Certainty::Certain(Ordering::Equal)
+                } else {
+                    Certainty::Uncertain
+                }
+            }
+            fn nan_interval() -> Self {
+                Interval { lo: $float::NAN, hi: $float::NAN }
+            }
+            fn entire() -> Self {
+                Interval { lo: $float::NEG_INFINITY, hi: $float::INFINITY }
+            }
+            /// Widens rounded-to-nearest bounds from the platform's math library by one unit.
+            fn enclose(lo: $float, hi: $float) -> Self {
+                Interval { lo: lo.next_down(), hi: hi.next_up() }
+            }
+            /// Returns the bounds of a rounded result `value` given the sign of the exact
+            /// result minus `value`, or a one-unit widening if that sign cannot be trusted.
+            fn round_outward(value: $float, error: Option<$float>) -> ($float, $float) {
+                match error {
+                    Some(error) if error > 0.0 => (value, value.next_up()),
+                    Some(error) if error < 0.0 => (value.next_down(), value),
+                    Some(_) => (value, value),
+                    None => (value.next_down(), value.next_up()),
+                }
+            }
+            /// Returns true if the error term of a product or quotient of size `value` may
+            /// have underflowed and lost its sign.
+            fn tiny(value: $float) -> bool {
+                value.abs() < $float::MIN_POSITIVE * (1u64 << $float::MANTISSA_DIGITS) as $float
+            }
+            fn add_bounds(a: $float, b: $float) -> ($float, $float) {
+                let sum = a + b;
+                if !sum.is_finite() {
+                    return Self::round_outward(sum, None);
+                }
+                // Error of the rounded sum, exact by Knuth's two-sum
+                let b_part = sum - a;
+                let error = (a - (sum - b_part)) + (b - b_part);
+                Self::round_outward(sum, Some(error))
+            }
+            fn mul_bounds(a: $float, b: $float) -> ($float, $float) {
+                if a == 0.0 || b == 0.0 {
+                    // Zero times an unbounded end is still zero for the values inside
+                    return (0.0, 0.0);
+                }
+                let product = a * b;
+                if !product.is_finite() || Self::tiny(product) {
+                    return Self::round_outward(product, None);
+                }
+                Self::round_outward(product, Some(a.mul_add(b, -product)))
+            }
+            fn div_bounds(a: $float, b: $float) -> ($float, $float) {
+                let quotient = a / b;
+                if !quotient.is_finite() || Self::tiny(quotient) || Self::tiny(a) {
+                    return Self::round_outward(quotient, None);
+                }
+                // a - quotient * b is exact, and the error has its sign over the sign of b
+                let remainder = -quotient.mul_add(b, -a);
+                Self::round_outward(quotient, Some(remainder * b.signum()))
+            }
+            /// Combines the bounds of the products or quotients of every pair of ends.
+            fn hull(bounds: [($float, $float); 4]) -> Self {
+                if bounds.iter().any(|(lo, hi)| lo.is_nan() || hi.is_nan()) {
+                    return Self::nan_interval();
+                }
+                let lo = bounds.iter().map(|b| b.0).fold($float::INFINITY, $float::min);
+                let hi = bounds.iter().map(|b| b.1).fold($float::NEG_INFINITY, $float::max);
+                Interval { lo, hi }
+            }
+            /// Limits the interval to the domain `[min, max]` of a function, or returns None if
+            /// they do not overlap.
+            fn restrict(self, min: $float, max: $float) -> Option<Self> {
+                (self.hi >= min && self.lo <= max).then(|| Interval { lo: self.lo.max(min), hi: self.hi.min(max) })
+            }
+            /// Applies an increasing function defined on `[min, max]`.
+            fn increasing(self, min: $float, max: $float, f: impl Fn($float) -> $float) -> Self {
+                match self.restrict(min, max) {
+                    Some(x) => Self::enclose(f(x.lo), f(x.hi)),
+                    None => Self::nan_interval(),
+                }
+            }
+            /// Returns the range of integers `n` for which `(n + shift) pi` may lie in the
+            /// interval, with a margin for the rounding of the quotients.
+            fn multiples_of_pi(self, shift: $float) -> ($float, $float) {
+                let pi = std::$float::consts::PI;
+                let (lo, hi) = (self.lo / pi - shift, self.hi / pi - shift);
+                let margin = |q: $float| 4.0 * $float::EPSILON * (1.0 + q.abs());
+                ((lo - margin(lo)).ceil(), (hi + margin(hi)).floor())
+            }
+            /// Applies sine (`shift` 0.5) or cosine (`shift` 0), whose extremes lie at
+            /// `(n + shift) pi` with value `(-1)^n`.
+            fn periodic(self, shift: $float, f: impl Fn($float) -> $float) -> Self {
+                if self.is_nan() || self.hi - self.lo >= 2.0 * std::$float::consts::PI {
+                    return Interval { lo: -1.0, hi: 1.0 };
+                }
+                let (a, b) = (f(self.lo), f(self.hi));
+                let mut result = Self::enclose(a.min(b), a.max(b));
+                let (first, last) = self.multiples_of_pi(shift);
+                for n in [first, first + 1.0] {
+                    if n <= last {
+                        if n.rem_euclid(2.0) == 0.0 { result.hi = 1.0 } else { result.lo = -1.0 }
+                    }
+                }
+                Interval { lo: result.lo.max(-1.0), hi: result.hi.min(1.0) }
+            }
+        }
+
+        impl fmt::Display for Interval<$float> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "[{}, {}]", self.lo, self.hi)
+            }
+        }
+
+        impl From<$float> for Interval<$float> {
+            fn from(value: $float) -> Self {
+                Self::point(value)
+            }
+        }
+
+        impl PartialOrd for Interval<$float> {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                if self.hi < other.lo {
+                    Some(Ordering::Less)
+                } else if self.lo > other.hi {
+                    Some(Ordering::Greater)
+                } else if self == other {
+                    Some(Ordering::Equal)
+                } else {
+                    None
+                }
+            }
+        }
+
+        impl Add for Interval<$float> {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self {
+                let (lo, hi) = (Self::add_bounds(self.lo, rhs.lo).0, Self::add_bounds(self.hi, rhs.hi).1);
+                if lo.is_nan() || hi.is_nan() { Self::nan_interval() } else { Interval { lo, hi } }
+            }
+        }
+
+        impl Sub for Interval<$float> {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self {
+                self + -rhs
+            }
+        }
+
+        impl Mul for Interval<$float> {
+            type Output = Self;
+            fn mul(self, rhs: Self) -> Self {
+                Self::hull([
+                    Self::mul_bounds(self.lo, rhs.lo),
+                    Self::mul_bounds(self.lo, rhs.hi),
+                    Self::mul_bounds(self.hi, rhs.lo),
+                    Self::mul_bounds(self.hi, rhs.hi),
+                ])
+            }
+        }
+
+        impl Div for Interval<$float> {
+            type Output = Self;
+            /// Divides; a divisor containing zero gives the whole line, or NaN if it is zero.
+            fn div(self, rhs: Self) -> Self {
+                if rhs.is_nan() || rhs.is_zero() {
+                    return Self::nan_interval();
+                }
+                if rhs.lo <= 0.0 && rhs.hi >= 0.0 {
+                    return Self::entire();
+                }
+                Self::hull([
+                    Self::div_bounds(self.lo, rhs.lo),
+                    Self::div_bounds(self.lo, rhs.hi),
+                    Self::div_bounds(self.hi, rhs.lo),
+                    Self::div_bounds(self.hi, rhs.hi),
+                ])
+            }
+        }
+
+        impl Rem for Interval<$float> {
+            type Output = Self;
+            fn rem(self, rhs: Self) -> Self {
+                self - rhs * (self / rhs).trunc()
+            }
+        }
+
+        impl Neg for Interval<$float> {
+            type Output = Self;
+            fn neg(self) -> Self {
+                Interval { lo: -self.hi, hi: -self.lo }
+            }
+        }
+
+        impl AddAssign for Interval<$float> {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl SubAssign for Interval<$float> {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl MulAssign for Interval<$float> {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl DivAssign for Interval<$float> {
+            fn div_assign(&mut self, rhs: Self) {
+                *self = *self / rhs;
+            }
+        }
+
+        impl RemAssign for Interval<$float> {
+            fn rem_assign(&mut self, rhs: Self) {
+                *self = *self % rhs;
+            }
+        }
+
+        impl Zero for Interval<$float> {
+            fn zero() -> Self {
+                Self::point(0.0)
+            }
+            fn is_zero(&self) -> bool {
+                self.lo == 0.0 && self.hi == 0.0
+            }
+        }
+
+        impl One for Interval<$float> {
+            fn one() -> Self {
+                Self::point(1.0)
+            }
+        }
+
+        impl Num for Interval<$float> {
+            type FromStrRadixErr = <$float as Num>::FromStrRadixErr;
+            /// Parses the nearest float and widens it by one unit, as most decimals are rounded.
+            fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+                let value = <$float as Num>::from_str_radix(s, radix)?;
+                Ok(Self::enclose(value, value))
+            }
+        }
+
+        impl ToPrimitive for Interval<$float> {
+            fn to_i64(&self) -> Option<i64> {
+                self.midpoint().to_i64()
+            }
+            fn to_u64(&self) -> Option<u64> {
+                self.midpoint().to_u64()
+            }
+            /// Returns the midpoint.
+            fn to_f64(&self) -> Option<f64> {
+                self.midpoint().to_f64()
+            }
+        }
+
+        impl NumCast for Interval<$float> {
+            /// Converts to a point interval, widened by one unit if the value was rounded.
+            fn from<N: ToPrimitive>(n: N) -> Option<Self> {
+                let wide = n.to_f64()?;
+                let value = wide as $float;
+                // Integers beyond the mantissa and f64 values narrowed to f32 may be rounded
+                let exact = value as f64 == wide && n.to_i128().is_none_or(|i| wide as i128 == i);
+                Some(if exact { Self::point(value) } else { Self::enclose(value, value) })
+            }
+        }
+
+        impl Signed for Interval<$float> {
+            fn abs(&self) -> Self {
+                if self.lo >= 0.0 {
+                    *self
+                } else if self.hi <= 0.0 {
+                    -*self
+                } else {
+                    Interval { lo: 0.0, hi: self.hi.max(-self.lo) }
+                }
+            }
+            fn abs_sub(&self, other: &Self) -> Self {
+                Float::max(*self - *other, Self::zero())
+            }
+            /// Returns the interval of signs, with zero for zero.
+            fn signum(&self) -> Self {
+                let sign = |x: $float| if x > 0.0 { 1.0 } else if x < 0.0 { -1.0 } else { x };
+                Interval { lo: sign(self.lo), hi: sign(self.hi) }
+            }
+            fn is_positive(&self) -> bool {
+                self.lo > 0.0
+            }
+            fn is_negative(&self) -> bool {
+                self.hi < 0.0
+            }
+        }
+
+        impl Float for Interval<$float> {
+            fn nan() -> Self {
+                Self::nan_interval()
+            }
+            fn infinity() -> Self {
+                Self::point($float::INFINITY)
+            }
+            fn neg_infinity() -> Self {
+                Self::point($float::NEG_INFINITY)
+            }
+            fn neg_zero() -> Self {
+                Self::point(-0.0)
+            }
+            fn min_value() -> Self {
+                Self::point($float::MIN)
+            }
+            fn min_positive_value() -> Self {
+                Self::point($float::MIN_POSITIVE)
+            }
+            fn epsilon() -> Self {
+                Self::point($float::EPSILON)
+            }
+            fn max_value() -> Self {
+                Self::point($float::MAX)
+            }
+            fn is_nan(self) -> bool {
+                self.lo.is_nan() || self.hi.is_nan()
+            }
+            /// Returns true if an end is infinite.
+            fn is_infinite(self) -> bool {
+                !self.is_nan() && !self.is_finite()
+            }
+            fn is_finite(self) -> bool {
+                self.lo.is_finite() && self.hi.is_finite()
+            }
+            fn is_normal(self) -> bool {
+                self.lo.is_normal() && self.hi.is_normal() && self.lo.signum() == self.hi.signum()
+            }
+            fn classify(self) -> FpCategory {
+                if self.is_nan() {
+                    FpCategory::Nan
+                } else if self.lo == self.hi {
+                    self.lo.classify()
+                } else if self.is_infinite() {
+                    FpCategory::Infinite
+                } else {
+                    FpCategory::Normal
+                }
+            }
+            fn floor(self) -> Self {
+                Interval { lo: self.lo.floor(), hi: self.hi.floor() }
+            }
+            fn ceil(self) -> Self {
+                Interval { lo: self.lo.ceil(), hi: self.hi.ceil() }
+            }
+            fn round(self) -> Self {
+                Interval { lo: self.lo.round(), hi: self.hi.round() }
+            }
+            fn trunc(self) -> Self {
+                Interval { lo: self.lo.trunc(), hi: self.hi.trunc() }
+            }
+            fn fract(self) -> Self {
+                self - self.trunc()
+            }
+            fn abs(self) -> Self {
+                Signed::abs(&self)
+            }
+            fn signum(self) -> Self {
+                Signed::signum(&self)
+            }
+            /// Returns true if every value in the interval has a positive sign.
+            fn is_sign_positive(self) -> bool {
+                self.lo.is_sign_positive()
+            }
+            /// Returns true if every value in the interval has a negative sign.
+            fn is_sign_negative(self) -> bool {
+                self.hi.is_sign_negative()
+            }
+            fn mul_add(self, a: Self, b: Self) -> Self {
+                self * a + b
+            }
+            fn recip(self) -> Self {
+                Self::one() / self
+            }
+            fn powi(self, n: i32) -> Self {
+                let (mut result, mut exponent) = (Self::one(), n.unsigned_abs());
+                // Even powers of a mixed-sign interval are never negative
+                let mut base = if exponent % 2 == 0 { Signed::abs(&self) } else { self };
+                while exponent > 0 {
+                    if exponent & 1 == 1 {
+                        result *= base;
+                    }
+                    base = Signed::abs(&(base * base));
+                    exponent >>= 1;
+                }
+                if n < 0 { result.recip() } else { result }
+            }
+            fn powf(self, n: Self) -> Self {
+                (n * self.ln()).exp()
+            }
+            fn sqrt(self) -> Self {
+                let Some(x) = self.restrict(0.0, $float::INFINITY) else {
+                    return Self::nan_interval();
+                };
+                let bounds = |value: $float| {
+                    let root = value.sqrt();
+                    // value - root^2 is exact unless it underflows
+                    let error = (!Self::tiny(value) && root.is_finite()).then(|| -root.mul_add(root, -value));
+                    if value == 0.0 { (0.0, 0.0) } else { Self::round_outward(root, error) }
+                };
+                Interval { lo: bounds(x.lo).0.max(0.0), hi: bounds(x.hi).1 }
+            }
+            fn exp(self) -> Self {
+                let e = self.increasing($float::NEG_INFINITY, $float::INFINITY, $float::exp);
+                Interval { lo: e.lo.max(0.0), hi: e.hi }
+            }
+            fn exp2(self) -> Self {
+                let e = self.increasing($float::NEG_INFINITY, $float::INFINITY, $float::exp2);
+                Interval { lo: e.lo.max(0.0), hi: e.hi }
+            }
+            fn ln(self) -> Self {
+                self.increasing(0.0, $float::INFINITY, $float::ln)
+            }
+            fn log(self, base: Self) -> Self {
+                self.ln() / base.ln()
+            }
+            fn log2(self) -> Self {
+                self.increasing(0.0, $float::INFINITY, $float::log2)
+            }
+            fn log10(self) -> Self {
+                self.increasing(0.0, $float::INFINITY, $float::log10)
+            }
+            fn max(self, other: Self) -> Self {
+                Interval { lo: self.lo.max(other.lo), hi: self.hi.max(other.hi) }
+            }
+            fn min(self, other: Self) -> Self {
+                Interval { lo: self.lo.min(other.lo), hi: self.hi.min(other.hi) }
+            }
+            fn abs_sub(self, other: Self) -> Self {
+                Signed::abs_sub(&self, &other)
+            }
+            fn cbrt(self) -> Self {
+                self.increasing($float::NEG_INFINITY, $float::INFINITY, $float::cbrt)
+            }
+            fn hypot(self, other: Self) -> Self {
+                let (x, y) = (Signed::abs(&self), Signed::abs(&other));
+                let h = Self::enclose(x.lo.hypot(y.lo), x.hi.hypot(y.hi));
+                Interval { lo: h.lo.max(0.0), hi: h.hi }
+            }
+            fn sin(self) -> Self {
+                self.periodic(0.5, $float::sin)
+            }
+            fn cos(self) -> Self {
+                self.periodic(0.0, $float::cos)
+            }
+            /// Returns the whole line if the interval may contain a pole.
+            fn tan(self) -> Self {
+                let (first, last) = self.multiples_of_pi(0.5);
+                if self.is_nan() || first <= last {
+                    return Self::entire();
+                }
+                Self::enclose(self.lo.tan(), self.hi.tan())
+            }
+            fn asin(self) -> Self {
+                self.increasing(-1.0, 1.0, $float::asin)
+            }
+            fn acos(self) -> Self {
+                match self.restrict(-1.0, 1.0) {
+                    Some(x) => Self::enclose(x.hi.acos(), x.lo.acos()),
+                    None => Self::nan_interval(),
+                }
+            }
+            fn atan(self) -> Self {
+                self.increasing($float::NEG_INFINITY, $float::INFINITY, $float::atan)
+            }
+            /// Returns the range of angles over the box `self` by `other`, the whole turn if
+            /// the box meets the negative x axis.
+            fn atan2(self, other: Self) -> Self {
+                let (y, x) = (self, other);
+                if x.lo < 0.0 && y.lo <= 0.0 && y.hi >= 0.0 {
+                    let pi = Self::pi().hi;
+                    return Interval { lo: -pi, hi: pi };
+                }
+                // Away from the cut the angle over a box is extreme at its corners
+                let angles = [y.lo.atan2(x.lo), y.lo.atan2(x.hi), y.hi.atan2(x.lo), y.hi.atan2(x.hi)];
+                if angles.iter().any(|a| a.is_nan()) {
+                    return Self::nan_interval();
+                }
+                let lo = angles.iter().copied().fold($float::INFINITY, $float::min);
+                let hi = angles.iter().copied().fold($float::NEG_INFINITY, $float::max);
+                Self::enclose(lo, hi)
+            }
+            fn sin_cos(self) -> (Self, Self) {
+                (self.sin(), self.cos())
+            }
+            fn exp_m1(self) -> Self {
+                self.increasing($float::NEG_INFINITY, $float::INFINITY, $float::exp_m1)
+            }
+            fn ln_1p(self) -> Self {
+                self.increasing(-1.0, $float::INFINITY, $float::ln_1p)
+            }
+            fn sinh(self) -> Self {
+                self.increasing($float::NEG_INFINITY, $float::INFINITY, $float::sinh)
+            }
+            fn cosh(self) -> Self {
+                let c = Signed::abs(&self).increasing(0.0, $float::INFINITY, $float::cosh);
+                Interval { lo: c.lo.max(1.0), hi: c.hi }
+            }
+            fn tanh(self) -> Self {
+                let t = self.increasing($float::NEG_INFINITY, $float::INFINITY, $float::tanh);
+                Interval { lo: t.lo.max(-1.0), hi: t.hi.min(1.0) }
+            }
+            fn asinh(self) -> Self {
+                self.increasing($float::NEG_INFINITY, $float::INFINITY, $float::asinh)
+            }
+            fn acosh(self) -> Self {
+                self.increasing(1.0, $float::INFINITY, $float::acosh)
+            }
+            fn atanh(self) -> Self {
+                self.increasing(-1.0, 1.0, $float::atanh)
+            }
+            /// Decodes the midpoint.
+            fn integer_decode(self) -> (u64, i16, i8) {
+                Float::integer_decode(self.midpoint())
+            }
+        }
+    };
+}
+
+impl_interval!(f32);
+impl_interval!(f64);
@@ -0,0 +1,7 @@
+mod dual;
+mod fixed;
+mod interval;
+mod q60;
+pub use dual::{derivative, gradient, jacobian, Dual};
+pub use fixed::{Fixed, Fixed32, Fixed64};
+pub use interval::{Certainty, Interval, Interval32, Interval64};
@@ -0,0 +1,124 @@
+// Integer-only transcendental functions shared by the fixed-point types. Values are held in
+// `i128` with 60 fraction bits, well beyond the precision of any `Fixed`, so results round
+// to the same bits on every machine.
+
+pub(crate) const BITS: u32 = 60;
+pub(crate) const ONE: i128 = 1 << BITS;
+pub(crate) const PI: i128 = 3622009729038561421;
+pub(crate) const LN2: i128 = 799144290325165979;
+pub(crate) const LN10: i128 = 2654699869899991814;
+/// CORDIC gain correction, the product of `1 / sqrt(1 + 2^-2i)` over all iterations.
+const GAIN: i128 = 700114967507363238;
+/// `atan(2^-i)`; from i = 20 on it equals `2^-i` at this precision.
+const ATAN: [i128; 20] = [
+    905502432259640355,
+    534549298976576474,
+    282441168888798124,
+    143371547418228444,
+    71963988336308046,
+    36017075762092179,
+    18012932708689205,
+    9007016009513623,
+    4503576721087964,
+    2251796950380271,
+    1125899548928887,
+    562949908682076,
+    281474971118251,
+    140737487656277,
+    70368744090283,
+    35184372077909,
+    17592186043051,
+    8796093022037,
+    4398046511083,
+    2199023255549,
+];
+
+fn atan_step(i: u32) -> i128 {
+    if (i as usize) < ATAN.len() { ATAN[i as usize] } else { ONE >> i }
+}
+
+pub(crate) fn mul(a: i128, b: i128) -> i128 {
+    (a * b) >> BITS
+}
+
+/// Returns the sine and cosine of `angle`, using CORDIC in rotation mode.
+pub(crate) fn sin_cos(angle: i128) -> (i128, i128) {
+    let mut z = angle.rem_euclid(2 * PI);
+    if z > PI {
+        z -= 2 * PI;
+    }
+    // CORDIC only converges within about 1.74 radians, so the outer quarters are turned
+    // by half a turn, which negates both results
+    let flip = z.abs() > PI / 2;
+    if flip {
+        z -= PI * z.signum();
+    }
+    let (mut x, mut y) = (GAIN, 0);
+    for i in 0..=BITS {
+        let (dx, dy) = (y >> i, x >> i);
+        if z >= 0 {
+            (x, y, z) = (x - dx, y + dy, z - atan_step(i));
+        } else {
+            (x, y, z) = (x + dx, y - dy, z + atan_step(i));
+        }
+    }
+    if flip { (-y, -x) } else { (y, x) }
+}
+
+/// Returns the angle of the vector `(x, y)` in `[-PI, PI]`, using CORDIC in vectoring mode.
+/// Both components may use any common scale.
+pub(crate) fn atan2(y: i128, x: i128) -> i128 {
+    if x == 0 && y == 0 {
+        return 0;
+    }
+    // Bring the larger component to about one so the shifts below keep full precision
+    let top = 127 - x.unsigned_abs().max(y.unsigned_abs()).leading_zeros() as i32;
+    let shift = BITS as i32 - top;
+    let scale = |v: i128| if shift >= 0 { v << shift } else { v >> -shift };
+    let (mut x, mut y) = (scale(x), scale(y));
+    let mut z = 0;
+    if x < 0 {
+        // Vectoring needs x >= 0; turning by half a turn changes the angle by PI
+        z = if y >= 0 { PI } else { -PI };
+        (x, y) = (-x, -y);
+    }
+    for i in 0..=BITS {
+        let (dx, dy) = (y >> i, x >> i);
+        if y > 0 {
+            (x, y, z) = (x + dx, y - dy, z + atan_step(i));
+        } else {
+            (x, y, z) = (x - dx, y + dy, z - atan_step(i));
+        }
+    }
+    z
+}
+
+/// Returns `(m, k)` with `e^x = m 2^k` and `m` in `[1, 2)`.
+pub(crate) fn exp(x: i128) -> (i128, i128) {
+    let k = x.div_euclid(LN2);
+    let r = x - k * LN2;
+    // Taylor series of e^r for r in [0, ln 2); terms vanish after about twenty steps
+    let (mut sum, mut term, mut n) = (ONE, ONE, 1);
+    while term != 0 {
+        term = mul(term, r) / n;
+        sum += term;
+        n += 1;
+    }
+    (sum, k)
+}
+
+/// Returns the natural logarithm of the positive value `v 2^-frac`.
+pub(crate) fn ln(v: u128, frac: u32) -> i128 {
+    let top = 127 - v.leading_zeros();
+    let m = if top <= BITS { (v << (BITS - top)) as i128 } else { (v >> (top - BITS)) as i128 };
+    // ln m = 2 atanh(z) with z = (m - 1) / (m + 1) in [0, 1/3)
+    let z = ((m - ONE) << BITS) / (m + ONE);
+    let z2 = mul(z, z);
+    let (mut sum, mut power, mut n) = (0, z, 1);
+    while power != 0 {
+        sum += power / n;
+        power = mul(power, z2);
+        n += 2;
+    }
+    (top as i128 - frac as i128) * LN2 + 2 * sum
+}
@@ -0,0 +1,793 @@
+use std::cmp::Ordering;
+
+use line2d::Line2d;
+use num_traits::Float;
+use point2d::Point2d;
+use vector2d::Vector2d;
+
+mod svg;
+pub use svg::PathParseError;
+
+/// One drawing command of a `Path2d`.
+///
+/// Every command except `MoveTo` and `Close` draws from the current point,
+/// which is the end point of the previous command.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PathSegment<T> {
+    /// Starts a new subpath at the given point.
+    MoveTo(Point2d<T>),
+    /// Draws a straight line to the given point.
+    LineTo(Point2d<T>),
+    /// Draws an elliptical arc to `to`, following the SVG endpoint parameterization.
+    /// `x_axis_rotation` is in radians.
+    ArcTo {
+        radii: Vector2d<T>,
+        x_axis_rotation: T,
+        large_arc: bool,
+        sweep: bool,
+        to: Point2d<T>,
+    },
+    /// Draws a quadratic Bézier curve to `to`.
+    QuadTo { ctrl: Point2d<T>, to: Point2d<T> },
+    /// Draws a cubic Bézier curve to `to`.
+    CubicTo { ctrl1: Point2d<T>, ctrl2: Point2d<T>, to: Point2d<T> },
+    /// Draws a straight line back to the start of the current subpath.
+    Close,
+}
+
+/// A sequence of lines, arcs and Bézier curves, split into subpaths by `MoveTo`.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct Path2d<T> {
+    segments: Vec<PathSegment<T>>,
+}
+
+/// A point where a path meets another path or a line segment.
+///
+/// `segment` indexes the path's `segments()` and `t` is the parameter along that segment in
+/// `[0, 1]`: the Bézier parameter for curves, the fraction of the swept angle for arcs and the
+/// fraction of the length for lines, including the line drawn by `Close`. `other_segment` and
+/// `u` are the same for the other path, or `0` and the parameter along the line segment.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PathIntersection<T> {
+    pub segment: usize,
+    pub t: T,
+    pub other_segment: usize,
+    pub u: T,
+    pub point: Point2d<T>,
+}
+
+/// Center parameterization of an elliptical arc.
+#[derive(Copy, Clone, Debug)]
+struct ArcCenter<T> {
+    center: Point2d<T>,
+    rx: T,
+    ry: T,
+    phi: T,
+    start_angle: T,
+    sweep_angle: T,
+}
+
+/// A drawing command with its start point resolved.
+#[derive(Copy, Clone, Debug)]
+enum Curve<T> {
+    Line(Line2d<T>),
+    Quad([Point2d<T>; 3]),
+    Cubic([Point2d<T>; 4]),
+    Arc(ArcCenter<T>),
+}
+
+/// A subpath with every command resolved to an absolute curve.
+struct Subpath<T> {
+    start: Point2d<T>,
+    /// Each curve with the index of the segment that draws it.
+    curves: Vec<(usize, Curve<T>)>,
+    closed: bool,
+}
+
+impl<T: Float> ArcCenter<T> {
+    /// Converts an SVG endpoint arc to its center parameterization,
+    /// or `None` if the arc degenerates to a straight line.
+    fn from_endpoints(from: Point2d<T>, radii: Vector2d<T>, phi: T, large_arc: bool, sweep: bool, to: Point2d<T>) -> Option<Self> {
+        let two = T::from(2.0).unwrap();
+        let mut rx = radii.x.abs();
+        let mut ry = radii.y.abs();
+        if rx.is_zero() || ry.is_zero() || from == to {
+            return None;
+        }
+        let (sin_phi, cos_phi) = phi.sin_cos();
+        let hx = (from.x() - to.x()) / two;
+        let hy = (from.y() - to.y()) / two;
+        let x1 = cos_phi * hx + sin_phi * hy;
+        let y1 = -sin_phi * hx + cos_phi * hy;
+        // Scale the radii up if they are too small to join both end points
+        let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+        if lambda > T::one() {
+            rx = rx * lambda.sqrt();
+            ry = ry * lambda.sqrt();
+        }
+        let numerator = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+        let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+        let mut coef = (numerator / denominator).max(T::zero()).sqrt();
+        if large_arc == sweep {
+            coef = -coef;
+        }
+        let cx1 = coef * rx * y1 / ry;
+        let cy1 = -coef * ry * x1 / rx;
+        let center = Point2d::new(
+            cos_phi * cx1 - sin_phi * cy1 + (from.x() + to.x()) / two,
+            sin_phi * cx1 + cos_phi * cy1 + (from.y() + to.y()) / two,
+        );
+        let start = Vector2d::new((x1 - cx1) / rx, (y1 - cy1) / ry);
+        let end = Vector2d::new((-x1 - cx1) / rx, (-y1 - cy1) / ry);
+        let two_pi = T::from(2.0 * std::f64::consts::PI).unwrap();
+        let start_angle = start.y.atan2(start.x);
+        let cross = start.x * end.y - start.y * end.x;
+        let mut sweep_angle = cross.atan2(start.x * end.x + start.y * end.y);
+        if !sweep && sweep_angle > T::zero() {
+            sweep_angle = sweep_angle - two_pi;
+        } else if sweep && sweep_angle < T::zero() {
+            sweep_angle = sweep_angle + two_pi;
+        }
+        Some(ArcCenter { center, rx, ry, phi, start_angle, sweep_angle })
+    }
+    fn point_at_angle(&self, angle: T) -> Point2d<T> {
+        let (sin_phi, cos_phi) = self.phi.sin_cos();
+        let (sin_a, cos_a) = angle.sin_cos();
+        Point2d::new(
+            self.center.x() + self.rx * cos_phi * cos_a - self.ry * sin_phi * sin_a,
+            self.center.y() + self.rx * sin_phi * cos_a + self.ry * cos_phi * sin_a,
+        )
+    }
+    /// Returns true if `angle` lies within the swept range of the arc.
+    fn contains_angle(&self, angle: T) -> bool {
+        let two_pi = T::from(2.0 * std::f64::consts::PI).unwrap();
+        let offset = if self.sweep_angle >= T::zero() {
+            angle - self.start_angle
+        } else {
+            self.start_angle - angle
+        };
+        let offset = offset - two_pi * (offset / two_pi).floor();
+        offset <= self.sweep_angle.abs()
+    }
+}
+
+impl<T: Float> Curve<T> {
+    fn start(&self) -> Point2d<T> {
+        match self {
+            Curve::Line(line) => line.pt1,
+            Curve::Quad(points) => points[0],
+            Curve::Cubic(points) => points[0],
+            Curve::Arc(arc) => arc.point_at_angle(arc.start_angle),
+        }
+    }
+    fn end(&self) -> Point2d<T> {
+        match self {
+            Curve::Line(line) => line.pt2,
+            Curve::Quad(points) => points[2],
+            Curve::Cubic(points) => points[3],
+            Curve::Arc(arc) => arc.point_at_angle(arc.start_angle + arc.sweep_angle),
+        }
+    }
+    /// Appends the points approximating the curve to `out`, excluding the start point.
+    fn flatten_into(&self, tolerance: T, out: &mut Vec<Point2d<T>>) {
+        match self {
+            Curve::Line(line) => out.push(line.pt2),
+            Curve::Quad(points) => {
+                let two = T::from(2.0).unwrap();
+                let three = T::from(3.0).unwrap();
+                // Degree elevation keeps a single subdivision routine
+                let ctrl1 = lerp(points[0], points[1], two / three);
+                let ctrl2 = lerp(points[2], points[1], two / three);
+                flatten_cubic(&[points[0], ctrl1, ctrl2, points[2]], tolerance, 0, out);
+            }
+            Curve::Cubic(points) => flatten_cubic(points, tolerance, 0, out),
+            Curve::Arc(arc) => {
+                let radius = arc.rx.max(arc.ry);
+                // A zero, negative or NaN tolerance asks for the finest output, not a single chord
+                let tolerance = tolerance.max(radius * T::from(1e-6).unwrap()).min(radius);
+                // Largest angle step whose chord stays within `tolerance` of the arc
+                let step = T::from(2.0).unwrap() * (T::one() - tolerance / radius).acos();
+                let count = if step > T::zero() {
+                    (arc.sweep_angle.abs() / step).ceil().max(T::one())
+                } else {
+                    T::one()
+                };
+                let count = count.min(T::from(4096.0).unwrap()).to_usize().unwrap_or(1);
+                for i in 1..=count {
+                    let t = T::from(i).unwrap() / T::from(count).unwrap();
+                    out.push(arc.point_at_angle(arc.start_angle + arc.sweep_angle * t));
+                }
+            }
+        }
+    }
+    /// Returns the point at parameter `t`, as in `PathIntersection`.
+    fn point_at(&self, t: T) -> Point2d<T> {
+        match self {
+            Curve::Line(line) => line.point_at(t),
+            Curve::Quad(points) => quad_point(points, t),
+            Curve::Cubic(points) => cubic_point(points, t),
+            Curve::Arc(arc) => arc.point_at_angle(arc.start_angle + arc.sweep_angle * t),
+        }
+    }
+    /// Returns the part of the curve between the parameters `t0 < t1`, as in `PathIntersection`.
+    fn part(&self, t0: T, t1: T) -> Curve<T> {
+        match self {
+            Curve::Line(line) => Curve::Line(Line2d::new(line.point_at(t0), line.point_at(t1))),
+            Curve::Quad(points) => Curve::Quad(split_bezier(&split_bezier(points, t1).0, t0 / t1).1),
+            Curve::Cubic(points) => Curve::Cubic(split_bezier(&split_bezier(points, t1).0, t0 / t1).1),
+            Curve::Arc(arc) => Curve::Arc(ArcCenter {
+                start_angle: arc.start_angle + arc.sweep_angle * t0,
+                sweep_angle: arc.sweep_angle * (t1 - t0),
+                ..*arc
+            }),
+        }
+    }
+    /// Returns true if the curve stays within `tolerance` of its chord.
+    fn is_flat(&self, tolerance: T) -> bool {
+        let chord = Line2d::new(self.start(), self.end());
+        match self {
+            Curve::Line(_) => true,
+            // The convex hull of the control points contains the curve
+            Curve::Quad(points) => chord.distance_to_point(&points[1]) <= tolerance,
+            Curve::Cubic(points) => points[1..3].iter().all(|p| chord.distance_to_point(p) <= tolerance),
+            Curve::Arc(arc) => {
+                let half_sweep = arc.sweep_angle.abs() / T::from(2.0).unwrap();
+                half_sweep < T::from(std::f64::consts::FRAC_PI_2).unwrap()
+                    && arc.rx.max(arc.ry) * (T::one() - half_sweep.cos()) <= tolerance
+            }
+        }
+    }
+    fn bounding_box(&self) -> (Point2d<T>, Point2d<T>) {
+        let (mut min, mut max) = (self.start(), self.start());
+        self.extend_bounds(&mut min, &mut max);
+        (min, max)
+    }
+    /// Extends `(min, max)` with the exact bounding box of the curve.
+    fn extend_bounds(&self, min: &mut Point2d<T>, max: &mut Point2d<T>) {
+        let mut include = |p: Point2d<T>| {
+            *min = Point2d::new(min.x().min(p.x()), min.y().min(p.y()));
+            *max = Point2d::new(max.x().max(p.x()), max.y().max(p.y()));
+        };
+        include(self.start());
+        include(self.end());
+        match self {
+            Curve::Line(_) => {}
+            Curve::Quad(points) => {
+                for (a, b, c) in [
+                    (points[0].x(), points[1].x(), points[2].x()),
+                    (points[0].y(), points[1].y(), points[2].y()),
+                ] {
+                    let denom = a - b - b + c;
+                    if !denom.is_zero() {
+                        let t = (a - b) / denom;
+                        if t > T::zero() && t < T::one() {
+                            include(quad_point(points, t));
+                        }
+                    }
+                }
+            }
+            Curve::Cubic(points) => {
+                for (a, b, c, d) in [
+                    (points[0].x(), points[1].x(), points[2].x(), points[3].x()),
+                    (points[0].y(), points[1].y(), points[2].y(), points[3].y()),
+                ] {
+                    let three = T::from(3.0).unwrap();
+                    let two = T::from(2.0).unwrap();
+                    // Roots of the derivative divided by three
+                    let qa = d - three * c + three * b - a;
+                    let qb = two * (c - two * b + a);
+                    let qc = b - a;
+                    for t in quadratic_roots(qa, qb, qc) {
+                        if t > T::zero() && t < T::one() {
+                            include(cubic_point(points, t));
+                        }
+                    }
+                }
+            }
+            Curve::Arc(arc) => {
+                let pi = T::from(std::f64::consts::PI).unwrap();
+                let (sin_phi, cos_phi) = arc.phi.sin_cos();
+                let angle_x = (-arc.ry * sin_phi).atan2(arc.rx * cos_phi);
+                let angle_y = (arc.ry * cos_phi).atan2(arc.rx * sin_phi);
+                for angle in [angle_x, angle_x + pi, angle_y, angle_y + pi] {
+                    if arc.contains_angle(angle) {
+                        include(arc.point_at_angle(angle));
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn lerp<T: Float>(a: Point2d<T>, b: Point2d<T>, t: T) -> Point2d<T> {
+    Point2d::new(a.x() + (b.x() - a.x()) * t, a.y() + (b.y() - a.y()) * t)
+}
+
+fn quad_point<T: Float>(points: &[Point2d<T>; 3], t: T) -> Point2d<T> {
+    lerp(lerp(points[0], points[1], t), lerp(points[1], points[2], t), t)
+}
+
+fn cubic_point<T: Float>(points: &[Point2d<T>; 4], t: T) -> Point2d<T> {
+    let a = lerp(points[0], points[1], t);
+    let b = lerp(points[1], points[2], t);
+    let c = lerp(points[2], points[3], t);
+    lerp(lerp(a, b, t), lerp(b, c, t), t)
+}
+
+/// Splits a Bézier curve at `t` by de Casteljau's algorithm.
+fn split_bezier<T: Float, const N: usize>(points: &[Point2d<T>; N], t: T) -> ([Point2d<T>; N], [Point2d<T>; N]) {
+    let (mut left, mut right, mut work) = (*points, *points, *points);
+    for level in 0..N {
+        left[level] = work[0];
+        right[N - 1 - level] = work[N - 1 - level];
+        for i in 0..N - 1 - level {
+            work[i] = lerp(work[i], work[i + 1], t);
+        }
+    }
+    (left, right)
+}
+
+/// Collects the parameters and points where two curves meet within the parameter ranges `a`
+/// and `b`, halving the ranges until the boxes of both parts are apart or both parts are
+/// flat enough to meet as line segments.
+fn intersect_curves<T: Float>(
+    first: (&Curve<T>, T, T),
+    second: (&Curve<T>, T, T),
+    tolerance: T,
+    depth: u32,
+    out: &mut Vec<(T, T, Point2d<T>)>,
+) {
+    let ((curve, a0, a1), (other, b0, b1)) = (first, second);
+    let (part, other_part) = (curve.part(a0, a1), other.part(b0, b1));
+    let ((min, max), (other_min, other_max)) = (part.bounding_box(), other_part.bounding_box());
+    if min.x() > other_max.x() + tolerance
+        || other_min.x() > max.x() + tolerance
+        || min.y() > other_max.y() + tolerance
+        || other_min.y() > max.y() + tolerance
+    {
+        return;
+    }
+    // Past the depth limit the parts are far below any useful tolerance
+    let flat = depth >= 48 || part.is_flat(tolerance);
+    let other_flat = depth >= 48 || other_part.is_flat(tolerance);
+    if flat && other_flat {
+        let chord = Line2d::new(part.start(), part.end());
+        for hit in chord.intersections(&Line2d::new(other_part.start(), other_part.end())) {
+            out.push((a0 + (a1 - a0) * hit.t, b0 + (b1 - b0) * hit.u, hit.point));
+        }
+        return;
+    }
+    let size = |min: Point2d<T>, max: Point2d<T>| (max.x() - min.x()).max(max.y() - min.y());
+    let half = T::from(0.5).unwrap();
+    if !flat && (other_flat || size(min, max) >= size(other_min, other_max)) {
+        let middle = (a0 + a1) * half;
+        intersect_curves((curve, a0, middle), second, tolerance, depth + 1, out);
+        intersect_curves((curve, middle, a1), second, tolerance, depth + 1, out);
+    } else {
+        let middle = (b0 + b1) * half;
+        intersect_curves(first, (other, b0, middle), tolerance, depth + 1, out);
+        intersect_curves(first, (other, middle, b1), tolerance, depth + 1, out);
+    }
+}
+
+/// Returns the parameter of a point of the curve within `tolerance` of `point`, searching the
+/// parameter range `(t0, t1)` as `intersect_curves` does.
+fn locate<T: Float>(curve: &Curve<T>, (t0, t1): (T, T), point: &Point2d<T>, tolerance: T, depth: u32) -> Option<T> {
+    let part = curve.part(t0, t1);
+    let (min, max) = part.bounding_box();
+    if point.x() < min.x() - tolerance
+        || point.x() > max.x() + tolerance
+        || point.y() < min.y() - tolerance
+        || point.y() > max.y() + tolerance
+    {
+        return None;
+    }
+    if depth >= 48 || part.is_flat(tolerance) {
+        // The chord strays up to `tolerance` from the curve
+        let (s, closest) = Line2d::new(part.start(), part.end()).closest_point(point);
+        return (closest.distance_to(point) <= tolerance + tolerance).then(|| t0 + (t1 - t0) * s);
+    }
+    let middle = (t0 + t1) * T::from(0.5).unwrap();
+    locate(curve, (t0, middle), point, tolerance, depth + 1)
+        .or_else(|| locate(curve, (middle, t1), point, tolerance, depth + 1))
+}
+
+/// Returns the ends of the stretch along which two curves coincide, if they do. Subdividing
+/// such curves would report points all along the stretch.
+fn overlap<T: Float>(curve: &Curve<T>, other: &Curve<T>, tolerance: T) -> Option<[(T, T, Point2d<T>); 2]> {
+    let (zero, one) = (T::zero(), T::one());
+    let mut ends: Vec<(T, T, Point2d<T>)> = Vec::new();
+    for (t, point) in [(zero, curve.start()), (one, curve.end())] {
+        ends.extend(locate(other, (zero, one), &point, tolerance, 0).map(|u| (t, u, point)));
+    }
+    for (u, point) in [(zero, other.start()), (one, other.end())] {
+        ends.extend(locate(curve, (zero, one), &point, tolerance, 0).map(|t| (t, u, point)));
+    }
+    ends.sort_by(|p, q| p.0.partial_cmp(&q.0).unwrap_or(Ordering::Equal));
+    let (first, last) = (*ends.first()?, *ends.last()?);
+    if first.2.distance_to(&last.2) <= tolerance {
+        return None;
+    }
+    // Distinct curves of degree three at most cannot agree at this many points in between
+    let samples = 9;
+    let coincide = (1..samples).all(|k| {
+        let t = first.0 + (last.0 - first.0) * T::from(k).unwrap() / T::from(samples).unwrap();
+        locate(other, (zero, one), &curve.point_at(t), tolerance, 0).is_some()
+    });
+    coincide.then_some([first, last])
+}
+
+/// Returns the points where two curves meet, ordered along the first, as `(t, u, point)`.
+/// Neighbouring parts report a crossing near their common end twice, so points closer than
+/// `tolerance` to an earlier one are dropped.
+fn curve_intersections<T: Float>(curve: &Curve<T>, other: &Curve<T>, tolerance: T) -> Vec<(T, T, Point2d<T>)> {
+    let ((min, max), (other_min, other_max)) = (curve.bounding_box(), other.bounding_box());
+    let extent = [min, max, other_min, other_max].iter().fold(T::zero(), |m, p| m.max(p.x().abs()).max(p.y().abs()));
+    // A zero, negative or NaN tolerance asks for the finest result, not endless subdivision
+    let tolerance = tolerance.max(extent * T::from(1e-12).unwrap());
+    if let Some(ends) = overlap(curve, other, tolerance) {
+        return ends.to_vec();
+    }
+    let mut hits = Vec::new();
+    intersect_curves((curve, T::zero(), T::one()), (other, T::zero(), T::one()), tolerance, 0, &mut hits);
+    hits.sort_by(|p, q| p.0.partial_cmp(&q.0).unwrap_or(Ordering::Equal));
+    let mut unique: Vec<(T, T, Point2d<T>)> = Vec::new();
+    for hit in hits {
+        if unique.iter().all(|kept| kept.2.distance_to(&hit.2) > tolerance) {
+            unique.push(hit);
+        }
+    }
+    unique
+}
+
+/// Real roots of `a t² + b t + c = 0`, falling back to the linear case when `a` is zero.
+fn quadratic_roots<T: Float>(a: T, b: T, c: T) -> Vec<T> {
+    let epsilon = T::from(1e-12).unwrap();
+    if a.abs() <= epsilon * (b.abs() + c.abs()) || a.is_zero() {
+        if b.is_zero() {
+            return Vec::new();
+        }
+        return vec![-c / b];
+    }
+    let discriminant = b * b - T::from(4.0).unwrap() * a * c;
+    if discriminant < T::zero() {
+        return Vec::new();
+    }
+    let root = discriminant.sqrt();
+    let two_a = a + a;
+    vec![(-b + root) / two_a, (-b - root) / two_a]
+}
+
+/// Recursively subdivides a cubic Bézier until its control points lie within `tolerance` of the chord.
+fn flatten_cubic<T: Float>(points: &[Point2d<T>; 4], tolerance: T, depth: u32, out: &mut Vec<Point2d<T>>) {
+    let chord = Line2d::new(points[0], points[3]);
+    let flat = chord.distance_to_point(&points[1]) <= tolerance && chord.distance_to_point(&points[2]) <= tolerance;
+    if flat || depth >= 16 {
+        out.push(points[3]);
+        return;
+    }
+    let half = T::from(0.5).unwrap();
+    let ab = lerp(points[0], points[1], half);
+    let bc = lerp(points[1], points[2], half);
+    let cd = lerp(points[2], points[3], half);
+    let abc = lerp(ab, bc, half);
+    let bcd = lerp(bc, cd, half);
+    let middle = lerp(abc, bcd, half);
+    flatten_cubic(&[points[0], ab, abc, middle], tolerance, depth + 1, out);
+    flatten_cubic(&[middle, bcd, cd, points[3]], tolerance, depth + 1, out);
+}
+
+/// Returns the singular values and the rotation of the left singular vectors of a 2x2 matrix,
+/// so that `[[a, b], [c, d]]` maps the unit circle onto an ellipse with those radii and rotation.
+fn ellipse_of_linear_map<T: Float>(a: T, b: T, c: T, d: T) -> (T, T, T) {
+    let two = T::from(2.0).unwrap();
+    let e = (a + d) / two;
+    let f = (a - d) / two;
+    let g = (c + b) / two;
+    let h = (c - b) / two;
+    let q = (e * e + h * h).sqrt();
+    let r = (f * f + g * g).sqrt();
+    let a1 = g.atan2(f);
+    let a2 = h.atan2(e);
+    ((q + r), (q - r).abs(), (a2 + a1) / two)
+}
+
+impl<T: Float> Path2d<T> {
+    pub fn new() -> Self {
+        Path2d { segments: Vec::new() }
+    }
+    pub fn from_segments(segments: Vec<PathSegment<T>>) -> Self {
+        Path2d { segments }
+    }
+    pub fn segments(&self) -> &[PathSegment<T>] {
+        &self.segments
+    }
+    pub fn len(&self) -> usize {
+        self.segments.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+    pub fn push(&mut self, segment: PathSegment<T>) {
+        self.segments.push(segment);
+    }
+    pub fn move_to(&mut self, to: Point2d<T>) {
+        self.segments.push(PathSegment::MoveTo(to));
+    }
+    pub fn line_to(&mut self, to: Point2d<T>) {
+        self.segments.push(PathSegment::LineTo(to));
+    }
+    pub fn quad_to(&mut self, ctrl: Point2d<T>, to: Point2d<T>) {
+        self.segments.push(PathSegment::QuadTo { ctrl, to });
+    }
+    pub fn cubic_to(&mut self, ctrl1: Point2d<T>, ctrl2: Point2d<T>, to: Point2d<T>) {
+        self.segments.push(PathSegment::CubicTo { ctrl1, ctrl2, to });
+    }
+    /// Appends an elliptical arc; `x_axis_rotation` is in radians.
+    pub fn arc_to(&mut self, radii: Vector2d<T>, x_axis_rotation: T, large_arc: bool, sweep: bool, to: Point2d<T>) {
+        self.segments.push(PathSegment::ArcTo { radii, x_axis_rotation, large_arc, sweep, to });
+    }
+    pub fn close(&mut self) {
+        self.segments.push(PathSegment::Close);
+    }
+    /// Resolves the segments into subpaths of absolute curves.
+    /// Drawing commands before the first `MoveTo` start at the origin.
+    fn subpaths(&self) -> Vec<Subpath<T>> {
+        let origin = Point2d::new(T::zero(), T::zero());
+        let mut subpaths: Vec<Subpath<T>> = Vec::new();
+        let mut current = origin;
+        for (index, segment) in self.segments.iter().enumerate() {
+            if let PathSegment::MoveTo(to) = segment {
+                subpaths.push(Subpath { start: *to, curves: Vec::new(), closed: false });
+                current = *to;
+                continue;
+            }
+            if subpaths.last().is_none_or(|subpath| subpath.closed) {
+                // Drawing after a Close starts a new subpath at the current point
+                subpaths.push(Subpath { start: current, curves: Vec::new(), closed: false });
+            }
+            let subpath = subpaths.last_mut().unwrap();
+            match *segment {
+                PathSegment::MoveTo(_) => unreachable!(),
+                PathSegment::LineTo(to) => {
+                    subpath.curves.push((index, Curve::Line(Line2d::new(current, to))));
+                    current = to;
+                }
+                PathSegment::QuadTo { ctrl, to } => {
+                    subpath.curves.push((index, Curve::Quad([current, ctrl, to])));
+                    current = to;
+                }
+                PathSegment::CubicTo { ctrl1, ctrl2, to } => {
+                    subpath.curves.push((index, Curve::Cubic([current, ctrl1, ctrl2, to])));
+                    current = to;
+                }
+                PathSegment::ArcTo { radii, x_axis_rotation, large_arc, sweep, to } => {
+                    match ArcCenter::from_endpoints(current, radii, x_axis_rotation, large_arc, sweep, to) {
+                        Some(arc) => subpath.curves.push((index, Curve::Arc(arc))),
+                        None if current != to => subpath.curves.push((index, Curve::Line(Line2d::new(current, to)))),
+                        None => {}
+                    }
+                    current = to;
+                }
+                PathSegment::Close => {
+                    if current != subpath.start {
+                        subpath.curves.push((index, Curve::Line(Line2d::new(current, subpath.start))));
+                    }
+                    subpath.closed = true;
+                    current = subpath.start;
+                }
+            }
+        }
+        subpaths
+    }
+    /// Approximates every subpath by a polyline whose distance to the path stays within `tolerance`.
+    ///
+    /// Closed subpaths end with a copy of their first point.
+    pub fn flatten(&self, tolerance: T) -> Vec<Vec<Point2d<T>>> {
+        self.subpaths()
+            .iter()
+            .map(|subpath| {
+                let mut points = vec![subpath.start];
+                for (_, curve) in &subpath.curves {
+                    curve.flatten_into(tolerance, &mut points);
+                }
+                points
+            })
+            .collect()
+    }
+    /// Returns the total length of the path, measured on its flattening with `tolerance`.
+    pub fn length(&self, tolerance: T) -> T {
+        self.flatten(tolerance)
+            .iter()
+            .flat_map(|points| points.windows(2))
+            .fold(T::zero(), |sum, pair| sum + pair[0].distance_to(&pair[1]))
+    }
+    /// Returns the point at `distance` along the path, measured on its flattening with `tolerance`.
+    ///
+    /// The distance is clamped to the length of the path; `None` is returned for an empty path.
+    pub fn point_at_length(&self, distance: T, tolerance: T) -> Option<Point2d<T>> {
+        let polylines = self.flatten(tolerance);
+        let mut remaining = distance.max(T::zero());
+        let mut last = None;
+        for pair in polylines.iter().flat_map(|points| points.windows(2)) {
+            let edge = Line2d::new(pair[0], pair[1]);
+            let length = edge.length();
+            if remaining <= length && !length.is_zero() {
+                return Some(edge.point_at(remaining / length));
+            }
+            remaining = remaining - length;
+            last = Some(pair[1]);
+        }
+        last.or_else(|| polylines.first().and_then(|points| points.first().copied()))
+    }
+    /// Returns the exact axis-aligned bounding box as `(min, max)`, or `None` for an empty path.
+    pub fn bounds(&self) -> Option<(Point2d<T>, Point2d<T>)> {
+        let subpaths = self.subpaths();
+        let first = subpaths.first()?.start;
+        let mut min = first;
+        let mut max = first;
+        for subpath in &subpaths {
+            min = Point2d::new(min.x().min(subpath.start.x()), min.y().min(subpath.start.y()));
+            max = Point2d::new(max.x().max(subpath.start.x()), max.y().max(subpath.start.y()));
+            for (_, curve) in &subpath.curves {
+                curve.extend_bounds(&mut min, &mut max);
+            }
+        }
+        Some((min, max))
+    }
+    /// Returns the drawing commands resolved to absolute curves, with their segment indices.
+    fn curves(&self) -> Vec<(usize, Curve<T>)> {
+        self.subpaths().into_iter().flat_map(|subpath| subpath.curves).collect()
+    }
+    /// Returns every point where this path meets `other`, ordered by segment and then along it.
+    ///
+    /// Curves are halved until they lie within `tolerance` of their chords, which bounds the
+    /// error of the points. Curves that overlap along a stretch give the ends of the stretch.
+    pub fn intersections(&self, other: &Path2d<T>, tolerance: T) -> Vec<PathIntersection<T>> {
+        let other_curves = other.curves();
+        let mut intersections = Vec::new();
+        for (segment, curve) in self.curves() {
+            for (other_segment, other_curve) in &other_curves {
+                intersections.extend(curve_intersections(&curve, other_curve, tolerance).into_iter().map(
+                    |(t, u, point)| PathIntersection { segment, t, other_segment: *other_segment, u, point },
+                ));
+            }
+        }
+        // Hits on several curves of the other path are ordered along each segment too
+        intersections.sort_by(|p, q| p.segment.cmp(&q.segment).then(p.t.partial_cmp(&q.t).unwrap_or(Ordering::Equal)));
+        intersections
+    }
+    /// Returns every point where the path meets the line segment, as for `intersections`.
+    pub fn line_intersections(&self, line: &Line2d<T>, tolerance: T) -> Vec<PathIntersection<T>> {
+        let other = Curve::Line(*line);
+        let mut intersections = Vec::new();
+        for (segment, curve) in self.curves() {
+            intersections.extend(
+                curve_intersections(&curve, &other, tolerance)
+                    .into_iter()
+                    .map(|(t, u, point)| PathIntersection { segment, t, other_segment: 0, u, point }),
+            );
+        }
+        intersections
+    }
+    /// Applies the affine map `p -> (a x + b y + e, c x + d y + f)` to the path.
+    ///
+    /// Arc radii, rotation and direction are adjusted so that arcs stay exact.
+    pub fn transform(&self, a: T, b: T, c: T, d: T, e: T, f: T) -> Self {
+        let map = |p: Point2d<T>| Point2d::new(a * p.x() + b * p.y() + e, c * p.x() + d * p.y() + f);
+        let flips = a * d - b * c < T::zero();
+        let segments = self
+            .segments
+            .iter()
+            .map(|segment| match *segment {
+                PathSegment::MoveTo(to) => PathSegment::MoveTo(map(to)),
+                PathSegment::LineTo(to) => PathSegment::LineTo(map(to)),
+                PathSegment::QuadTo { ctrl, to } => PathSegment::QuadTo { ctrl: map(ctrl), to: map(to) },
+                PathSegment::CubicTo { ctrl1, ctrl2, to } => PathSegment::CubicTo {
+                    ctrl1: map(ctrl1),
+                    ctrl2: map(ctrl2),
+                    to: map(to),
+                },
+                PathSegment::ArcTo { radii, x_axis_rotation, large_arc, sweep, to } => {
+                    // The arc's ellipse is the unit circle under `linear * rotation * diag(rx, ry)`
+                    let (sin_phi, cos_phi) = x_axis_rotation.sin_cos();
+                    let m00 = (a * cos_phi + b * sin_phi) * radii.x;
+                    let m01 = (b * cos_phi - a * sin_phi) * radii.y;
+                    let m10 = (c * cos_phi + d * sin_phi) * radii.x;
+                    let m11 = (d * cos_phi - c * sin_phi) * radii.y;
+                    let (rx, ry, rotation) = ellipse_of_linear_map(m00, m01, m10, m11);
+                    PathSegment::ArcTo {
+                        radii: Vector2d::new(rx, ry),
+                        x_axis_rotation: rotation,
+                        large_arc,
+                        sweep: sweep != flips,
+                        to: map(to),
+                    }
+                }
+                PathSegment::Close => PathSegment::Close,
+            })
+            .collect();
+        Path2d { segments }
+    }
+    pub fn translate(&self, dx: T, dy: T) -> Self {
+        self.transform(T::one(), T::zero(), T::zero(), T::one(), dx, dy)
+    }
+    pub fn scale(&self, sx: T, sy: T) -> Self {
+        self.transform(sx, T::zero(), T::zero(), sy, T::zero(), T::zero())
+    }
+    /// Rotates the path around the origin by `angle` radians.
+    pub fn rotate(&self, angle: T) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        self.transform(cos, -sin, sin, cos, T::zero(), T::zero())
+    }
+    /// Returns the path traversed in the opposite direction.
+    ///
+    /// Subpaths appear in reverse order and closed subpaths stay closed.
+    pub fn reversed(&self) -> Self {
+        // Each subpath is kept as its start point, its drawing segments and whether it is closed
+        let mut subpaths: Vec<(Point2d<T>, Vec<PathSegment<T>>, bool)> = Vec::new();
+        let mut current = Point2d::new(T::zero(), T::zero());
+        for segment in &self.segments {
+            match *segment {
+                PathSegment::MoveTo(to) => {
+                    subpaths.push((to, Vec::new(), false));
+                    current = to;
+                }
+                PathSegment::Close => {
+                    if let Some(subpath) = subpaths.last_mut() {
+                        subpath.2 = true;
+                        current = subpath.0;
+                    }
+                }
+                _ => {
+                    if subpaths.last().is_none_or(|subpath| subpath.2) {
+                        subpaths.push((current, Vec::new(), false));
+                    }
+                    subpaths.last_mut().unwrap().1.push(*segment);
+                    current = segment_end(segment).unwrap_or(current);
+                }
+            }
+        }
+        let mut segments = Vec::with_capacity(self.segments.len());
+        for (start, drawn, closed) in subpaths.into_iter().rev() {
+            let mut starts = Vec::with_capacity(drawn.len());
+            let mut point = start;
+            for segment in &drawn {
+                starts.push(point);
+                point = segment_end(segment).unwrap_or(point);
+            }
+            segments.push(PathSegment::MoveTo(point));
+            for (segment, from) in drawn.iter().zip(starts).rev() {
+                segments.push(match *segment {
+                    PathSegment::LineTo(_) => PathSegment::LineTo(from),
+                    PathSegment::QuadTo { ctrl, .. } => PathSegment::QuadTo { ctrl, to: from },
+                    PathSegment::CubicTo { ctrl1, ctrl2, .. } => PathSegment::CubicTo { ctrl1: ctrl2, ctrl2: ctrl1, to: from },
+                    PathSegment::ArcTo { radii, x_axis_rotation, large_arc, sweep, .. } => PathSegment::ArcTo {
+                        radii,
+                        x_axis_rotation,
+                        large_arc,
+                        sweep: !sweep,
+                        to: from,
+                    },
+                    PathSegment::MoveTo(_) | PathSegment::Close => unreachable!(),
+                });
+            }
+            if closed {
+                segments.push(PathSegment::Close);
+            }
+        }
+        Path2d { segments }
+    }
+}
+
+/// Returns the end point of a drawing segment, or `None` for `Close`.
+fn segment_end<T: Copy>(segment: &PathSegment<T>) -> Option<Point2d<T>> {
+    match *segment {
+        PathSegment::MoveTo(to)
+        | PathSegment::LineTo(to)
+        | PathSegment::QuadTo { to, .. }
+        | PathSegment::CubicTo { to, .. }
+        | PathSegment::ArcTo { to, .. } => Some(to),
+        PathSegment::Close => None,
+    }
+}
@@ -0,0 +1,298 @@
+use std::fmt;
+use std::str::FromStr;
+
+use num_traits::Float;
+use point2d::Point2d;
+use vector2d::Vector2d;
+
+use crate::{Path2d, PathSegment};
+
+/// Error returned when an SVG path string cannot be parsed.
+///
+/// Positions are byte offsets into the parsed string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PathParseError {
+    /// The path does not start with a `M` or `m` command.
+    MissingMoveTo { position: usize },
+    /// A character that is neither a command, a number nor a separator.
+    UnexpectedCharacter { position: usize, found: char },
+    /// A command argument is missing or malformed.
+    ExpectedNumber { position: usize },
+    /// An arc flag is neither `0` nor `1`.
+    ExpectedFlag { position: usize },
+}
+
+impl fmt::Display for PathParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathParseError::MissingMoveTo { position } => write!(f, "path data must start with a move-to command (at {})", position),
+            PathParseError::UnexpectedCharacter { position, found } => write!(f, "unexpected character '{}' at {}", found, position),
+            PathParseError::ExpectedNumber { position } => write!(f, "expected a number at {}", position),
+            PathParseError::ExpectedFlag { position } => write!(f, "expected an arc flag (0 or 1) at {}", position),
+        }
+    }
+}
+
+impl std::error::Error for PathParseError {}
+
+/// Splits SVG path data into commands, numbers and flags.
+struct Lexer<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn skip_separators(&mut self) {
+        while self.pos < self.bytes.len() && (self.bytes[self.pos].is_ascii_whitespace() || self.bytes[self.pos] == b',') {
+            self.pos += 1;
+        }
+    }
+    fn at_end(&mut self) -> bool {
+        self.skip_separators();
+        self.pos >= self.bytes.len()
+    }
+    /// Returns the next command letter without consuming it.
+    fn peek_command(&mut self) -> Option<u8> {
+        self.skip_separators();
+        self.bytes.get(self.pos).copied().filter(|b| b.is_ascii_alphabetic())
+    }
+    /// Returns true if the next token starts a number.
+    fn at_number(&mut self) -> bool {
+        self.skip_separators();
+        matches!(self.bytes.get(self.pos), Some(b'0'..=b'9' | b'.' | b'-' | b'+'))
+    }
+    fn number(&mut self) -> Result<f64, PathParseError> {
+        self.skip_separators();
+        let start = self.pos;
+        let digits = |lexer: &mut Self| {
+            let from = lexer.pos;
+            while lexer.pos < lexer.bytes.len() && lexer.bytes[lexer.pos].is_ascii_digit() {
+                lexer.pos += 1;
+            }
+            lexer.pos > from
+        };
+        if matches!(self.bytes.get(self.pos), Some(b'-' | b'+')) {
+            self.pos += 1;
+        }
+        let mut has_digits = digits(self);
+        if self.bytes.get(self.pos) == Some(&b'.') {
+            self.pos += 1;
+            has_digits |= digits(self);
+        }
+        if !has_digits {
+            self.pos = start;
+            return Err(PathParseError::ExpectedNumber { position: start });
+        }
+        if matches!(self.bytes.get(self.pos), Some(b'e' | b'E')) {
+            let mark = self.pos;
+            self.pos += 1;
+            if matches!(self.bytes.get(self.pos), Some(b'-' | b'+')) {
+                self.pos += 1;
+            }
+            if !digits(self) {
+                self.pos = mark; // Not an exponent after all
+            }
+        }
+        let text = std::str::from_utf8(&self.bytes[start..self.pos]).map_err(|_| PathParseError::ExpectedNumber { position: start })?;
+        text.parse().map_err(|_| PathParseError::ExpectedNumber { position: start })
+    }
+    fn flag(&mut self) -> Result<bool, PathParseError> {
+        self.skip_separators();
+        let result = match self.bytes.get(self.pos) {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            _ => return Err(PathParseError::ExpectedFlag { position: self.pos }),
+        };
+        self.pos += 1;
+        Ok(result)
+    }
+}
+
+impl<T: Float> Path2d<T> {
+    /// Parses SVG path data (the `d` attribute).
+    ///
+    /// Relative commands are made absolute, `H`/`V` become `LineTo` and the
+    /// smooth `S`/`T` curves become `CubicTo`/`QuadTo` with reflected control points.
+    pub fn from_svg(data: &str) -> Result<Self, PathParseError> {
+        let mut lexer = Lexer { bytes: data.as_bytes(), pos: 0 };
+        let mut path = Path2d::new();
+        let origin = Point2d::new(T::zero(), T::zero());
+        let mut current = origin;
+        let mut subpath_start = origin;
+        // Control point of the previous curve, used by the smooth variants
+        let mut last_cubic_ctrl: Option<Point2d<T>> = None;
+        let mut last_quad_ctrl: Option<Point2d<T>> = None;
+        let to_t = |value: f64| T::from(value).unwrap_or(T::nan());
+        let mut first = true;
+        while !lexer.at_end() {
+            let position = lexer.pos;
+            let command = match lexer.peek_command() {
+                Some(command) => command,
+                None => {
+                    let found = data[position..].chars().next().unwrap_or(' ');
+                    return Err(PathParseError::UnexpectedCharacter { position, found });
+                }
+            };
+            if first && !matches!(command, b'M' | b'm') {
+                return Err(PathParseError::MissingMoveTo { position });
+            }
+            first = false;
+            lexer.pos += 1;
+            let relative = command.is_ascii_lowercase();
+            let mut repeat = false;
+            loop {
+                if repeat && !lexer.at_number() {
+                    break;
+                }
+                let offset = if relative { current } else { origin };
+                let point = |lexer: &mut Lexer| -> Result<Point2d<T>, PathParseError> {
+                    let x = to_t(lexer.number()?);
+                    let y = to_t(lexer.number()?);
+                    Ok(Point2d::new(offset.x() + x, offset.y() + y))
+                };
+                let mut cubic_ctrl = None;
+                let mut quad_ctrl = None;
+                match command.to_ascii_uppercase() {
+                    b'M' => {
+                        let to = point(&mut lexer)?;
+                        // Pairs after the first one are implicit line-to commands
+                        if repeat {
+                            path.line_to(to);
+                        } else {
+                            path.move_to(to);
+                            subpath_start = to;
+                        }
+                        current = to;
+                    }
+                    b'L' => {
+                        current = point(&mut lexer)?;
+                        path.line_to(current);
+                    }
+                    b'H' => {
+                        let x = to_t(lexer.number()?) + offset.x();
+                        current = Point2d::new(x, current.y());
+                        path.line_to(current);
+                    }
+                    b'V' => {
+                        let y = to_t(lexer.number()?) + offset.y();
+                        current = Point2d::new(current.x(), y);
+                        path.line_to(current);
+                    }
+                    b'C' => {
+                        let ctrl1 = point(&mut lexer)?;
+                        let ctrl2 = point(&mut lexer)?;
+                        current = point(&mut lexer)?;
+                        path.cubic_to(ctrl1, ctrl2, current);
+                        cubic_ctrl = Some(ctrl2);
+                    }
+                    b'S' => {
+                        let ctrl1 = reflect(last_cubic_ctrl, current);
+                        let ctrl2 = point(&mut lexer)?;
+                        current = point(&mut lexer)?;
+                        path.cubic_to(ctrl1, ctrl2, current);
+                        cubic_ctrl = Some(ctrl2);
+                    }
+                    b'Q' => {
+                        let ctrl = point(&mut lexer)?;
+                        current = point(&mut lexer)?;
+                        path.quad_to(ctrl, current);
+                        quad_ctrl = Some(ctrl);
+                    }
+                    b'T' => {
+                        let ctrl = reflect(last_quad_ctrl, current);
+                        current = point(&mut lexer)?;
+                        path.quad_to(ctrl, current);
+                        quad_ctrl = Some(ctrl);
+                    }
+                    b'A' => {
+                        let rx = to_t(lexer.number()?);
+                        let ry = to_t(lexer.number()?);
+                        let rotation = to_t(lexer.number()?).to_radians();
+                        let large_arc = lexer.flag()?;
+                        let sweep = lexer.flag()?;
+                        current = point(&mut lexer)?;
+                        path.arc_to(Vector2d::new(rx, ry), rotation, large_arc, sweep, current);
+                    }
+                    b'Z' => {
+                        path.close();
+                        current = subpath_start;
+                        last_cubic_ctrl = None;
+                        last_quad_ctrl = None;
+                        break;
+                    }
+                    _ => {
+                        return Err(PathParseError::UnexpectedCharacter { position, found: command as char });
+                    }
+                }
+                last_cubic_ctrl = cubic_ctrl;
+                last_quad_ctrl = quad_ctrl;
+                repeat = true;
+            }
+        }
+        Ok(path)
+    }
+}
+
+/// Reflects the previous control point about `current`, or returns `current` if there is none.
+fn reflect<T: Float>(ctrl: Option<Point2d<T>>, current: Point2d<T>) -> Point2d<T> {
+    match ctrl {
+        Some(ctrl) => Point2d::new(current.x() + current.x() - ctrl.x(), current.y() + current.y() - ctrl.y()),
+        None => current,
+    }
+}
+
+impl<T: Float> FromStr for Path2d<T> {
+    type Err = PathParseError;
+    fn from_str(data: &str) -> Result<Self, Self::Err> {
+        Path2d::from_svg(data)
+    }
+}
+
+impl<T: Float + fmt::Display> Path2d<T> {
+    /// Serializes the path as SVG path data using absolute commands.
+    pub fn to_svg(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl<T: Float + fmt::Display> fmt::Display for Path2d<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // SVG data must open with a move, so name the origin that a path drawn without one starts at
+        if self.segments().first().is_some_and(|segment| !matches!(segment, PathSegment::MoveTo(_))) {
+            write!(f, "M0 0 ")?;
+        }
+        for (i, segment) in self.segments().iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            match segment {
+                PathSegment::MoveTo(to) => write!(f, "M{} {}", to.x(), to.y())?,
+                PathSegment::LineTo(to) => write!(f, "L{} {}", to.x(), to.y())?,
+                PathSegment::QuadTo { ctrl, to } => write!(f, "Q{} {} {} {}", ctrl.x(), ctrl.y(), to.x(), to.y())?,
+                PathSegment::CubicTo { ctrl1, ctrl2, to } => write!(
+                    f,
+                    "C{} {} {} {} {} {}",
+                    ctrl1.x(),
+                    ctrl1.y(),
+                    ctrl2.x(),
+                    ctrl2.y(),
+                    to.x(),
+                    to.y()
+                )?,
+                PathSegment::ArcTo { radii, x_axis_rotation, large_arc, sweep, to } => write!(
+                    f,
+                    "A{} {} {} {} {} {} {}",
+                    radii.x,
+                    radii.y,
+                    x_axis_rotation.to_degrees(),
+                    *large_arc as u8,
+                    *sweep as u8,
+                    to.x(),
+                    to.y()
+                )?,
+                PathSegment::Close => write!(f, "Z")?,
+            }
+        }
+        Ok(())
+    }
+}
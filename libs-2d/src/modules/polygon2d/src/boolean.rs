@@ -0,0 +1,74 @@
+use point2d::Point2d;
+
+use crate::overlay::Overlay;
+use crate::{MultiPolygon2d, Polygon2d};
+
+/// Set operation between two polygon sets.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BooleanOp {
+    Union,
+    Intersection,
+    /// The first operand minus the second.
+    Difference,
+    Xor,
+}
+
+impl BooleanOp {
+    fn fill(self, in_a: bool, in_b: bool) -> bool {
+        match self {
+            BooleanOp::Union => in_a || in_b,
+            BooleanOp::Intersection => in_a && in_b,
+            BooleanOp::Difference => in_a && !in_b,
+            BooleanOp::Xor => in_a != in_b,
+        }
+    }
+}
+
+fn rings(polygons: &MultiPolygon2d<f64>) -> Vec<Vec<Point2d<f64>>> {
+    polygons.0.iter().flat_map(|polygon| polygon.rings().map(|ring| ring.to_vec())).collect()
+}
+
+impl MultiPolygon2d<f64> {
+    /// Applies a boolean operation to two polygon sets.
+    ///
+    /// Inputs are read with the even-odd rule, so ring orientation does not matter and
+    /// shared edges, touching vertices and overlapping collinear edges are all handled.
+    /// Edge intersections are found with exact orientation predicates; only the
+    /// coordinates of new crossing points are rounded. The result has counter-clockwise
+    /// exteriors and clockwise holes.
+    pub fn boolean(&self, other: &MultiPolygon2d<f64>, op: BooleanOp) -> MultiPolygon2d<f64> {
+        let overlay = Overlay::new(&[rings(self), rings(other)]);
+        overlay.extract(|winding| op.fill(winding[0] % 2 != 0, winding[1] % 2 != 0))
+    }
+    pub fn union(&self, other: &MultiPolygon2d<f64>) -> MultiPolygon2d<f64> {
+        self.boolean(other, BooleanOp::Union)
+    }
+    pub fn intersection(&self, other: &MultiPolygon2d<f64>) -> MultiPolygon2d<f64> {
+        self.boolean(other, BooleanOp::Intersection)
+    }
+    pub fn difference(&self, other: &MultiPolygon2d<f64>) -> MultiPolygon2d<f64> {
+        self.boolean(other, BooleanOp::Difference)
+    }
+    pub fn xor(&self, other: &MultiPolygon2d<f64>) -> MultiPolygon2d<f64> {
+        self.boolean(other, BooleanOp::Xor)
+    }
+}
+
+impl Polygon2d<f64> {
+    /// Applies a boolean operation to two polygons; see `MultiPolygon2d::boolean`.
+    pub fn boolean(&self, other: &Polygon2d<f64>, op: BooleanOp) -> MultiPolygon2d<f64> {
+        MultiPolygon2d(vec![self.clone()]).boolean(&MultiPolygon2d(vec![other.clone()]), op)
+    }
+    pub fn union(&self, other: &Polygon2d<f64>) -> MultiPolygon2d<f64> {
+        self.boolean(other, BooleanOp::Union)
+    }
+    pub fn intersection(&self, other: &Polygon2d<f64>) -> MultiPolygon2d<f64> {
+        self.boolean(other, BooleanOp::Intersection)
+    }
+    pub fn difference(&self, other: &Polygon2d<f64>) -> MultiPolygon2d<f64> {
+        self.boolean(other, BooleanOp::Difference)
+    }
+    pub fn xor(&self, other: &Polygon2d<f64>) -> MultiPolygon2d<f64> {
+        self.boolean(other, BooleanOp::Xor)
+    }
+}
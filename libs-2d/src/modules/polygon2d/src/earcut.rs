@@ -0,0 +1,573 @@
+use num_traits::Float;
+use point2d::Point2d;
+
+use crate::{ring_signed_area_doubled, Polygon2d};
+
+/// Vertex in one of the circular lists being clipped. Removed nodes keep their links,
+/// which the clipping relies on when removing neighbors one after the other.
+struct Node<T> {
+    /// Index of the vertex in `Polygon2d::vertices`.
+    i: usize,
+    x: T,
+    y: T,
+    prev: usize,
+    next: usize,
+    /// Set for the single point of a hole made of one point, which must never be filtered out.
+    steiner: bool,
+    /// Position on the z-order curve, and the neighbors in a list sorted by it.
+    z: u32,
+    prev_z: Option<usize>,
+    next_z: Option<usize>,
+}
+
+/// Maps points to a z-order curve over the bounding box of the exterior, so that points
+/// close in the plane tend to be close in the curve order.
+struct ZOrder<T> {
+    min_x: T,
+    min_y: T,
+    inv_size: T,
+}
+
+impl<T: Float> ZOrder<T> {
+    /// Interleaves the bits of the coordinates scaled to 15 bits each.
+    fn z(&self, x: T, y: T) -> u32 {
+        let limit = T::from(32767.0).unwrap();
+        let scale = |v: T, min: T| ((v - min) * self.inv_size).max(T::zero()).min(limit).to_u32().unwrap_or(0);
+        let spread = |v: u32| {
+            let v = (v | (v << 8)) & 0x00FF00FF;
+            let v = (v | (v << 4)) & 0x0F0F0F0F;
+            let v = (v | (v << 2)) & 0x33333333;
+            (v | (v << 1)) & 0x55555555
+        };
+        spread(scale(x, self.min_x)) | (spread(scale(y, self.min_y)) << 1)
+    }
+}
+
+struct Earcut<T> {
+    nodes: Vec<Node<T>>,
+    triangles: Vec<[usize; 3]>,
+    /// Set for polygons large enough that looking up ears by z-order pays off.
+    hash: Option<ZOrder<T>>,
+}
+
+impl<T: Float> Earcut<T> {
+    fn insert_node(&mut self, i: usize, point: &Point2d<T>, last: Option<usize>) -> usize {
+        let p = self.nodes.len();
+        let (prev, next) = match last {
+            None => (p, p),
+            Some(last) => (last, self.nodes[last].next),
+        };
+        let (x, y) = (point.x(), point.y());
+        self.nodes.push(Node { i, x, y, prev, next, steiner: false, z: 0, prev_z: None, next_z: None });
+        if let Some(last) = last {
+            self.nodes[next].prev = p;
+            self.nodes[last].next = p;
+        }
+        p
+    }
+    fn remove_node(&mut self, p: usize) {
+        let (prev, next) = (self.nodes[p].prev, self.nodes[p].next);
+        self.nodes[next].prev = prev;
+        self.nodes[prev].next = next;
+        let (prev_z, next_z) = (self.nodes[p].prev_z, self.nodes[p].next_z);
+        if let Some(prev_z) = prev_z {
+            self.nodes[prev_z].next_z = next_z;
+        }
+        if let Some(next_z) = next_z {
+            self.nodes[next_z].prev_z = prev_z;
+        }
+    }
+    fn next(&self, p: usize) -> usize {
+        self.nodes[p].next
+    }
+    fn prev(&self, p: usize) -> usize {
+        self.nodes[p].prev
+    }
+    fn equals(&self, a: usize, b: usize) -> bool {
+        self.nodes[a].x == self.nodes[b].x && self.nodes[a].y == self.nodes[b].y
+    }
+    /// Twice the signed area of `p, q, r`, negative when they turn counter-clockwise.
+    fn area(&self, p: usize, q: usize, r: usize) -> T {
+        let (p, q, r) = (&self.nodes[p], &self.nodes[q], &self.nodes[r]);
+        (q.y - p.y) * (r.x - q.x) - (q.x - p.x) * (r.y - q.y)
+    }
+
+    /// Links a ring into a circular list running counter-clockwise, or clockwise if `ccw` is false.
+    fn linked_list(&mut self, ring: &[Point2d<T>], offset: usize, ccw: bool) -> Option<usize> {
+        let mut last = None;
+        if ccw == (ring_signed_area_doubled(ring) > T::zero()) {
+            for (k, point) in ring.iter().enumerate() {
+                last = Some(self.insert_node(offset + k, point, last));
+            }
+        } else {
+            for (k, point) in ring.iter().enumerate().rev() {
+                last = Some(self.insert_node(offset + k, point, last));
+            }
+        }
+        if let Some(node) = last
+            && self.equals(node, self.next(node))
+        {
+            self.remove_node(node);
+            last = Some(self.next(node));
+        }
+        last
+    }
+
+    /// Removes repeated and collinear points between `start` and `end`.
+    fn filter_points(&mut self, start: usize, end: Option<usize>) -> usize {
+        let mut end = end.unwrap_or(start);
+        let mut p = start;
+        loop {
+            let mut again = false;
+            if !self.nodes[p].steiner && (self.equals(p, self.next(p)) || self.area(self.prev(p), p, self.next(p)) == T::zero()) {
+                self.remove_node(p);
+                p = self.prev(p);
+                end = p;
+                if p == self.next(p) {
+                    break;
+                }
+                again = true;
+            } else {
+                p = self.next(p);
+            }
+            if !again && p == end {
+                break;
+            }
+        }
+        end
+    }
+
+    /// Clips ears until one triangle is left. When no ear is found the list is cleaned up,
+    /// then local self-intersections are cut off, and finally the polygon is split in two.
+    fn earcut_linked(&mut self, mut ear: usize, pass: u8) {
+        if pass == 0 && self.hash.is_some() {
+            self.index_curve(ear);
+        }
+        let mut stop = ear;
+        while self.prev(ear) != self.next(ear) {
+            let (prev, next) = (self.prev(ear), self.next(ear));
+            let is_ear = if self.hash.is_some() { self.is_ear_hashed(ear) } else { self.is_ear(ear) };
+            if is_ear {
+                self.triangles.push([self.nodes[prev].i, self.nodes[ear].i, self.nodes[next].i]);
+                self.remove_node(ear);
+                ear = self.next(next);
+                stop = ear;
+                continue;
+            }
+            ear = next;
+            if ear == stop {
+                match pass {
+                    0 => {
+                        let start = self.filter_points(ear, None);
+                        self.earcut_linked(start, 1);
+                    }
+                    1 => {
+                        let start = self.filter_points(ear, None);
+                        let start = self.cure_local_intersections(start);
+                        self.earcut_linked(start, 2);
+                    }
+                    _ => self.split_earcut(ear),
+                }
+                break;
+            }
+        }
+    }
+
+    /// Returns true if the corner at `ear` is convex and no other reflex vertex lies in it.
+    fn is_ear(&self, ear: usize) -> bool {
+        let (a, b, c) = (self.prev(ear), ear, self.next(ear));
+        if self.area(a, b, c) >= T::zero() {
+            return false;
+        }
+        let (na, nb, nc) = (&self.nodes[a], &self.nodes[b], &self.nodes[c]);
+        let (x0, x1) = (na.x.min(nb.x).min(nc.x), na.x.max(nb.x).max(nc.x));
+        let (y0, y1) = (na.y.min(nb.y).min(nc.y), na.y.max(nb.y).max(nc.y));
+        let mut p = self.next(c);
+        while p != a {
+            let np = &self.nodes[p];
+            if np.x >= x0
+                && np.x <= x1
+                && np.y >= y0
+                && np.y <= y1
+                && point_in_triangle((na.x, na.y), (nb.x, nb.y), (nc.x, nc.y), (np.x, np.y))
+                && self.area(self.prev(p), p, self.next(p)) >= T::zero()
+            {
+                return false;
+            }
+            p = self.next(p);
+        }
+        true
+    }
+
+    /// Like `is_ear`, but only looks at the vertices whose z-order lies between those of the
+    /// corners of the ear's bounding box, walking out from the ear in both directions.
+    fn is_ear_hashed(&self, ear: usize) -> bool {
+        let (a, b, c) = (self.prev(ear), ear, self.next(ear));
+        if self.area(a, b, c) >= T::zero() {
+            return false;
+        }
+        let Some(hash) = &self.hash else {
+            return self.is_ear(ear);
+        };
+        let (na, nb, nc) = (&self.nodes[a], &self.nodes[b], &self.nodes[c]);
+        let (x0, x1) = (na.x.min(nb.x).min(nc.x), na.x.max(nb.x).max(nc.x));
+        let (y0, y1) = (na.y.min(nb.y).min(nc.y), na.y.max(nb.y).max(nc.y));
+        let (min_z, max_z) = (hash.z(x0, y0), hash.z(x1, y1));
+        let blocks = |p: usize| {
+            let np = &self.nodes[p];
+            p != a
+                && p != c
+                && np.x >= x0
+                && np.x <= x1
+                && np.y >= y0
+                && np.y <= y1
+                && point_in_triangle((na.x, na.y), (nb.x, nb.y), (nc.x, nc.y), (np.x, np.y))
+                && self.area(self.prev(p), p, self.next(p)) >= T::zero()
+        };
+        let (mut p, mut n) = (self.nodes[ear].prev_z, self.nodes[ear].next_z);
+        loop {
+            let down = p.filter(|&p| self.nodes[p].z >= min_z);
+            let up = n.filter(|&n| self.nodes[n].z <= max_z);
+            if down.is_none() && up.is_none() {
+                return true;
+            }
+            if let Some(down) = down {
+                if blocks(down) {
+                    return false;
+                }
+                p = self.nodes[down].prev_z;
+            } else {
+                p = None;
+            }
+            if let Some(up) = up {
+                if blocks(up) {
+                    return false;
+                }
+                n = self.nodes[up].next_z;
+            } else {
+                n = None;
+            }
+        }
+    }
+
+    /// Computes the z-order of every vertex of the list and links them sorted by it.
+    fn index_curve(&mut self, start: usize) {
+        let Some(hash) = &self.hash else {
+            return;
+        };
+        let mut ring = Vec::new();
+        let mut p = start;
+        loop {
+            self.nodes[p].z = hash.z(self.nodes[p].x, self.nodes[p].y);
+            ring.push(p);
+            p = self.next(p);
+            if p == start {
+                break;
+            }
+        }
+        ring.sort_by_key(|&p| self.nodes[p].z);
+        for (k, &p) in ring.iter().enumerate() {
+            self.nodes[p].prev_z = k.checked_sub(1).map(|k| ring[k]);
+            self.nodes[p].next_z = ring.get(k + 1).copied();
+        }
+    }
+
+    /// Cuts off the small triangles formed where two edges a vertex apart cross.
+    fn cure_local_intersections(&mut self, mut start: usize) -> usize {
+        let mut p = start;
+        loop {
+            let a = self.prev(p);
+            let b = self.next(self.next(p));
+            if !self.equals(a, b)
+                && self.intersects(a, p, self.next(p), b)
+                && self.locally_inside(a, b)
+                && self.locally_inside(b, a)
+            {
+                self.triangles.push([self.nodes[a].i, self.nodes[p].i, self.nodes[b].i]);
+                self.remove_node(p);
+                let after = self.next(p);
+                self.remove_node(after);
+                p = b;
+                start = b;
+            }
+            p = self.next(p);
+            if p == start {
+                break;
+            }
+        }
+        self.filter_points(p, None)
+    }
+
+    /// Splits the polygon along a valid diagonal and triangulates both halves.
+    fn split_earcut(&mut self, start: usize) {
+        let mut a = start;
+        loop {
+            let mut b = self.next(self.next(a));
+            while b != self.prev(a) {
+                if self.nodes[a].i != self.nodes[b].i && self.is_valid_diagonal(a, b) {
+                    let c = self.split_polygon(a, b);
+                    let a = self.filter_points(a, Some(self.next(a)));
+                    let c = self.filter_points(c, Some(self.next(c)));
+                    self.earcut_linked(a, 0);
+                    self.earcut_linked(c, 0);
+                    return;
+                }
+                b = self.next(b);
+            }
+            a = self.next(a);
+            if a == start {
+                return;
+            }
+        }
+    }
+
+    /// Joins each hole to the outer list with a pair of bridge edges, leftmost hole first.
+    fn eliminate_holes(&mut self, holes: Vec<usize>, mut outer: usize) -> usize {
+        let mut leftmost: Vec<usize> = holes.into_iter().map(|hole| self.leftmost(hole)).collect();
+        leftmost.sort_by(|&a, &b| self.nodes[a].x.partial_cmp(&self.nodes[b].x).unwrap_or(std::cmp::Ordering::Equal));
+        for hole in leftmost {
+            if let Some(bridge) = self.find_hole_bridge(hole, outer) {
+                let reverse = self.split_polygon(bridge, hole);
+                self.filter_points(reverse, Some(self.next(reverse)));
+                outer = self.filter_points(bridge, Some(self.next(bridge)));
+            }
+        }
+        outer
+    }
+
+    fn leftmost(&self, start: usize) -> usize {
+        let mut p = start;
+        let mut leftmost = start;
+        loop {
+            let (np, nl) = (&self.nodes[p], &self.nodes[leftmost]);
+            if np.x < nl.x || (np.x == nl.x && np.y < nl.y) {
+                leftmost = p;
+            }
+            p = self.next(p);
+            if p == start {
+                return leftmost;
+            }
+        }
+    }
+
+    /// Finds an outer vertex that the leftmost point of a hole can be connected to.
+    fn find_hole_bridge(&self, hole: usize, outer: usize) -> Option<usize> {
+        let (hx, hy) = (self.nodes[hole].x, self.nodes[hole].y);
+        let mut qx = T::neg_infinity();
+        let mut m = None;
+        // Cast a ray to the left and find the nearest edge it hits
+        let mut p = outer;
+        loop {
+            let (np, nn) = (&self.nodes[p], &self.nodes[self.next(p)]);
+            if hy <= np.y && hy >= nn.y && nn.y != np.y {
+                let x = np.x + (hy - np.y) * (nn.x - np.x) / (nn.y - np.y);
+                if x <= hx && x > qx {
+                    qx = x;
+                    m = Some(if np.x < nn.x { p } else { self.next(p) });
+                    if x == hx {
+                        return m; // The hole touches the outer ring
+                    }
+                }
+            }
+            p = self.next(p);
+            if p == outer {
+                break;
+            }
+        }
+        let mut m = m?;
+        // A vertex inside the triangle formed by the hole point, the hit point and `m` would
+        // block the bridge; pick the one closest in angle to the ray instead
+        let stop = m;
+        let (mx, my) = (self.nodes[m].x, self.nodes[m].y);
+        let mut tan_min = T::infinity();
+        let mut p = m;
+        loop {
+            let np = &self.nodes[p];
+            let (a, c) = if hy < my { ((hx, hy), (qx, hy)) } else { ((qx, hy), (hx, hy)) };
+            if hx >= np.x && np.x >= mx && hx != np.x && point_in_triangle(a, (mx, my), c, (np.x, np.y)) {
+                let tan = (hy - np.y).abs() / (hx - np.x);
+                if self.locally_inside(p, hole)
+                    && (tan < tan_min
+                        || (tan == tan_min
+                            && (np.x > self.nodes[m].x || (np.x == self.nodes[m].x && self.sector_contains_sector(m, p)))))
+                {
+                    m = p;
+                    tan_min = tan;
+                }
+            }
+            p = self.next(p);
+            if p == stop {
+                return Some(m);
+            }
+        }
+    }
+
+    /// Returns true if the corner at `m` lies inside the corner at `p`.
+    fn sector_contains_sector(&self, m: usize, p: usize) -> bool {
+        self.area(self.prev(m), m, self.prev(p)) < T::zero() && self.area(self.next(p), m, self.next(m)) < T::zero()
+    }
+
+    fn is_valid_diagonal(&self, a: usize, b: usize) -> bool {
+        let ib = self.nodes[b].i;
+        self.nodes[self.next(a)].i != ib
+            && self.nodes[self.prev(a)].i != ib
+            && !self.intersects_polygon(a, b)
+            && ((self.locally_inside(a, b)
+                && self.locally_inside(b, a)
+                && self.middle_inside(a, b)
+                && (self.area(self.prev(a), a, self.prev(b)) != T::zero() || self.area(a, self.prev(b), b) != T::zero()))
+                || (self.equals(a, b)
+                    && self.area(self.prev(a), a, self.next(a)) > T::zero()
+                    && self.area(self.prev(b), b, self.next(b)) > T::zero()))
+    }
+
+    /// Returns true if the segments `p1 - q1` and `p2 - q2` share a point.
+    fn intersects(&self, p1: usize, q1: usize, p2: usize, q2: usize) -> bool {
+        let sign = |v: T| {
+            if v > T::zero() {
+                1
+            } else if v < T::zero() {
+                -1
+            } else {
+                0
+            }
+        };
+        let o1 = sign(self.area(p1, q1, p2));
+        let o2 = sign(self.area(p1, q1, q2));
+        let o3 = sign(self.area(p2, q2, p1));
+        let o4 = sign(self.area(p2, q2, q1));
+        (o1 != o2 && o3 != o4)
+            || (o1 == 0 && self.on_segment(p1, p2, q1))
+            || (o2 == 0 && self.on_segment(p1, q2, q1))
+            || (o3 == 0 && self.on_segment(p2, p1, q2))
+            || (o4 == 0 && self.on_segment(p2, q1, q2))
+    }
+
+    /// For collinear points, returns true if `q` lies on the segment `p - r`.
+    fn on_segment(&self, p: usize, q: usize, r: usize) -> bool {
+        let (p, q, r) = (&self.nodes[p], &self.nodes[q], &self.nodes[r]);
+        q.x <= p.x.max(r.x) && q.x >= p.x.min(r.x) && q.y <= p.y.max(r.y) && q.y >= p.y.min(r.y)
+    }
+
+    fn intersects_polygon(&self, a: usize, b: usize) -> bool {
+        let (ia, ib) = (self.nodes[a].i, self.nodes[b].i);
+        let mut p = a;
+        loop {
+            let next = self.next(p);
+            let (ip, inext) = (self.nodes[p].i, self.nodes[next].i);
+            if ip != ia && inext != ia && ip != ib && inext != ib && self.intersects(p, next, a, b) {
+                return true;
+            }
+            p = next;
+            if p == a {
+                return false;
+            }
+        }
+    }
+
+    /// Returns true if the diagonal from `a` to `b` starts into the inside of the polygon.
+    fn locally_inside(&self, a: usize, b: usize) -> bool {
+        let (prev, next) = (self.prev(a), self.next(a));
+        if self.area(prev, a, next) < T::zero() {
+            self.area(a, b, next) >= T::zero() && self.area(a, prev, b) >= T::zero()
+        } else {
+            self.area(a, b, prev) < T::zero() || self.area(a, next, b) < T::zero()
+        }
+    }
+
+    /// Returns true if the midpoint of the diagonal `a - b` is inside the polygon.
+    fn middle_inside(&self, a: usize, b: usize) -> bool {
+        let two = T::one() + T::one();
+        let (px, py) = ((self.nodes[a].x + self.nodes[b].x) / two, (self.nodes[a].y + self.nodes[b].y) / two);
+        let mut inside = false;
+        let mut p = a;
+        loop {
+            let (np, nn) = (&self.nodes[p], &self.nodes[self.next(p)]);
+            if (np.y > py) != (nn.y > py) && nn.y != np.y && px < (nn.x - np.x) * (py - np.y) / (nn.y - np.y) + np.x {
+                inside = !inside;
+            }
+            p = self.next(p);
+            if p == a {
+                return inside;
+            }
+        }
+    }
+
+    /// Links `a` to `b` with a diagonal, splitting the list in two by duplicating both
+    /// vertices. Returns the copy of `b`, which starts the second list.
+    fn split_polygon(&mut self, a: usize, b: usize) -> usize {
+        let (ia, ib) = (self.nodes[a].i, self.nodes[b].i);
+        let (pa, pb) = (Point2d::new(self.nodes[a].x, self.nodes[a].y), Point2d::new(self.nodes[b].x, self.nodes[b].y));
+        let a2 = self.insert_node(ia, &pa, None);
+        let b2 = self.insert_node(ib, &pb, None);
+        let (an, bp) = (self.next(a), self.prev(b));
+        self.nodes[a].next = b;
+        self.nodes[b].prev = a;
+        self.nodes[a2].next = an;
+        self.nodes[an].prev = a2;
+        self.nodes[b2].next = a2;
+        self.nodes[a2].prev = b2;
+        self.nodes[bp].next = b2;
+        self.nodes[b2].prev = bp;
+        b2
+    }
+}
+
+/// Returns true if `p` lies inside or on the counter-clockwise triangle `a, b, c`.
+fn point_in_triangle<T: Float>(a: (T, T), b: (T, T), c: (T, T), p: (T, T)) -> bool {
+    (c.0 - p.0) * (a.1 - p.1) >= (a.0 - p.0) * (c.1 - p.1)
+        && (a.0 - p.0) * (b.1 - p.1) >= (b.0 - p.0) * (a.1 - p.1)
+        && (b.0 - p.0) * (c.1 - p.1) >= (c.0 - p.0) * (b.1 - p.1)
+}
+
+impl<T: Float> Polygon2d<T> {
+    /// Returns the points of the exterior followed by the points of each hole in order;
+    /// this is the vertex list `triangulate` indexes into.
+    pub fn vertices(&self) -> Vec<Point2d<T>> {
+        self.rings().flatten().copied().collect()
+    }
+    /// Triangulates the polygon by ear clipping, with each hole joined to the exterior by a bridge.
+    ///
+    /// Returns counter-clockwise triangles as indices into `vertices()`. No points are
+    /// added, and degenerate or slightly self-intersecting input still gives a result. Above 80
+    /// vertices, the vertices that could block an ear are found along a z-order curve instead of
+    /// scanning the whole polygon.
+    pub fn triangulate(&self) -> Vec<[usize; 3]> {
+        let mut earcut = Earcut { nodes: Vec::new(), triangles: Vec::new(), hash: None };
+        let Some(mut outer) = earcut.linked_list(&self.exterior, 0, true) else {
+            return Vec::new();
+        };
+        if earcut.next(outer) == earcut.prev(outer) {
+            return Vec::new();
+        }
+        let mut offset = self.exterior.len();
+        let mut holes = Vec::new();
+        for ring in &self.interiors {
+            if let Some(hole) = earcut.linked_list(ring, offset, false) {
+                if hole == earcut.next(hole) {
+                    earcut.nodes[hole].steiner = true;
+                }
+                holes.push(hole);
+            }
+            offset += ring.len();
+        }
+        if !holes.is_empty() {
+            outer = earcut.eliminate_holes(holes, outer);
+        }
+        // Below about 80 vertices scanning the whole list is cheaper than sorting it
+        if offset > 80 {
+            let (mut min, mut max) = (self.exterior[0], self.exterior[0]);
+            for p in &self.exterior {
+                min = Point2d::new(min.x().min(p.x()), min.y().min(p.y()));
+                max = Point2d::new(max.x().max(p.x()), max.y().max(p.y()));
+            }
+            let size = (max.x() - min.x()).max(max.y() - min.y());
+            if size > T::zero() {
+                let inv_size = T::from(32767.0).unwrap() / size;
+                earcut.hash = Some(ZOrder { min_x: min.x(), min_y: min.y(), inv_size });
+            }
+        }
+        earcut.earcut_linked(outer, 0);
+        earcut.triangles
+    }
+}
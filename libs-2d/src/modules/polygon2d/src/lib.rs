@@ -0,0 +1,153 @@
+use line2d::Line2d;
+use num_traits::Float;
+use point2d::Point2d;
+
+mod boolean;
+mod earcut;
+mod offset;
+mod overlay;
+pub use boolean::BooleanOp;
+pub use offset::{offset_polyline, EndType, JoinType};
+
+/// A polygon made of an exterior ring and any number of holes.
+///
+/// Rings are stored without repeating their first point at the end. Polygons
+/// produced by this crate have a counter-clockwise exterior and clockwise holes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polygon2d<T> {
+    exterior: Vec<Point2d<T>>,
+    interiors: Vec<Vec<Point2d<T>>>,
+}
+
+/// A set of polygons whose interiors do not overlap.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct MultiPolygon2d<T>(pub Vec<Polygon2d<T>>);
+
+/// Returns twice the signed area of a ring: positive for counter-clockwise rings.
+pub fn ring_signed_area_doubled<T: Float>(ring: &[Point2d<T>]) -> T {
+    let n = ring.len();
+    (0..n).fold(T::zero(), |sum, i| {
+        let p = ring[i];
+        let q = ring[(i + 1) % n];
+        sum + p.x() * q.y() - q.x() * p.y()
+    })
+}
+
+/// Returns the signed area of a ring: positive for counter-clockwise rings.
+pub fn ring_signed_area<T: Float>(ring: &[Point2d<T>]) -> T {
+    ring_signed_area_doubled(ring) / T::from(2.0).unwrap()
+}
+
+/// Returns true if `point` lies inside `ring` by the even-odd rule.
+pub fn ring_contains<T: Float>(ring: &[Point2d<T>], point: &Point2d<T>) -> bool {
+    let n = ring.len();
+    let mut inside = false;
+    for i in 0..n {
+        let p = ring[i];
+        let q = ring[(i + 1) % n];
+        if (p.y() > point.y()) != (q.y() > point.y()) {
+            let x = p.x() + (point.y() - p.y()) * (q.x() - p.x()) / (q.y() - p.y());
+            if point.x() < x {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+/// Drops a trailing copy of the first point and consecutive duplicates.
+fn clean_ring<T: Float>(mut ring: Vec<Point2d<T>>) -> Vec<Point2d<T>> {
+    ring.dedup();
+    while ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    ring
+}
+
+impl<T: Float> Polygon2d<T> {
+    /// Creates a polygon from its rings, dropping a repeated closing point.
+    /// Ring orientation is kept as given; see `oriented`.
+    pub fn new(exterior: Vec<Point2d<T>>, interiors: Vec<Vec<Point2d<T>>>) -> Self {
+        Polygon2d {
+            exterior: clean_ring(exterior),
+            interiors: interiors.into_iter().map(clean_ring).collect(),
+        }
+    }
+    pub fn from_exterior(exterior: Vec<Point2d<T>>) -> Self {
+        Self::new(exterior, Vec::new())
+    }
+    pub fn exterior(&self) -> &[Point2d<T>] {
+        &self.exterior
+    }
+    pub fn interiors(&self) -> &[Vec<Point2d<T>>] {
+        &self.interiors
+    }
+    /// Iterates over the exterior ring followed by the holes.
+    pub fn rings(&self) -> impl Iterator<Item = &[Point2d<T>]> {
+        std::iter::once(self.exterior.as_slice()).chain(self.interiors.iter().map(|ring| ring.as_slice()))
+    }
+    /// Returns every edge of every ring.
+    pub fn edges(&self) -> Vec<Line2d<T>> {
+        self.rings()
+            .flat_map(|ring| (0..ring.len()).map(move |i| Line2d::new(ring[i], ring[(i + 1) % ring.len()])))
+            .collect()
+    }
+    /// Returns the area enclosed by the exterior minus the area of the holes.
+    pub fn area(&self) -> T {
+        self.interiors
+            .iter()
+            .fold(ring_signed_area(&self.exterior).abs(), |area, hole| area - ring_signed_area(hole).abs())
+    }
+    pub fn perimeter(&self) -> T {
+        self.edges().iter().fold(T::zero(), |sum, edge| sum + edge.length())
+    }
+    /// Returns true if the exterior ring runs counter-clockwise.
+    pub fn is_ccw(&self) -> bool {
+        ring_signed_area_doubled(&self.exterior) > T::zero()
+    }
+    /// Returns a copy with a counter-clockwise exterior and clockwise holes.
+    pub fn oriented(&self) -> Self {
+        let orient = |ring: &Vec<Point2d<T>>, ccw: bool| {
+            let mut ring = ring.clone();
+            if (ring_signed_area_doubled(&ring) > T::zero()) != ccw {
+                ring.reverse();
+            }
+            ring
+        };
+        Polygon2d {
+            exterior: orient(&self.exterior, true),
+            interiors: self.interiors.iter().map(|hole| orient(hole, false)).collect(),
+        }
+    }
+    /// Returns true if `point` lies inside the exterior and outside every hole.
+    pub fn contains(&self, point: &Point2d<T>) -> bool {
+        ring_contains(&self.exterior, point) && !self.interiors.iter().any(|hole| ring_contains(hole, point))
+    }
+}
+
+impl<T: Float> MultiPolygon2d<T> {
+    pub fn new(polygons: Vec<Polygon2d<T>>) -> Self {
+        MultiPolygon2d(polygons)
+    }
+    pub fn polygons(&self) -> &[Polygon2d<T>] {
+        &self.0
+    }
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    pub fn area(&self) -> T {
+        self.0.iter().fold(T::zero(), |sum, polygon| sum + polygon.area())
+    }
+    pub fn contains(&self, point: &Point2d<T>) -> bool {
+        self.0.iter().any(|polygon| polygon.contains(point))
+    }
+}
+
+impl<T> From<Polygon2d<T>> for MultiPolygon2d<T> {
+    fn from(polygon: Polygon2d<T>) -> Self {
+        MultiPolygon2d(vec![polygon])
+    }
+}
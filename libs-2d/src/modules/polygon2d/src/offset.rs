@@ -0,0 +1,210 @@
+use line2d::Line2d;
+use point2d::Point2d;
+use vector2d::Vector2d;
+
+use crate::overlay::Overlay;
+use crate::{MultiPolygon2d, Polygon2d};
+
+type Point = Point2d<f64>;
+/// Unit direction and unit right-hand normal of an edge.
+type Edge = (Vector2d<f64>, Vector2d<f64>);
+
+/// How the offset outline goes around a convex corner.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum JoinType {
+    /// Extends both edges until they meet. Corners whose miter would reach further than
+    /// `limit` times the offset distance are squared off instead.
+    Miter(f64),
+    /// Follows a circular arc, approximated by chords that stay within the given tolerance.
+    Round(f64),
+    /// Cuts the corner at the offset distance, perpendicular to the corner bisector.
+    Square,
+}
+
+/// How the offset outline of an open polyline closes around its two ends.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum EndType {
+    /// Ends flush with the end points.
+    Butt,
+    /// Ends with a half circle, approximated by chords that stay within the given tolerance.
+    Round(f64),
+    /// Ends with a half square extending the offset distance past the end points.
+    Square,
+}
+
+fn add(p: Point, v: Vector2d<f64>, scale: f64) -> Point {
+    Point::new(p.x() + v.x * scale, p.y() + v.y * scale)
+}
+
+/// Unit normal on the right-hand side of the edge `a -> b`.
+fn right_normal(a: Point, b: Point) -> Vector2d<f64> {
+    unit(Line2d::new(a, b).normal_vector()).negate()
+}
+
+/// The vector scaled to length one, or the zero vector for a zero one.
+fn unit(v: Vector2d<f64>) -> Vector2d<f64> {
+    v.normalized().map_or(Vector2d::null(), Vector2d::from)
+}
+
+fn edge(a: Point, b: Point) -> Edge {
+    (unit(Vector2d::new(b.x() - a.x(), b.y() - a.y())), right_normal(a, b))
+}
+
+/// Appends the points of an arc of `radius` around `center`, starting in the unit direction
+/// `from` and sweeping by `sweep` radians.
+fn push_arc(out: &mut Vec<Point>, center: Point, from: Vector2d<f64>, sweep: f64, radius: f64, tolerance: f64) {
+    let tolerance = tolerance.max(radius * 1e-6).min(radius);
+    let max_step = 2.0 * (1.0 - tolerance / radius).acos();
+    let steps = if max_step > 0.0 { (sweep.abs() / max_step).ceil().max(1.0) as usize } else { 1 };
+    for i in 0..=steps {
+        let direction = from.rotate(sweep * i as f64 / steps as f64);
+        out.push(add(center, direction, radius));
+    }
+}
+
+/// Appends the offset of the corner at `p` between an edge with unit direction `d1` and right
+/// normal `n1` and the following edge with `d2` and `n2`.
+fn push_join(out: &mut Vec<Point>, p: Point, (d1, n1): Edge, (d2, n2): Edge, delta: f64, join: JoinType) {
+    let q1 = add(p, n1, delta);
+    let q2 = add(p, n2, delta);
+    let turn = d1.cross(&d2);
+    if turn.abs() < 1e-12 && d1.dot(&d2) > 0.0 {
+        out.push(q1); // Straight continuation
+        return;
+    }
+    if turn * delta < 0.0 {
+        // The offset edges overlap on this side; going through the vertex leaves a loop
+        // that the cleanup removes
+        out.push(q1);
+        out.push(p);
+        out.push(q2);
+        return;
+    }
+    let radius = delta.abs();
+    let cos_half = ((1.0 + n1.dot(&n2)) / 2.0).max(0.0).sqrt();
+    let join = match join {
+        JoinType::Miter(limit) if cos_half * limit < 1.0 => JoinType::Square,
+        join => join,
+    };
+    match join {
+        JoinType::Miter(_) => {
+            let scale = delta / (1.0 + n1.dot(&n2));
+            out.push(Point::new(p.x() + (n1.x + n2.x) * scale, p.y() + (n1.y + n2.y) * scale));
+        }
+        JoinType::Round(tolerance) => {
+            let v1 = n1.scale(delta.signum());
+            let v2 = n2.scale(delta.signum());
+            let sweep = v1.cross(&v2).atan2(v1.dot(&v2));
+            push_arc(out, p, v1, sweep, radius, tolerance);
+        }
+        JoinType::Square => {
+            // Cut perpendicular to the bisector, at the offset distance from the vertex
+            let sum = n1 + n2;
+            let bisector = if sum.length() < 1e-12 { d1 } else { unit(sum).scale(delta.signum()) };
+            let along = d1.dot(&bisector);
+            let s = if along.abs() < 1e-12 { 0.0 } else { radius * (1.0 - cos_half) / along };
+            out.push(add(q1, d1, s));
+            out.push(add(q2, d2, -s));
+        }
+    }
+}
+
+/// Returns the raw offset outline of a closed ring, which may contain loops.
+fn offset_ring(ring: &[Point], delta: f64, join: JoinType) -> Vec<Point> {
+    let n = ring.len();
+    let edges: Vec<Edge> = (0..n).map(|i| edge(ring[i], ring[(i + 1) % n])).collect();
+    let mut out = Vec::new();
+    for i in 0..n {
+        push_join(&mut out, ring[i], edges[(i + n - 1) % n], edges[i], delta, join);
+    }
+    out
+}
+
+/// Appends the cap around the end point `p` of a polyline reached along the unit direction `d`.
+fn push_cap(out: &mut Vec<Point>, p: Point, d: Vector2d<f64>, delta: f64, end: EndType) {
+    let n = d.perpendicular_neg();
+    match end {
+        EndType::Butt => {
+            out.push(add(p, n, delta));
+            out.push(add(p, n, -delta));
+        }
+        EndType::Square => {
+            out.push(add(add(p, n, delta), d, delta));
+            out.push(add(add(p, n, -delta), d, delta));
+        }
+        EndType::Round(tolerance) => push_arc(out, p, n, std::f64::consts::PI, delta, tolerance),
+    }
+}
+
+fn clean_up(raw: Vec<Vec<Point>>) -> MultiPolygon2d<f64> {
+    Overlay::new(&[raw]).extract(|winding| winding[0] > 0)
+}
+
+impl Polygon2d<f64> {
+    /// Grows the polygon by `delta`, or shrinks it for a negative `delta`.
+    ///
+    /// Holes shrink as the exterior grows and the other way round. Parts that
+    /// vanish and loops created by the offset are removed, so the result may hold
+    /// several polygons or none.
+    pub fn offset(&self, delta: f64, join: JoinType) -> MultiPolygon2d<f64> {
+        MultiPolygon2d(vec![self.clone()]).offset(delta, join)
+    }
+}
+
+impl MultiPolygon2d<f64> {
+    /// Grows every polygon by `delta`, or shrinks them for a negative `delta`.
+    /// Polygons that grow into each other are merged.
+    pub fn offset(&self, delta: f64, join: JoinType) -> MultiPolygon2d<f64> {
+        let mut raw = Vec::new();
+        for polygon in &self.0 {
+            for ring in polygon.oriented().rings() {
+                if ring.len() >= 3 {
+                    raw.push(if delta == 0.0 { ring.to_vec() } else { offset_ring(ring, delta, join) });
+                }
+            }
+        }
+        clean_up(raw)
+    }
+}
+
+/// Returns the area within `distance` of an open polyline, with the given corner joins and end caps.
+pub fn offset_polyline(points: &[Point2d<f64>], distance: f64, join: JoinType, end: EndType) -> MultiPolygon2d<f64> {
+    let mut path = points.to_vec();
+    path.dedup();
+    let delta = distance.abs();
+    if path.is_empty() || delta == 0.0 {
+        return MultiPolygon2d::default();
+    }
+    if path.len() == 1 {
+        // A single point becomes a circle or a square around it
+        let p = path[0];
+        let mut outline = Vec::new();
+        match end {
+            EndType::Butt => return MultiPolygon2d::default(),
+            EndType::Round(tolerance) => {
+                push_arc(&mut outline, p, Vector2d::new(1.0, 0.0), 2.0 * std::f64::consts::PI, delta, tolerance)
+            }
+            EndType::Square => {
+                for (x, y) in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)] {
+                    outline.push(Point::new(p.x() + x * delta, p.y() + y * delta));
+                }
+            }
+        }
+        return clean_up(vec![outline]);
+    }
+    // Walk down the right side, around the far end, back up the other side and around the start
+    let forward: Vec<Edge> = path.windows(2).map(|pair| edge(pair[0], pair[1])).collect();
+    let last = path.len() - 1;
+    let mut outline = Vec::new();
+    outline.push(add(path[0], forward[0].1, delta));
+    for i in 1..last {
+        push_join(&mut outline, path[i], forward[i - 1], forward[i], delta, join);
+    }
+    push_cap(&mut outline, path[last], forward[last - 1].0, delta, end);
+    let backward: Vec<Edge> = forward.iter().rev().map(|(d, n)| (d.negate(), n.negate())).collect();
+    for i in 1..last {
+        push_join(&mut outline, path[last - i], backward[i - 1], backward[i], delta, join);
+    }
+    push_cap(&mut outline, path[0], backward[last - 1].0, delta, end);
+    clean_up(vec![outline])
+}
@@ -0,0 +1,620 @@
+//! Planar overlay of rings, shared by the boolean operations and offsetting.
+//!
+//! A first sweep splits every input edge wherever it meets another edge, so
+//! that the resulting sub-edges only touch at their end points. Coincident
+//! sub-edges are merged, and a second sweep computes the winding number of
+//! every input source on both sides of each one from the sub-edge below it.
+//! A fill rule then decides which sub-edges separate filled from empty space;
+//! those are chained into output rings.
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeSet, BinaryHeap, HashMap};
+use std::ops::Bound::{Excluded, Unbounded};
+
+use point2d::Point2d;
+use predicates2d::{in_box, orient2d};
+
+use crate::{MultiPolygon2d, Polygon2d, ring_signed_area_doubled};
+
+type Point = Point2d<f64>;
+type Key = (u64, u64);
+
+fn key(p: &Point) -> Key {
+    // -0.0 and 0.0 must map to the same vertex
+    let bits = |v: f64| if v == 0.0 { 0u64 } else { v.to_bits() };
+    (bits(p.x()), bits(p.y()))
+}
+
+/// Orders points from left to right, then from bottom to top, the order of both sweeps.
+fn compare_points(p: &Point, q: &Point) -> Ordering {
+    let order = |u: f64, v: f64| u.partial_cmp(&v).unwrap_or(Ordering::Equal);
+    order(p.x(), q.x()).then(order(p.y(), q.y()))
+}
+
+/// A segment in a sweep status, from its first end `a` in sweep order to `b`.
+#[derive(Copy, Clone, Debug)]
+struct Segment {
+    a: Point,
+    b: Point,
+    id: usize,
+}
+
+impl Segment {
+    /// Orders this segment, which starts no earlier than `older`, against it by the side of
+    /// `older` its start lies on, or its end if the start is on `older`.
+    fn side_of(&self, older: &Segment) -> Ordering {
+        for p in [&self.a, &self.b] {
+            let turn = orient2d(&older.a, &older.b, p);
+            if turn != 0.0 {
+                return if turn > 0.0 { Ordering::Greater } else { Ordering::Less };
+            }
+        }
+        // Collinear segments only overlap until the first sweep splits and merges them
+        self.id.cmp(&older.id)
+    }
+}
+
+// Segments crossed by the same sweep line are ordered from bottom to top. The order only
+// holds for segments that do not cross, which the sweeps ensure for those in their status.
+impl Ord for Segment {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.id == other.id {
+            Ordering::Equal
+        } else if compare_points(&self.a, &other.a) != Ordering::Less {
+            self.side_of(other)
+        } else {
+            other.side_of(self).reverse()
+        }
+    }
+}
+impl PartialOrd for Segment {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl PartialEq for Segment {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+impl Eq for Segment {}
+
+/// A segment entering the sweep at its first end or leaving it at its last. Events at the
+/// same point handle leaving segments first, then entering ones from bottom to top.
+#[derive(Copy, Clone, Debug)]
+struct Event {
+    point: Point,
+    enters: bool,
+    segment: Segment,
+}
+
+impl Ord for Event {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare_points(&self.point, &other.point).then(self.enters.cmp(&other.enters)).then_with(|| {
+            if self.enters && other.enters {
+                self.segment.cmp(&other.segment)
+            } else {
+                self.segment.id.cmp(&other.segment.id)
+            }
+        })
+    }
+}
+impl PartialOrd for Event {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl PartialEq for Event {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for Event {}
+
+/// Removes a segment from a sweep status, falling back to a scan should rounding have
+/// upset the order around it.
+fn remove(status: &mut BTreeSet<Segment>, segment: &Segment) {
+    if !status.remove(segment) {
+        status.retain(|s| s.id != segment.id);
+    }
+}
+
+/// A sub-edge shared by one or more input edges, from its first end `a` in sweep order.
+struct SubEdge {
+    a: Point,
+    b: Point,
+    /// Per source, the number of input edges running `a -> b` minus those running `b -> a`.
+    count: Vec<i32>,
+    /// Per source, the winding number just left of the sub-edge.
+    winding_left: Vec<i32>,
+}
+
+pub(crate) struct Overlay {
+    edges: Vec<SubEdge>,
+}
+
+/// Merges points that are closer than a tolerance, so that intersection
+/// points computed from different edge pairs end up as the same vertex.
+struct Snapper {
+    cell: f64,
+    grid: HashMap<(i64, i64), Vec<Point>>,
+}
+
+impl Snapper {
+    fn cell_of(&self, p: &Point) -> (i64, i64) {
+        ((p.x() / self.cell).floor() as i64, (p.y() / self.cell).floor() as i64)
+    }
+    fn insert(&mut self, p: Point) {
+        let cell = self.cell_of(&p);
+        self.grid.entry(cell).or_default().push(p);
+    }
+    fn snap(&mut self, p: Point) -> Point {
+        let (cx, cy) = self.cell_of(&p);
+        let mut best: Option<(f64, Point)> = None;
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for q in self.grid.get(&(cx + dx, cy + dy)).into_iter().flatten() {
+                    let d = p.distance_to(q);
+                    if d <= self.cell && best.is_none_or(|(best_d, _)| d < best_d) {
+                        best = Some((d, *q));
+                    }
+                }
+            }
+        }
+        match best {
+            Some((_, q)) => q,
+            None => {
+                self.insert(p);
+                p
+            }
+        }
+    }
+}
+
+/// Returns the crossing point of two pieces that properly cross, computed from the input edges
+/// they were cut from and kept inside both bounding boxes.
+fn crossing_point(s: &Piece, t: &Piece) -> Point {
+    let ([a0, a1], [b0, b1]) = (s.edge, t.edge);
+    let rx = a1.x() - a0.x();
+    let ry = a1.y() - a0.y();
+    let sx = b1.x() - b0.x();
+    let sy = b1.y() - b0.y();
+    let denom = rx * sy - ry * sx;
+    let u = ((b0.x() - a0.x()) * sy - (b0.y() - a0.y()) * sx) / denom;
+    let u = u.clamp(0.0, 1.0);
+    let clamp = |v: f64, p: f64, q: f64, r: f64, s: f64| v.max(p.min(q).max(r.min(s))).min(p.max(q).min(r.max(s)));
+    Point::new(
+        clamp(a0.x() + rx * u, s.a.x(), s.b.x(), t.a.x(), t.b.x()),
+        clamp(a0.y() + ry * u, s.a.y(), s.b.y(), t.a.y(), t.b.y()),
+    )
+}
+
+/// A piece of one or more input edges, from its first end `a` in sweep order to `b`.
+struct Piece {
+    a: Point,
+    b: Point,
+    /// Per source, the number of input edges running `a -> b` minus those running `b -> a`.
+    count: Vec<i32>,
+    /// The input edge the piece was cut from. Its ends are exact, unlike those of pieces that
+    /// end at crossings, so crossings computed from it do not drift with every cut.
+    edge: [Point; 2],
+    /// False once merged into a coincident piece.
+    alive: bool,
+}
+
+/// The first sweep: a Bentley-Ottmann sweep that splits pieces where they meet their
+/// neighbours in the status, until no two pieces cross.
+struct Subdivision {
+    pieces: Vec<Piece>,
+    events: BinaryHeap<Reverse<Event>>,
+    status: BTreeSet<Segment>,
+    snapper: Snapper,
+    /// Pieces that became neighbours when a cut piece moved in the status, to check once the
+    /// current event is done.
+    pending: Vec<(usize, usize)>,
+}
+
+impl Subdivision {
+    fn segment(&self, id: usize) -> Segment {
+        Segment { a: self.pieces[id].a, b: self.pieces[id].b, id }
+    }
+    fn below(&self, segment: &Segment) -> Option<usize> {
+        self.status.range(..segment).next_back().map(|s| s.id)
+    }
+    fn above(&self, segment: &Segment) -> Option<usize> {
+        self.status.range((Excluded(segment), Unbounded)).next().map(|s| s.id)
+    }
+    fn add(&mut self, a: Point, b: Point, mut count: Vec<i32>, edge: [Point; 2]) {
+        let (a, b) = match compare_points(&a, &b) {
+            Ordering::Less => (a, b),
+            _ => {
+                count.iter_mut().for_each(|c| *c = -*c);
+                (b, a)
+            }
+        };
+        self.pieces.push(Piece { a, b, count, edge, alive: true });
+        let segment = self.segment(self.pieces.len() - 1);
+        self.events.push(Reverse(Event { point: a, enters: true, segment }));
+        self.events.push(Reverse(Event { point: b, enters: false, segment }));
+    }
+    /// Cuts the piece in the status at `p`, keeping the part before it in place and queueing
+    /// the rest as a new piece.
+    fn split(&mut self, id: usize, p: Point) {
+        let (a, b) = (self.pieces[id].a, self.pieces[id].b);
+        if compare_points(&p, &a) != Ordering::Greater || compare_points(&p, &b) != Ordering::Less {
+            return;
+        }
+        let old = self.segment(id);
+        let before = (self.below(&old), self.above(&old));
+        remove(&mut self.status, &old);
+        self.pieces[id].b = p;
+        let segment = self.segment(id);
+        self.status.insert(segment);
+        // A piece whose ends were rounded can pass on the wrong side of a point on its line,
+        // and cutting it there moves it back among its neighbours
+        let after = (self.below(&segment), self.above(&segment));
+        if after != before {
+            if let (Some(below), Some(above)) = before {
+                self.pending.push((below, above));
+            }
+            self.pending.extend(after.0.map(|below| (below, id)));
+            self.pending.extend(after.1.map(|above| (id, above)));
+        }
+        self.events.push(Reverse(Event { point: p, enters: false, segment }));
+        self.add(p, b, self.pieces[id].count.clone(), self.pieces[id].edge);
+    }
+    /// Merges a piece into a coincident one, so that later cuts apply to both, and checks the
+    /// survivor against the neighbour it gains.
+    fn merge(&mut self, id: usize, into: usize) {
+        let segment = self.segment(id);
+        let neighbours = (self.below(&segment), self.above(&segment));
+        remove(&mut self.status, &segment);
+        let count = std::mem::take(&mut self.pieces[id].count);
+        self.pieces[id].alive = false;
+        for (total, c) in self.pieces[into].count.iter_mut().zip(count) {
+            *total += c;
+        }
+        match neighbours {
+            (Some(below), _) if below != into => self.intersect(below, into),
+            (_, Some(above)) if above != into => self.intersect(into, above),
+            _ => {}
+        }
+    }
+    /// Splits two pieces neighbouring in the status wherever they cross or touch.
+    fn intersect(&mut self, s: usize, t: usize) {
+        if !self.pieces[s].alive || !self.pieces[t].alive {
+            return;
+        }
+        let (a0, a1, b0, b1) = (self.pieces[s].a, self.pieces[s].b, self.pieces[t].a, self.pieces[t].b);
+        if a0.y().max(a1.y()) < b0.y().min(b1.y()) || b0.y().max(b1.y()) < a0.y().min(a1.y()) {
+            return;
+        }
+        let o1 = orient2d(&a0, &a1, &b0);
+        let o2 = orient2d(&a0, &a1, &b1);
+        let o3 = orient2d(&b0, &b1, &a0);
+        let o4 = orient2d(&b0, &b1, &a1);
+        // End points closer to the other piece than the snapping tolerance count as on it, since
+        // cutting a piece at a rounded point tilts it by about that much
+        let (near_s, near_t) = (self.snapper.cell * a0.distance_to(&a1), self.snapper.cell * b0.distance_to(&b1));
+        let [o1, o2] = [o1, o2].map(|o| if o.abs() <= near_s { 0.0 } else { o });
+        let [o3, o4] = [o3, o4].map(|o| if o.abs() <= near_t { 0.0 } else { o });
+        let opposite = |p: f64, q: f64| (p > 0.0 && q < 0.0) || (p < 0.0 && q > 0.0);
+        if opposite(o1, o2) && opposite(o3, o4) {
+            let point = self.snapper.snap(crossing_point(&self.pieces[s], &self.pieces[t]));
+            self.split(s, point);
+            self.split(t, point);
+            return;
+        }
+        // End points lying on the other piece, which covers touching and overlapping edges.
+        // The later point goes first, so that the earlier one still lies on the kept part.
+        for (id, on_line, ends) in [(s, [o1 == 0.0, o2 == 0.0], [b0, b1]), (t, [o3 == 0.0, o4 == 0.0], [a0, a1])] {
+            for k in [1, 0] {
+                if on_line[k] && in_box(&self.pieces[id].a, &self.pieces[id].b, &ends[k]) {
+                    self.split(id, ends[k]);
+                }
+            }
+        }
+        let same = |p: &Point, q: &Point| compare_points(p, q) == Ordering::Equal;
+        if same(&self.pieces[s].a, &self.pieces[t].a) && same(&self.pieces[s].b, &self.pieces[t].b) {
+            let (older, newer) = (s.min(t), s.max(t));
+            self.merge(newer, older);
+        }
+    }
+    fn run(&mut self) {
+        while let Some(Reverse(event)) = self.events.pop() {
+            let id = event.segment.id;
+            if !self.pieces[id].alive {
+                continue;
+            }
+            let segment = self.segment(id);
+            if event.enters {
+                self.status.insert(segment);
+                let (below, above) = (self.below(&segment), self.above(&segment));
+                if let Some(above) = above {
+                    self.intersect(id, above);
+                }
+                if let Some(below) = below {
+                    self.intersect(below, id);
+                }
+            } else if compare_points(&segment.b, &event.point) == Ordering::Equal {
+                // Pieces cut short since their end was queued leave at their new end instead
+                let (below, above) = (self.below(&segment), self.above(&segment));
+                remove(&mut self.status, &segment);
+                if let (Some(below), Some(above)) = (below, above) {
+                    self.intersect(below, above);
+                }
+            }
+            while let Some((below, above)) = self.pending.pop() {
+                if self.pieces[below].alive && self.above(&self.segment(below)) == Some(above) {
+                    self.intersect(below, above);
+                }
+            }
+        }
+    }
+}
+
+impl Overlay {
+    /// Builds the overlay of several sources, each given as a list of closed rings.
+    pub(crate) fn new(sources: &[Vec<Vec<Point>>]) -> Self {
+        let mut input: Vec<(Point, Point, usize)> = Vec::new();
+        let mut extent: f64 = 1.0;
+        for (source, rings) in sources.iter().enumerate() {
+            for ring in rings {
+                for i in 0..ring.len() {
+                    let a = ring[i];
+                    let b = ring[(i + 1) % ring.len()];
+                    extent = extent.max(a.x().abs()).max(a.y().abs());
+                    if key(&a) != key(&b) {
+                        input.push((a, b, source));
+                    }
+                }
+            }
+        }
+        let mut snapper = Snapper { cell: extent * 1e-12, grid: HashMap::new() };
+        for (a, b, _) in &input {
+            snapper.insert(*a);
+            snapper.insert(*b);
+        }
+        let mut subdivision = Subdivision {
+            pieces: Vec::new(),
+            events: BinaryHeap::new(),
+            status: BTreeSet::new(),
+            snapper,
+            pending: Vec::new(),
+        };
+        for &(a, b, source) in &input {
+            let mut count = vec![0; sources.len()];
+            count[source] = 1;
+            subdivision.add(a, b, count, [a, b]);
+        }
+        subdivision.run();
+        // Pieces that coincide without having met in the status still share a sub-edge
+        let mut edges: Vec<SubEdge> = Vec::new();
+        let mut index: HashMap<(Key, Key), usize> = HashMap::new();
+        for piece in subdivision.pieces.iter().filter(|piece| piece.alive) {
+            let (ka, kb) = (key(&piece.a), key(&piece.b));
+            if ka == kb {
+                continue;
+            }
+            let slot = *index.entry((ka, kb)).or_insert_with(|| {
+                let winding_left = vec![0; sources.len()];
+                edges.push(SubEdge { a: piece.a, b: piece.b, count: vec![0; sources.len()], winding_left });
+                edges.len() - 1
+            });
+            for (total, c) in edges[slot].count.iter_mut().zip(&piece.count) {
+                *total += c;
+            }
+        }
+        let mut overlay = Overlay { edges };
+        overlay.compute_windings();
+        overlay
+    }
+
+    /// The second sweep: each sub-edge entering the status takes the winding numbers just
+    /// above the sub-edge below it, and adds its own count to get those above itself.
+    fn compute_windings(&mut self) {
+        let source_count = self.edges.first().map_or(0, |edge| edge.count.len());
+        let mut events = Vec::new();
+        for (id, edge) in self.edges.iter().enumerate() {
+            if edge.count.iter().all(|&c| c == 0) {
+                continue;
+            }
+            let segment = Segment { a: edge.a, b: edge.b, id };
+            events.push(Event { point: edge.a, enters: true, segment });
+            events.push(Event { point: edge.b, enters: false, segment });
+        }
+        events.sort_unstable();
+        let mut status = BTreeSet::new();
+        for Event { enters, segment, .. } in events {
+            if !enters {
+                remove(&mut status, &segment);
+                continue;
+            }
+            let below = match status.range(..segment).next_back() {
+                Some(below) => self.edges[below.id].winding_left.clone(),
+                None => vec![0; source_count],
+            };
+            status.insert(segment);
+            // Sub-edges run forward in sweep order, so their left side is above them
+            let edge = &mut self.edges[segment.id];
+            edge.winding_left = below.iter().zip(&edge.count).map(|(w, c)| w + c).collect();
+        }
+    }
+
+    /// Returns the polygons covering the area where `fill` holds for the per-source winding numbers.
+    pub(crate) fn extract(&self, fill: impl Fn(&[i32]) -> bool) -> MultiPolygon2d<f64> {
+        let mut directed: Vec<(Point, Point)> = Vec::new();
+        for edge in &self.edges {
+            let winding_right: Vec<i32> = edge.winding_left.iter().zip(&edge.count).map(|(w, c)| w - c).collect();
+            match (fill(&edge.winding_left), fill(&winding_right)) {
+                (true, false) => directed.push((edge.a, edge.b)),
+                (false, true) => directed.push((edge.b, edge.a)),
+                _ => {}
+            }
+        }
+        let rings = trace_rings(&directed);
+        assemble(rings)
+    }
+}
+
+/// Chains directed edges into closed rings, taking the sharpest left turn at every vertex
+/// and splitting the chains where they pass a vertex twice, so that rings touching at a
+/// vertex are kept apart.
+fn trace_rings(directed: &[(Point, Point)]) -> Vec<Vec<Point>> {
+    let mut outgoing: HashMap<Key, Vec<usize>> = HashMap::new();
+    for (i, (a, _)) in directed.iter().enumerate() {
+        outgoing.entry(key(a)).or_default().push(i);
+    }
+    let mut used = vec![false; directed.len()];
+    let mut rings = Vec::new();
+    for first in 0..directed.len() {
+        if used[first] {
+            continue;
+        }
+        used[first] = true;
+        let start = key(&directed[first].0);
+        let mut ring = vec![directed[first].0];
+        let mut current = first;
+        loop {
+            let (from, to) = directed[current];
+            if key(&to) == start {
+                break;
+            }
+            ring.push(to);
+            let back = (from.x() - to.x(), from.y() - to.y());
+            let next = outgoing.get(&key(&to)).and_then(|candidates| {
+                candidates
+                    .iter()
+                    .filter(|&&k| !used[k])
+                    .map(|&k| {
+                        let d = (directed[k].1.x() - to.x(), directed[k].1.y() - to.y());
+                        // Clockwise angle from the way back to the candidate, in (0, 2π]
+                        let mut angle = -(back.0 * d.1 - back.1 * d.0).atan2(back.0 * d.0 + back.1 * d.1);
+                        if angle <= 0.0 {
+                            angle += 2.0 * std::f64::consts::PI;
+                        }
+                        (angle, k)
+                    })
+                    .min_by(|x, y| x.0.total_cmp(&y.0))
+                    .map(|(_, k)| k)
+            });
+            match next {
+                Some(k) => {
+                    used[k] = true;
+                    current = k;
+                }
+                None => break, // Open chain; only possible with inconsistent input
+            }
+        }
+        rings.extend(split_at_repeats(ring));
+    }
+    rings
+}
+
+/// Splits a closed chain into loops that each visit a vertex once.
+fn split_at_repeats(chain: Vec<Point>) -> Vec<Vec<Point>> {
+    let mut loops = Vec::new();
+    let mut stack: Vec<Point> = Vec::with_capacity(chain.len());
+    let mut position: HashMap<Key, usize> = HashMap::new();
+    for p in chain {
+        if let Some(&start) = position.get(&key(&p)) {
+            let closed: Vec<Point> = stack.drain(start..).collect();
+            for q in &closed {
+                position.remove(&key(q));
+            }
+            loops.push(closed);
+        }
+        position.insert(key(&p), stack.len());
+        stack.push(p);
+    }
+    loops.push(stack);
+    loops
+}
+
+/// Removes vertices lying exactly on the line between their neighbours.
+fn drop_collinear(mut ring: Vec<Point>) -> Vec<Point> {
+    let mut changed = true;
+    while changed && ring.len() >= 3 {
+        changed = false;
+        let n = ring.len();
+        for i in 0..n {
+            let prev = ring[(i + n - 1) % n];
+            let next = ring[(i + 1) % n];
+            if orient2d(&prev, &ring[i], &next) == 0.0 {
+                ring.remove(i);
+                changed = true;
+                break;
+            }
+        }
+    }
+    ring
+}
+
+/// Groups rings into polygons: counter-clockwise rings become exteriors and
+/// each clockwise ring becomes a hole of the smallest exterior around it.
+pub(crate) fn assemble(rings: Vec<Vec<Point>>) -> MultiPolygon2d<f64> {
+    let mut exteriors: Vec<(f64, Vec<Point>)> = Vec::new();
+    let mut holes: Vec<Vec<Point>> = Vec::new();
+    for ring in rings {
+        let ring = drop_collinear(ring);
+        if ring.len() < 3 {
+            continue;
+        }
+        let area = ring_signed_area_doubled(&ring);
+        if area > 0.0 {
+            exteriors.push((area, ring));
+        } else if area < 0.0 {
+            holes.push(ring);
+        }
+    }
+    let mut interiors: Vec<Vec<Vec<Point>>> = vec![Vec::new(); exteriors.len()];
+    for hole in holes {
+        let owner = exteriors
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, exterior))| {
+                // Use a hole vertex that is not on the exterior, since holes may touch their
+                // exterior, or else the middle of a hole edge
+                let n = hole.len();
+                let middles = (0..n).map(|i| hole[i].midpoint(&hole[(i + 1) % n]));
+                hole.iter()
+                    .copied()
+                    .chain(middles)
+                    .find_map(|p| point_in_ring(exterior, &p))
+                    .unwrap_or(false)
+            })
+            .min_by(|x, y| x.1.0.total_cmp(&y.1.0))
+            .map(|(i, _)| i);
+        if let Some(i) = owner {
+            interiors[i].push(hole);
+        }
+    }
+    MultiPolygon2d(
+        exteriors
+            .into_iter()
+            .zip(interiors)
+            .map(|((_, exterior), interiors)| Polygon2d { exterior, interiors })
+            .collect(),
+    )
+}
+
+/// Exact point in ring test: `Some(true)` inside, `Some(false)` outside and `None` on the boundary.
+pub(crate) fn point_in_ring(ring: &[Point], p: &Point) -> Option<bool> {
+    let n = ring.len();
+    let mut inside = false;
+    for i in 0..n {
+        let a = ring[i];
+        let b = ring[(i + 1) % n];
+        let turn = orient2d(&a, &b, p);
+        if turn == 0.0 && in_box(&a, &b, p) {
+            return None;
+        }
+        if (a.y() > p.y()) != (b.y() > p.y()) {
+            // The edge crosses the horizontal line through p; count it if it passes right of p
+            let upward = b.y() > a.y();
+            if (turn > 0.0) == upward {
+                inside = !inside;
+            }
+        }
+    }
+    Some(inside)
+}
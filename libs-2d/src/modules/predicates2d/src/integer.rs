@@ -0,0 +1,157 @@
+use point2d::Point2d;
+
+use crate::Orientation;
+
+type Point = Point2d<i64>;
+
+/// Largest coordinate magnitude for which `orient2d` and `doubled_area` are exact: differences
+/// of such coordinates fit in 63 bits, so a difference of two of their products fits in `i128`.
+pub const MAX_COORDINATE: i64 = (1 << 62) - 1;
+
+/// Error returned when an exact result does not fit in `i128`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Overflow;
+
+/// A point with rational coordinates `x / denominator` and `y / denominator`.
+///
+/// The fraction is kept in lowest terms with a positive denominator, so two points are equal
+/// exactly when their fields are.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RationalPoint2d {
+    pub x: i128,
+    pub y: i128,
+    pub denominator: i128,
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a as i128
+}
+
+impl RationalPoint2d {
+    /// Returns the reduced point, or None if `denominator` is zero.
+    pub fn new(x: i128, y: i128, denominator: i128) -> Option<Self> {
+        if denominator == 0 {
+            return None;
+        }
+        let divisor = gcd(gcd(x, y), denominator) * denominator.signum();
+        // Only a divisor of -1 applied to i128::MIN can overflow
+        Some(RationalPoint2d {
+            x: x.checked_div(divisor)?,
+            y: y.checked_div(divisor)?,
+            denominator: denominator.checked_div(divisor)?,
+        })
+    }
+    pub fn from_point(point: &Point) -> Self {
+        RationalPoint2d { x: point.x() as i128, y: point.y() as i128, denominator: 1 }
+    }
+    /// Returns the point if both coordinates are integers that fit in `i64`.
+    pub fn to_point(&self) -> Option<Point> {
+        if self.denominator != 1 {
+            return None;
+        }
+        Some(Point2d::new(i64::try_from(self.x).ok()?, i64::try_from(self.y).ok()?))
+    }
+    /// Returns the nearest floating-point approximation of the point.
+    pub fn to_f64(&self) -> Point2d<f64> {
+        let denominator = self.denominator as f64;
+        Point2d::new(self.x as f64 / denominator, self.y as f64 / denominator)
+    }
+}
+
+/// Returns twice the signed area of the triangle `a, b, c`: positive if the points turn
+/// counter-clockwise, negative if they turn clockwise and zero if they are collinear.
+///
+/// The result is exact for coordinates up to `MAX_COORDINATE` in magnitude.
+pub fn orient2d(a: &Point, b: &Point, c: &Point) -> i128 {
+    let (abx, aby) = (b.x() as i128 - a.x() as i128, b.y() as i128 - a.y() as i128);
+    let (acx, acy) = (c.x() as i128 - a.x() as i128, c.y() as i128 - a.y() as i128);
+    abx * acy - aby * acx
+}
+
+/// Returns the exact turn direction of `a, b, c`.
+pub fn orientation(a: &Point, b: &Point, c: &Point) -> Orientation {
+    match orient2d(a, b, c).signum() {
+        1 => Orientation::CounterClockwise,
+        -1 => Orientation::Clockwise,
+        _ => Orientation::Collinear,
+    }
+}
+
+pub fn is_collinear(a: &Point, b: &Point, c: &Point) -> bool {
+    orient2d(a, b, c) == 0
+}
+
+/// Returns twice the signed area of a ring, positive if it runs counter-clockwise.
+///
+/// Partial sums may wrap, but the result is exact whenever it fits in `i128`, which holds
+/// for any simple ring with coordinates up to `MAX_COORDINATE` in magnitude.
+pub fn doubled_area(ring: &[Point]) -> i128 {
+    let Some(first) = ring.first() else {
+        return 0;
+    };
+    ring.windows(2).skip(1).fold(0i128, |sum, w| sum.wrapping_add(orient2d(first, &w[0], &w[1])))
+}
+
+/// Returns true if `p` lies in the bounding box of `a` and `b`; for a point collinear
+/// with `a` and `b` this means it lies on the segment.
+pub fn in_box(a: &Point, b: &Point, p: &Point) -> bool {
+    p.x() >= a.x().min(b.x()) && p.x() <= a.x().max(b.x()) && p.y() >= a.y().min(b.y()) && p.y() <= a.y().max(b.y())
+}
+
+/// Returns true if `p` lies on the closed segment `a-b`.
+pub fn on_segment(p: &Point, a: &Point, b: &Point) -> bool {
+    is_collinear(a, b, p) && in_box(a, b, p)
+}
+
+/// Returns true if the closed segments `a0-a1` and `b0-b1` share at least one point.
+pub fn segments_intersect(a0: &Point, a1: &Point, b0: &Point, b1: &Point) -> bool {
+    let (o1, o2) = (orient2d(a0, a1, b0).signum(), orient2d(a0, a1, b1).signum());
+    let (o3, o4) = (orient2d(b0, b1, a0).signum(), orient2d(b0, b1, a1).signum());
+    if o1 * o2 < 0 && o3 * o4 < 0 {
+        return true;
+    }
+    (o1 == 0 && in_box(a0, a1, b0))
+        || (o2 == 0 && in_box(a0, a1, b1))
+        || (o3 == 0 && in_box(b0, b1, a0))
+        || (o4 == 0 && in_box(b0, b1, a1))
+}
+
+fn checked_cross(ax: i128, ay: i128, bx: i128, by: i128) -> Result<i128, Overflow> {
+    ax.checked_mul(by).zip(ay.checked_mul(bx)).and_then(|(p, q)| p.checked_sub(q)).ok_or(Overflow)
+}
+
+/// Returns the single point where the closed segments `a0-a1` and `b0-b1` meet, or None if
+/// they are disjoint or overlap along a common stretch.
+///
+/// The point is exact for coordinates up to `MAX_COORDINATE` in magnitude. Its numerators are
+/// formed with checked arithmetic and cannot overflow below 2^41; larger inputs may return
+/// `Overflow`.
+pub fn segment_intersection(a0: &Point, a1: &Point, b0: &Point, b1: &Point) -> Result<Option<RationalPoint2d>, Overflow> {
+    if !segments_intersect(a0, a1, b0, b1) {
+        return Ok(None);
+    }
+    let (rx, ry) = (a1.x() as i128 - a0.x() as i128, a1.y() as i128 - a0.y() as i128);
+    let (sx, sy) = (b1.x() as i128 - b0.x() as i128, b1.y() as i128 - b0.y() as i128);
+    let denominator = checked_cross(rx, ry, sx, sy)?;
+    if denominator == 0 {
+        // Parallel segments that meet are collinear; they share one point only if they
+        // touch end to end or one of them is a single point
+        let mut shared: Vec<&Point> = [a0, a1].into_iter().filter(|p| in_box(b0, b1, p)).collect();
+        shared.extend([b0, b1].into_iter().filter(|p| in_box(a0, a1, p)));
+        let first = shared[0];
+        return Ok(shared.iter().all(|p| *p == first).then(|| RationalPoint2d::from_point(first)));
+    }
+    // a0 + t (a1 - a0) with t = cross(b0 - a0, s) / cross(r, s)
+    let (qx, qy) = (b0.x() as i128 - a0.x() as i128, b0.y() as i128 - a0.y() as i128);
+    let t = checked_cross(qx, qy, sx, sy)?;
+    let coordinate = |origin: i64, direction: i128| {
+        (origin as i128).checked_mul(denominator)?.checked_add(t.checked_mul(direction)?)
+    };
+    let x = coordinate(a0.x(), rx).ok_or(Overflow)?;
+    let y = coordinate(a0.y(), ry).ok_or(Overflow)?;
+    RationalPoint2d::new(x, y, denominator).map(Some).ok_or(Overflow)
+}
@@ -0,0 +1,197 @@
+use point2d::Point2d;
+
+pub mod integer;
+
+/// Turn direction of three points.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    CounterClockwise,
+    Clockwise,
+    Collinear,
+}
+
+/// Machine epsilon as used by Shewchuk's error bounds (half an ulp of one).
+const EPSILON: f64 = f64::EPSILON * 0.5;
+const CCW_ERROR_BOUND: f64 = (3.0 + 16.0 * EPSILON) * EPSILON;
+const ICC_ERROR_BOUND: f64 = (10.0 + 96.0 * EPSILON) * EPSILON;
+
+/// Returns `a + b` as the rounded sum and its exact rounding error.
+#[inline]
+pub fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let b_virtual = x - a;
+    let a_virtual = x - b_virtual;
+    let b_round = b - b_virtual;
+    let a_round = a - a_virtual;
+    (x, a_round + b_round)
+}
+
+/// Returns `a * b` as the rounded product and its exact rounding error.
+#[inline]
+pub fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let x = a * b;
+    (x, a.mul_add(b, -x))
+}
+
+/// Adds the scalar `b` to the expansion `e`, dropping zero components.
+///
+/// Expansions are sums of non-overlapping `f64` components stored in increasing magnitude,
+/// so their sign is the sign of the last component.
+pub fn grow_expansion(e: &[f64], b: f64) -> Vec<f64> {
+    let mut h = Vec::with_capacity(e.len() + 1);
+    let mut q = b;
+    for &component in e {
+        let (sum, error) = two_sum(q, component);
+        q = sum;
+        if error != 0.0 {
+            h.push(error);
+        }
+    }
+    if q != 0.0 || h.is_empty() {
+        h.push(q);
+    }
+    h
+}
+
+/// Returns the exact sum of two expansions.
+pub fn expansion_sum(e: &[f64], f: &[f64]) -> Vec<f64> {
+    f.iter().fold(e.to_vec(), |sum, &component| grow_expansion(&sum, component))
+}
+
+/// Returns the exact product of an expansion and a scalar.
+pub fn scale_expansion(e: &[f64], b: f64) -> Vec<f64> {
+    e.iter().fold(vec![0.0], |sum, &component| {
+        let (product, error) = two_product(component, b);
+        grow_expansion(&grow_expansion(&sum, error), product)
+    })
+}
+
+/// Returns the exact product of two expansions.
+pub fn expansion_product(e: &[f64], f: &[f64]) -> Vec<f64> {
+    f.iter().fold(vec![0.0], |sum, &component| expansion_sum(&sum, &scale_expansion(e, component)))
+}
+
+/// Returns an approximation of the expansion with the exact sign.
+pub fn estimate(e: &[f64]) -> f64 {
+    e.iter().sum()
+}
+
+/// Returns twice the signed area of the triangle `a, b, c`: positive if the points turn
+/// counter-clockwise, negative if they turn clockwise and zero if they are collinear.
+///
+/// Unlike `Vector2d::cross` the sign is exact: a floating-point filter handles the usual
+/// case and near-degenerate inputs are re-evaluated with exact expansion arithmetic.
+pub fn orient2d(a: &Point2d<f64>, b: &Point2d<f64>, c: &Point2d<f64>) -> f64 {
+    let det_left = (a.x() - c.x()) * (b.y() - c.y());
+    let det_right = (a.y() - c.y()) * (b.x() - c.x());
+    let det = det_left - det_right;
+    // The sign is already exact if both products do not share a sign
+    let det_sum = if det_left > 0.0 && det_right > 0.0 {
+        det_left + det_right
+    } else if det_left < 0.0 && det_right < 0.0 {
+        -det_left - det_right
+    } else {
+        return det;
+    };
+    if det.abs() >= CCW_ERROR_BOUND * det_sum {
+        return det;
+    }
+    orient2d_exact(a, b, c)
+}
+
+fn orient2d_exact(a: &Point2d<f64>, b: &Point2d<f64>, c: &Point2d<f64>) -> f64 {
+    // (ax - cx)(by - cy) - (ay - cy)(bx - cx) expanded into six exact products
+    let terms = [
+        two_product(a.x(), b.y()),
+        two_product(-a.x(), c.y()),
+        two_product(-c.x(), b.y()),
+        two_product(-a.y(), b.x()),
+        two_product(a.y(), c.x()),
+        two_product(c.y(), b.x()),
+    ];
+    let mut sum = vec![0.0];
+    for (product, error) in terms {
+        sum = grow_expansion(&grow_expansion(&sum, error), product);
+    }
+    estimate(&sum)
+}
+
+/// Returns a value that is positive if `d` lies inside the circle through `a, b, c`,
+/// negative if it lies outside and zero if the four points are cocircular. The points
+/// `a, b, c` must turn counter-clockwise; for clockwise points the sign is reversed.
+///
+/// As with `orient2d` the sign is exact.
+pub fn incircle(a: &Point2d<f64>, b: &Point2d<f64>, c: &Point2d<f64>, d: &Point2d<f64>) -> f64 {
+    let (adx, ady) = (a.x() - d.x(), a.y() - d.y());
+    let (bdx, bdy) = (b.x() - d.x(), b.y() - d.y());
+    let (cdx, cdy) = (c.x() - d.x(), c.y() - d.y());
+    let (bdxcdy, cdxbdy) = (bdx * cdy, cdx * bdy);
+    let (cdxady, adxcdy) = (cdx * ady, adx * cdy);
+    let (adxbdy, bdxady) = (adx * bdy, bdx * ady);
+    let alift = adx * adx + ady * ady;
+    let blift = bdx * bdx + bdy * bdy;
+    let clift = cdx * cdx + cdy * cdy;
+    let det = alift * (bdxcdy - cdxbdy) + blift * (cdxady - adxcdy) + clift * (adxbdy - bdxady);
+    let permanent = (bdxcdy.abs() + cdxbdy.abs()) * alift
+        + (cdxady.abs() + adxcdy.abs()) * blift
+        + (adxbdy.abs() + bdxady.abs()) * clift;
+    if det.abs() > ICC_ERROR_BOUND * permanent {
+        return det;
+    }
+    incircle_exact(a, b, c, d)
+}
+
+fn incircle_exact(a: &Point2d<f64>, b: &Point2d<f64>, c: &Point2d<f64>, d: &Point2d<f64>) -> f64 {
+    // Same determinant with every difference, square and product kept as an exact expansion
+    let difference = |p: f64, q: f64| grow_expansion(&[p], -q);
+    let (adx, ady) = (difference(a.x(), d.x()), difference(a.y(), d.y()));
+    let (bdx, bdy) = (difference(b.x(), d.x()), difference(b.y(), d.y()));
+    let (cdx, cdy) = (difference(c.x(), d.x()), difference(c.y(), d.y()));
+    let lift = |dx: &[f64], dy: &[f64]| expansion_sum(&expansion_product(dx, dx), &expansion_product(dy, dy));
+    let cross = |px: &[f64], py: &[f64], qx: &[f64], qy: &[f64]| {
+        expansion_sum(&expansion_product(px, qy), &scale_expansion(&expansion_product(qx, py), -1.0))
+    };
+    let terms = [
+        expansion_product(&lift(&adx, &ady), &cross(&bdx, &bdy, &cdx, &cdy)),
+        expansion_product(&lift(&bdx, &bdy), &cross(&cdx, &cdy, &adx, &ady)),
+        expansion_product(&lift(&cdx, &cdy), &cross(&adx, &ady, &bdx, &bdy)),
+    ];
+    let det = terms.iter().fold(vec![0.0], |sum, term| expansion_sum(&sum, term));
+    estimate(&det)
+}
+
+/// Returns the exact turn direction of `a, b, c`.
+pub fn orientation(a: &Point2d<f64>, b: &Point2d<f64>, c: &Point2d<f64>) -> Orientation {
+    let det = orient2d(a, b, c);
+    if det > 0.0 {
+        Orientation::CounterClockwise
+    } else if det < 0.0 {
+        Orientation::Clockwise
+    } else {
+        Orientation::Collinear
+    }
+}
+
+/// Returns true if the closed segments `a0-a1` and `b0-b1` share at least one point.
+///
+/// The test only uses exact orientations, so touching and collinear segments are classified exactly.
+pub fn segments_intersect(a0: &Point2d<f64>, a1: &Point2d<f64>, b0: &Point2d<f64>, b1: &Point2d<f64>) -> bool {
+    let o1 = orient2d(a0, a1, b0);
+    let o2 = orient2d(a0, a1, b1);
+    let o3 = orient2d(b0, b1, a0);
+    let o4 = orient2d(b0, b1, a1);
+    let opposite = |p: f64, q: f64| (p > 0.0 && q < 0.0) || (p < 0.0 && q > 0.0);
+    if opposite(o1, o2) && opposite(o3, o4) {
+        return true;
+    }
+    (o1 == 0.0 && in_box(a0, a1, b0))
+        || (o2 == 0.0 && in_box(a0, a1, b1))
+        || (o3 == 0.0 && in_box(b0, b1, a0))
+        || (o4 == 0.0 && in_box(b0, b1, a1))
+}
+
+/// Returns true if `p` lies in the bounding box of `a` and `b`; for a point collinear
+/// with `a` and `b` this means it lies on the segment.
+pub fn in_box(a: &Point2d<f64>, b: &Point2d<f64>, p: &Point2d<f64>) -> bool {
+    p.x() >= a.x().min(b.x()) && p.x() <= a.x().max(b.x()) && p.y() >= a.y().min(b.y()) && p.y() <= a.y().max(b.y())
+}
@@ -0,0 +1,169 @@
+use num_traits::Float;
+use point2d::Point2d;
+use vector2d::{Size2d, Vector2d};
+
+mod rotated;
+pub use rotated::RotatedRect2d;
+
+/// A point of a rectangle by name, in screen coordinates: y grows downwards, so the top edge
+/// is the one with the smaller y.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    /// Returns the position of the anchor as fractions of the width and height from the top
+    /// left corner.
+    pub fn fractions<T: Float>(&self) -> (T, T) {
+        let (zero, half, one) = (T::zero(), T::from(0.5).unwrap(), T::one());
+        match self {
+            Anchor::TopLeft => (zero, zero),
+            Anchor::Top => (half, zero),
+            Anchor::TopRight => (one, zero),
+            Anchor::Left => (zero, half),
+            Anchor::Center => (half, half),
+            Anchor::Right => (one, half),
+            Anchor::BottomLeft => (zero, one),
+            Anchor::Bottom => (half, one),
+            Anchor::BottomRight => (one, one),
+        }
+    }
+}
+
+/// An axis-aligned rectangle given by its top left corner, the one with the smallest
+/// coordinates, and its size. A rectangle without a positive width and height is empty.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Rect2d<T> {
+    pub origin: Point2d<T>,
+    pub width: T,
+    pub height: T,
+}
+
+/// Splits `length` into `count` cells separated by `gap`, returning the offset and length of
+/// each.
+pub(crate) fn cells<T: Float>(length: T, count: usize, gap: T) -> impl Iterator<Item = (T, T)> {
+    let n = T::from(count).unwrap();
+    let cell = ((length - gap * (n - T::one())) / n).max(T::zero());
+    (0..count).map(move |i| (T::from(i).unwrap() * (cell + gap), cell))
+}
+
+impl<T: Float> Rect2d<T> {
+    pub fn new(origin: Point2d<T>, width: T, height: T) -> Self {
+        Rect2d { origin, width, height }
+    }
+    /// Returns the smallest rectangle with both points as corners.
+    pub fn from_points(p: &Point2d<T>, q: &Point2d<T>) -> Self {
+        let origin = Point2d::new(p.x().min(q.x()), p.y().min(q.y()));
+        Rect2d::new(origin, (p.x() - q.x()).abs(), (p.y() - q.y()).abs())
+    }
+    pub fn from_center(center: &Point2d<T>, width: T, height: T) -> Self {
+        let two = T::one() + T::one();
+        Rect2d::new(Point2d::new(center.x() - width / two, center.y() - height / two), width, height)
+    }
+    pub fn from_size(origin: Point2d<T>, size: Size2d<T>) -> Self {
+        Rect2d::new(origin, size.width, size.height)
+    }
+    pub fn size(&self) -> Size2d<T> {
+        Size2d::new(self.width, self.height)
+    }
+    /// Returns the rectangle with the given origin and size, as vectors.
+    pub fn from_vectors(origin: Vector2d<T>, size: Vector2d<T>) -> Self {
+        Rect2d::new(Point2d::from_vector(origin), size.x, size.y)
+    }
+    /// Returns the origin and size as vectors.
+    pub fn to_vectors(&self) -> (Vector2d<T>, Vector2d<T>) {
+        (self.origin.0, Vector2d::new(self.width, self.height))
+    }
+    pub fn min(&self) -> Point2d<T> {
+        self.origin
+    }
+    pub fn max(&self) -> Point2d<T> {
+        Point2d::new(self.origin.x() + self.width, self.origin.y() + self.height)
+    }
+    pub fn center(&self) -> Point2d<T> {
+        self.anchor(Anchor::Center)
+    }
+    pub fn area(&self) -> T {
+        self.size().area()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.size().is_empty()
+    }
+    pub fn anchor(&self, anchor: Anchor) -> Point2d<T> {
+        let (fx, fy): (T, T) = anchor.fractions();
+        Point2d::new(self.origin.x() + fx * self.width, self.origin.y() + fy * self.height)
+    }
+    /// Returns the corners clockwise on screen from the top left: top left, top right,
+    /// bottom right and bottom left.
+    pub fn corners(&self) -> [Point2d<T>; 4] {
+        [Anchor::TopLeft, Anchor::TopRight, Anchor::BottomRight, Anchor::BottomLeft].map(|a| self.anchor(a))
+    }
+    /// Returns true if the point is inside the rectangle or on its boundary.
+    pub fn contains(&self, point: &Point2d<T>) -> bool {
+        let max = self.max();
+        point.x() >= self.origin.x() && point.x() <= max.x() && point.y() >= self.origin.y() && point.y() <= max.y()
+    }
+    pub fn contains_rect(&self, other: &Rect2d<T>) -> bool {
+        self.contains(&other.min()) && self.contains(&other.max())
+    }
+    /// Returns the overlap of both rectangles, or None if they do not overlap with a positive
+    /// area; rectangles that only touch do not intersect.
+    pub fn intersection(&self, other: &Rect2d<T>) -> Option<Rect2d<T>> {
+        let (a, b) = (self.max(), other.max());
+        let low = Point2d::new(self.origin.x().max(other.origin.x()), self.origin.y().max(other.origin.y()));
+        let high = Point2d::new(a.x().min(b.x()), a.y().min(b.y()));
+        (high.x() > low.x() && high.y() > low.y()).then(|| Rect2d::from_points(&low, &high))
+    }
+    pub fn intersects(&self, other: &Rect2d<T>) -> bool {
+        self.intersection(other).is_some()
+    }
+    /// Returns the smallest rectangle containing both.
+    pub fn union(&self, other: &Rect2d<T>) -> Rect2d<T> {
+        let (a, b) = (self.max(), other.max());
+        Rect2d::from_points(
+            &Point2d::new(self.origin.x().min(other.origin.x()), self.origin.y().min(other.origin.y())),
+            &Point2d::new(a.x().max(b.x()), a.y().max(b.y())),
+        )
+    }
+    /// Returns the rectangle moved in by `dx` on the left and right and `dy` on the top and
+    /// bottom. Insetting past the center leaves a zero size at the center.
+    pub fn inset(&self, dx: T, dy: T) -> Rect2d<T> {
+        let two = T::one() + T::one();
+        let (width, height) = ((self.width - two * dx).max(T::zero()), (self.height - two * dy).max(T::zero()));
+        Rect2d::from_center(&self.center(), width, height)
+    }
+    /// Returns the rectangle grown by `dx` on the left and right and `dy` on the top and bottom.
+    pub fn outset(&self, dx: T, dy: T) -> Rect2d<T> {
+        self.inset(-dx, -dy)
+    }
+    pub fn translate(&self, offset: &Vector2d<T>) -> Rect2d<T> {
+        Rect2d::new(Point2d::new(self.origin.x() + offset.x, self.origin.y() + offset.y), self.width, self.height)
+    }
+    /// Returns this rectangle moved so that its `anchor` lies on the same anchor of
+    /// `container`; `Anchor::Center` centers it, `Anchor::BottomRight` aligns the bottom right
+    /// corners.
+    pub fn align_within(&self, container: &Rect2d<T>, anchor: Anchor) -> Rect2d<T> {
+        let (target, current) = (container.anchor(anchor), self.anchor(anchor));
+        self.translate(&Vector2d::new(target.x() - current.x(), target.y() - current.y()))
+    }
+    /// Splits the rectangle into `rows` by `columns` cells separated by `gap`, in rows from
+    /// the top, each from the left.
+    pub fn grid(&self, rows: usize, columns: usize, gap: T) -> Vec<Rect2d<T>> {
+        let mut grid = Vec::with_capacity(rows * columns);
+        for (y, height) in cells(self.height, rows, gap) {
+            for (x, width) in cells(self.width, columns, gap) {
+                grid.push(Rect2d::new(Point2d::new(self.origin.x() + x, self.origin.y() + y), width, height));
+            }
+        }
+        grid
+    }
+}
@@ -0,0 +1,118 @@
+use num_traits::Float;
+use point2d::Point2d;
+
+use crate::{cells, Anchor, Rect2d};
+
+/// A rectangle turned by `rotation` radians about its center. Anchors, corners and grid
+/// cells are named as for the unturned rectangle, so its top left corner stays the first
+/// corner as it turns.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RotatedRect2d<T> {
+    pub center: Point2d<T>,
+    pub width: T,
+    pub height: T,
+    pub rotation: T,
+}
+
+impl<T: Float> RotatedRect2d<T> {
+    pub fn new(center: Point2d<T>, width: T, height: T, rotation: T) -> Self {
+        RotatedRect2d { center, width, height, rotation }
+    }
+    /// Returns the rectangle turned by `rotation` about its center.
+    pub fn from_rect(rect: &Rect2d<T>, rotation: T) -> Self {
+        RotatedRect2d::new(rect.center(), rect.width, rect.height, rotation)
+    }
+    /// Returns the rectangle before rotation.
+    pub fn unrotated(&self) -> Rect2d<T> {
+        Rect2d::from_center(&self.center, self.width, self.height)
+    }
+    pub fn area(&self) -> T {
+        self.unrotated().area()
+    }
+    /// Maps a point of the unrotated rectangle to the plane.
+    fn rotate(&self, point: &Point2d<T>) -> Point2d<T> {
+        let (sin, cos) = self.rotation.sin_cos();
+        let (dx, dy) = (point.x() - self.center.x(), point.y() - self.center.y());
+        Point2d::new(self.center.x() + dx * cos - dy * sin, self.center.y() + dx * sin + dy * cos)
+    }
+    /// Maps a point of the plane to the unrotated rectangle.
+    fn unrotate(&self, point: &Point2d<T>) -> Point2d<T> {
+        let (sin, cos) = self.rotation.sin_cos();
+        let (dx, dy) = (point.x() - self.center.x(), point.y() - self.center.y());
+        Point2d::new(self.center.x() + dx * cos + dy * sin, self.center.y() - dx * sin + dy * cos)
+    }
+    pub fn anchor(&self, anchor: Anchor) -> Point2d<T> {
+        self.rotate(&self.unrotated().anchor(anchor))
+    }
+    /// Returns the corners in the order of `Rect2d::corners`.
+    pub fn corners(&self) -> [Point2d<T>; 4] {
+        self.unrotated().corners().map(|corner| self.rotate(&corner))
+    }
+    /// Returns true if the point is inside the rectangle or on its boundary.
+    pub fn contains(&self, point: &Point2d<T>) -> bool {
+        self.unrotated().contains(&self.unrotate(point))
+    }
+    /// Returns the smallest axis-aligned rectangle containing this one.
+    pub fn bounding_rect(&self) -> Rect2d<T> {
+        let [first, rest @ ..] = self.corners();
+        let (min, max) = rest.iter().fold((first, first), |(min, max), p| {
+            (Point2d::new(min.x().min(p.x()), min.y().min(p.y())), Point2d::new(max.x().max(p.x()), max.y().max(p.y())))
+        });
+        Rect2d::from_points(&min, &max)
+    }
+    /// Returns the overlap of both rectangles as a convex polygon, in the turning direction of
+    /// this rectangle's corners, empty if they do not overlap.
+    pub fn intersection(&self, other: &RotatedRect2d<T>) -> Vec<Point2d<T>> {
+        // Sutherland-Hodgman: clip this rectangle by each edge of the other in turn
+        let clip = other.corners();
+        // The corners turn counter-clockwise in y-up terms, so inside lies left of every edge
+        let side = |a: &Point2d<T>, b: &Point2d<T>, p: &Point2d<T>| {
+            (b.x() - a.x()) * (p.y() - a.y()) - (b.y() - a.y()) * (p.x() - a.x())
+        };
+        let mut polygon = self.corners().to_vec();
+        for i in 0..4 {
+            let (a, b) = (clip[i], clip[(i + 1) % 4]);
+            let input = std::mem::take(&mut polygon);
+            for (j, current) in input.iter().enumerate() {
+                let previous = input[(j + input.len() - 1) % input.len()];
+                let (current_side, previous_side) = (side(&a, &b, current), side(&a, &b, &previous));
+                let current_in = current_side >= T::zero();
+                if current_in != (previous_side >= T::zero()) {
+                    // Where the segment from previous to current crosses the clip line
+                    let t = previous_side / (previous_side - current_side);
+                    polygon.push(Point2d::new(
+                        previous.x() + (current.x() - previous.x()) * t,
+                        previous.y() + (current.y() - previous.y()) * t,
+                    ));
+                }
+                if current_in {
+                    polygon.push(*current);
+                }
+            }
+            if polygon.is_empty() {
+                break;
+            }
+        }
+        polygon
+    }
+    /// Returns the rectangle moved in by `dx` on the left and right and `dy` on the top and
+    /// bottom, as for `Rect2d::inset`.
+    pub fn inset(&self, dx: T, dy: T) -> RotatedRect2d<T> {
+        RotatedRect2d::from_rect(&self.unrotated().inset(dx, dy), self.rotation)
+    }
+    pub fn outset(&self, dx: T, dy: T) -> RotatedRect2d<T> {
+        self.inset(-dx, -dy)
+    }
+    /// Splits the rectangle into cells as `Rect2d::grid` does, each turned with it.
+    pub fn grid(&self, rows: usize, columns: usize, gap: T) -> Vec<RotatedRect2d<T>> {
+        let unrotated = self.unrotated();
+        let mut grid = Vec::with_capacity(rows * columns);
+        for (y, height) in cells(self.height, rows, gap) {
+            for (x, width) in cells(self.width, columns, gap) {
+                let cell = Rect2d::new(Point2d::new(unrotated.origin.x() + x, unrotated.origin.y() + y), width, height);
+                grid.push(RotatedRect2d::new(self.rotate(&cell.center()), width, height, self.rotation));
+            }
+        }
+        grid
+    }
+}
@@ -0,0 +1,141 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use num_traits::Float;
+use point2d::Point2d;
+
+mod topology;
+
+/// Returns the distance from `p` to the segment `a - b`.
+fn segment_distance<T: Float>(p: &Point2d<T>, a: &Point2d<T>, b: &Point2d<T>) -> T {
+    let (dx, dy) = (b.x() - a.x(), b.y() - a.y());
+    let length_squared = dx * dx + dy * dy;
+    if length_squared == T::zero() {
+        return p.distance_to(a);
+    }
+    let t = (((p.x() - a.x()) * dx + (p.y() - a.y()) * dy) / length_squared).max(T::zero()).min(T::one());
+    p.distance_to(&Point2d::new(a.x() + dx * t, a.y() + dy * t))
+}
+
+/// Returns the index strictly between `start` and `end` farthest from the segment joining
+/// them, with its distance.
+fn farthest<T: Float>(points: &[Point2d<T>], start: usize, end: usize) -> Option<(usize, T)> {
+    (start + 1..end)
+        .map(|i| (i, segment_distance(&points[i], &points[start], &points[end])))
+        .fold(None, |best, (i, d)| if best.is_none_or(|(_, max)| d > max) { Some((i, d)) } else { best })
+}
+
+/// Twice the area of the triangle `a, b, c`.
+fn triangle_area<T: Float>(a: &Point2d<T>, b: &Point2d<T>, c: &Point2d<T>) -> T {
+    ((b.x() - a.x()) * (c.y() - a.y()) - (b.y() - a.y()) * (c.x() - a.x())).abs()
+}
+
+/// Simplifies a polyline with the Douglas-Peucker algorithm and returns the indices of the
+/// kept points, in order.
+///
+/// Every dropped point lies within `tolerance` of the simplified line. The end points are
+/// always kept; a closed ring, whose last point repeats the first, keeps its farthest point too.
+pub fn douglas_peucker<T: Float>(points: &[Point2d<T>], tolerance: T) -> Vec<usize> {
+    if points.len() <= 2 {
+        return (0..points.len()).collect();
+    }
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+    // Spans still to split, handled without recursion for long inputs
+    let mut spans = vec![(0, points.len() - 1)];
+    while let Some((start, end)) = spans.pop() {
+        if let Some((i, distance)) = farthest(points, start, end)
+            && distance > tolerance
+        {
+            keep[i] = true;
+            spans.push((start, i));
+            spans.push((i, end));
+        }
+    }
+    (0..points.len()).filter(|&i| keep[i]).collect()
+}
+
+/// Like `douglas_peucker`, but restores dropped points wherever the simplified line would
+/// cross itself at a place the input does not.
+pub fn douglas_peucker_preserve_topology<T: Float>(points: &[Point2d<T>], tolerance: T) -> Vec<usize> {
+    topology::repair(points, douglas_peucker(points, tolerance))
+}
+
+/// Heap entry ordered so that the smallest area is popped first.
+struct Candidate<T> {
+    area: T,
+    index: usize,
+    version: usize,
+}
+
+impl<T: Float> PartialEq for Candidate<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: Float> Eq for Candidate<T> {}
+
+impl<T: Float> PartialOrd for Candidate<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Float> Ord for Candidate<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.area.partial_cmp(&self.area).unwrap_or(Ordering::Equal).then(other.index.cmp(&self.index))
+    }
+}
+
+/// Simplifies a polyline with the Visvalingam-Whyatt algorithm and returns the indices of
+/// the kept points, in order.
+///
+/// Points are removed smallest effective area first, where a point's effective area is the
+/// area of the triangle it forms with its current neighbors, until every remaining point
+/// has an effective area of at least `min_area`. The end points are always kept.
+pub fn visvalingam_whyatt<T: Float>(points: &[Point2d<T>], min_area: T) -> Vec<usize> {
+    let n = points.len();
+    if n <= 2 {
+        return (0..n).collect();
+    }
+    let two = T::one() + T::one();
+    let mut prev: Vec<usize> = (0..n).map(|i| i.wrapping_sub(1)).collect();
+    let mut next: Vec<usize> = (1..=n).collect();
+    let mut version = vec![0; n];
+    let mut removed = vec![false; n];
+    let area = |prev: usize, i: usize, next: usize| triangle_area(&points[prev], &points[i], &points[next]) / two;
+    let mut heap: BinaryHeap<Candidate<T>> =
+        (1..n - 1).map(|i| Candidate { area: area(i - 1, i, i + 1), index: i, version: 0 }).collect();
+    // Areas never drop below the last removed one, so a point is not removed before a
+    // neighbor it outlived
+    let mut floor = T::neg_infinity();
+    while let Some(Candidate { area: effective, index, version: seen }) = heap.pop() {
+        if removed[index] || seen != version[index] {
+            continue;
+        }
+        if effective >= min_area {
+            break;
+        }
+        floor = floor.max(effective);
+        removed[index] = true;
+        let (before, after) = (prev[index], next[index]);
+        next[before] = after;
+        prev[after] = before;
+        for neighbor in [before, after] {
+            if neighbor != 0 && neighbor != n - 1 {
+                version[neighbor] += 1;
+                let updated = area(prev[neighbor], neighbor, next[neighbor]).max(floor);
+                heap.push(Candidate { area: updated, index: neighbor, version: version[neighbor] });
+            }
+        }
+    }
+    (0..n).filter(|&i| !removed[i]).collect()
+}
+
+/// Like `visvalingam_whyatt`, but restores removed points wherever the simplified line
+/// would cross itself at a place the input does not.
+pub fn visvalingam_whyatt_preserve_topology<T: Float>(points: &[Point2d<T>], min_area: T) -> Vec<usize> {
+    topology::repair(points, visvalingam_whyatt(points, min_area))
+}
@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+
+use num_traits::Float;
+use point2d::Point2d;
+
+use crate::farthest;
+
+/// Restores dropped points until the simplified line crosses itself only where the input
+/// already did.
+///
+/// Each simplified segment stands for the run of input segments it replaces. Two simplified
+/// segments may meet if their runs meet; any other meeting splits both at the dropped point
+/// farthest from them. The input's own crossings are found once, so a self-crossing input
+/// keeps its crossings without restoring the points around them, and the loop ends at worst
+/// with the input itself. After the first round only the segments cut from split runs are
+/// checked again, since nothing else has changed.
+pub(crate) fn repair<T: Float>(points: &[Point2d<T>], kept: Vec<usize>) -> Vec<usize> {
+    if kept.len() < 3 {
+        return kept;
+    }
+    let (first, last) = (kept[0], kept[kept.len() - 1]);
+    // A closed ring joins its last segment to its first
+    let closed = (points[first] == points[last]).then_some((first, last));
+    let input: Vec<(usize, usize)> = (first..last).map(|i| (i, i + 1)).collect();
+    let existing = Grid::new(points, &input).meeting_pairs(points, closed);
+    // The input's own crossings, by the input segment on either side of them
+    let mut crossings_at: Vec<Vec<usize>> = vec![Vec::new(); points.len()];
+    for (id, &(i, j)) in existing.iter().enumerate() {
+        crossings_at[i].push(id);
+        crossings_at[j].push(id);
+    }
+    // For each input segment the first point of the run holding it, and for each kept point
+    // the next one
+    let mut run = vec![first; points.len()];
+    let mut next = vec![usize::MAX; points.len()];
+    for w in kept.windows(2) {
+        run[w[0]..w[1]].fill(w[0]);
+        next[w[0]] = w[1];
+    }
+    // The simplified segments whose runs hold the two sides of an input crossing
+    let runs = |run: &[usize], (i, j): (usize, usize)| {
+        let (k, l) = (run[i], run[j]);
+        (k != l).then_some((k.min(l), k.max(l)))
+    };
+    let mut allowed: HashMap<(usize, usize), usize> = HashMap::new();
+    for &crossing in &existing {
+        if let Some(pair) = runs(&run, crossing) {
+            *allowed.entry(pair).or_default() += 1;
+        }
+    }
+    // The simplified segments in order
+    let segments = |next: &[usize]| {
+        let mut segments = Vec::new();
+        let mut s = first;
+        while s != last {
+            segments.push((s, next[s]));
+            s = next[s];
+        }
+        segments
+    };
+    let length = |(a, b): (usize, usize)| coordinate(points[a].distance_to(&points[b]));
+    let mut total: f64 = segments(&next).into_iter().map(length).sum();
+    let mut count = kept.len() - 1;
+    let mut grid = Grid::new(points, &segments(&next));
+    let mut meeting = grid.meeting_pairs(points, closed);
+    loop {
+        let mut split: Vec<usize> = meeting
+            .iter()
+            .filter(|pair| !allowed.contains_key(pair))
+            .flat_map(|&(s, t)| [s, t])
+            .collect();
+        split.sort_unstable();
+        split.dedup();
+        let cuts: Vec<(usize, usize, usize)> = split
+            .into_iter()
+            .filter_map(|s| farthest(points, s, next[s]).map(|(i, _)| (s, i, next[s])))
+            .collect();
+        if cuts.is_empty() {
+            return segments(&next).into_iter().map(|(s, _)| s).chain([last]).collect();
+        }
+        // Only input crossings past a cut change the run they fall in
+        let mut touched: Vec<usize> = cuts
+            .iter()
+            .flat_map(|&(_, i, e)| crossings_at[i..e].iter().flatten().copied())
+            .collect();
+        touched.sort_unstable();
+        touched.dedup();
+        for &id in &touched {
+            if let Some(pair) = runs(&run, existing[id])
+                && let Some(count) = allowed.get_mut(&pair)
+            {
+                *count -= 1;
+                if *count == 0 {
+                    allowed.remove(&pair);
+                }
+            }
+        }
+        for &(s, i, e) in &cuts {
+            next[s] = i;
+            next[i] = e;
+            run[i..e].fill(i);
+        }
+        for &id in &touched {
+            if let Some(pair) = runs(&run, existing[id]) {
+                *allowed.entry(pair).or_default() += 1;
+            }
+        }
+        let fresh: Vec<(usize, usize)> = cuts.iter().flat_map(|&(s, i, e)| [(s, i), (i, e)]).collect();
+        total += fresh.iter().map(|&segment| length(segment)).sum::<f64>();
+        total -= cuts.iter().map(|&(s, _, e)| length((s, e))).sum::<f64>();
+        count += cuts.len();
+        // Cells stay about as large as the segments, which shrink as points come back
+        if total / (count as f64) < grid.size / 2.0 {
+            grid = Grid::new(points, &segments(&next));
+        } else {
+            for &segment in &fresh {
+                grid.insert(points, segment);
+            }
+        }
+        meeting = fresh
+            .iter()
+            .flat_map(|&segment| {
+                grid.candidates(points, segment)
+                    .filter(|&(c, d)| (c, d) != segment && next[c] == d)
+                    .filter(|&other| segments_meet(points, segment, other, closed))
+                    .map(|(c, _)| (segment.0.min(c), segment.0.max(c)))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        meeting.sort_unstable();
+        meeting.dedup();
+    }
+}
+
+fn orientation<T: Float>(a: &Point2d<T>, b: &Point2d<T>, c: &Point2d<T>) -> T {
+    (b.x() - a.x()) * (c.y() - a.y()) - (b.y() - a.y()) * (c.x() - a.x())
+}
+
+/// Returns true if `p`, known to be collinear with `a - b`, lies within its bounding box.
+fn within<T: Float>(p: &Point2d<T>, a: &Point2d<T>, b: &Point2d<T>) -> bool {
+    p.x() >= a.x().min(b.x()) && p.x() <= a.x().max(b.x()) && p.y() >= a.y().min(b.y()) && p.y() <= a.y().max(b.y())
+}
+
+/// Returns true if segments `a - b` and `c - d` share any point, touching included.
+fn segments_touch<T: Float>(a: &Point2d<T>, b: &Point2d<T>, c: &Point2d<T>, d: &Point2d<T>) -> bool {
+    let (d1, d2) = (orientation(a, b, c), orientation(a, b, d));
+    let (d3, d4) = (orientation(c, d, a), orientation(c, d, b));
+    if d1 * d2 < T::zero() && d3 * d4 < T::zero() {
+        return true;
+    }
+    (d1 == T::zero() && within(c, a, b))
+        || (d2 == T::zero() && within(d, a, b))
+        || (d3 == T::zero() && within(a, c, d))
+        || (d4 == T::zero() && within(b, c, d))
+}
+
+/// Returns true if segments `a - b` and `b - c`, which share `b`, double back over each other.
+fn folds_back<T: Float>(a: &Point2d<T>, b: &Point2d<T>, c: &Point2d<T>) -> bool {
+    orientation(a, b, c) == T::zero() && (b.x() - a.x()) * (c.x() - b.x()) + (b.y() - a.y()) * (c.y() - b.y()) < T::zero()
+}
+
+/// Returns true if two segments of the line, given by the indices of their ends, meet anywhere
+/// but at the end they share. `closed` holds the ends of a ring, whose last segment joins its first.
+fn segments_meet<T: Float>(
+    points: &[Point2d<T>],
+    (a, b): (usize, usize),
+    (c, d): (usize, usize),
+    closed: Option<(usize, usize)>,
+) -> bool {
+    let p = |i: usize| &points[i];
+    if p(a) == p(b) || p(c) == p(d) {
+        false
+    } else if b == c || closed == Some((c, b)) {
+        folds_back(p(a), p(b), p(d))
+    } else if d == a || closed == Some((a, d)) {
+        folds_back(p(c), p(d), p(b))
+    } else {
+        segments_touch(p(a), p(b), p(c), p(d))
+    }
+}
+
+/// Segments bucketed by the grid cells they pass through. Cells are about as large as the
+/// segments, so that each segment lands in a few.
+struct Grid {
+    size: f64,
+    cells: HashMap<(i64, i64), Vec<(usize, usize)>>,
+}
+
+impl Grid {
+    fn new<T: Float>(points: &[Point2d<T>], segments: &[(usize, usize)]) -> Self {
+        let total: f64 = segments.iter().map(|&(a, b)| coordinate(points[a].distance_to(&points[b]))).sum();
+        let mean = total / segments.len().max(1) as f64;
+        let size = if mean > 0.0 && mean.is_finite() { mean } else { 1.0 };
+        let mut grid = Grid { size, cells: HashMap::new() };
+        for &segment in segments {
+            grid.insert(points, segment);
+        }
+        grid
+    }
+
+    /// Returns the cells a segment passes through, column by column.
+    fn cells_of<T: Float>(&self, points: &[Point2d<T>], (a, b): (usize, usize)) -> Vec<(i64, i64)> {
+        let (mut p, mut q) = (&points[a], &points[b]);
+        if p.x() > q.x() {
+            (p, q) = (q, p);
+        }
+        let (x0, y0, x1, y1) = (coordinate(p.x()), coordinate(p.y()), coordinate(q.x()), coordinate(q.y()));
+        let cell = |v: f64| (v / self.size).floor() as i64;
+        // Slack so that a point on a cell border lands on both sides for every segment through it
+        let slack = self.size * 1e-9;
+        let mut cells = Vec::new();
+        for x in cell(x0)..=cell(x1) {
+            let (left, right) = ((x as f64 * self.size).max(x0), ((x + 1) as f64 * self.size).min(x1));
+            let y_at = |v: f64| if x1 > x0 { y0 + (y1 - y0) * ((v - x0) / (x1 - x0)) } else { y0 };
+            let (ya, yb) = if x1 > x0 { (y_at(left), y_at(right)) } else { (y0, y1) };
+            for y in cell(ya.min(yb) - slack)..=cell(ya.max(yb) + slack) {
+                cells.push((x, y));
+            }
+        }
+        cells
+    }
+
+    fn insert<T: Float>(&mut self, points: &[Point2d<T>], segment: (usize, usize)) {
+        for cell in self.cells_of(points, segment) {
+            self.cells.entry(cell).or_default().push(segment);
+        }
+    }
+
+    /// Returns the segments sharing a cell with `segment`, including ones since cut, which
+    /// callers skip.
+    fn candidates<'a, T: Float>(
+        &'a self,
+        points: &[Point2d<T>],
+        segment: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        self.cells_of(points, segment)
+            .into_iter()
+            .flat_map(|cell| self.cells.get(&cell).into_iter().flatten().copied())
+    }
+
+    /// Returns the pairs `(s, t)`, `s < t`, of the first points of segments that meet anywhere
+    /// but at a shared end point.
+    fn meeting_pairs<T: Float>(&self, points: &[Point2d<T>], closed: Option<(usize, usize)>) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        for bucket in self.cells.values() {
+            for (k, &s) in bucket.iter().enumerate() {
+                for &t in &bucket[k + 1..] {
+                    if segments_meet(points, s, t, closed) {
+                        pairs.push((s.0.min(t.0), s.0.max(t.0)));
+                    }
+                }
+            }
+        }
+        // Segments sharing several cells are found once per cell
+        pairs.sort_unstable();
+        pairs.dedup();
+        pairs
+    }
+}
+
+fn coordinate<T: Float>(v: T) -> f64 {
+    v.to_f64().unwrap_or(0.0)
+}
@@ -0,0 +1,356 @@
+use num_traits::Float;
+use point2d::Point2d;
+use vector2d::Vector2d;
+
+/// Knot spacing of a Catmull-Rom spline.
+///
+/// Centripetal spacing avoids cusps and self-intersections on unevenly spaced
+/// points, chordal spacing follows the point distances most closely.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Parameterization {
+    Uniform,
+    Centripetal,
+    Chordal,
+}
+
+impl Parameterization {
+    fn alpha<T: Float>(self) -> T {
+        match self {
+            Parameterization::Uniform => T::zero(),
+            Parameterization::Centripetal => T::from(0.5).unwrap(),
+            Parameterization::Chordal => T::one(),
+        }
+    }
+}
+
+/// A Catmull-Rom spline passing through every point of a sequence.
+///
+/// The curve is parameterized by `t` in `[0, n - 1]` for `n` points, so that
+/// `point_at(i as T)` is the `i`-th point. The end segments use mirrored
+/// phantom points.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CatmullRom2d<T> {
+    points: Vec<Point2d<T>>,
+    parameterization: Parameterization,
+    /// Bézier control points of every segment, computed once on construction.
+    segments: Vec<[Point2d<T>; 4]>,
+}
+
+/// A cubic B-spline curve defined by control points and a knot vector.
+///
+/// The curve is defined for parameters between `knots[3]` and `knots[n]`
+/// for `n` control points.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BSpline2d<T> {
+    control_points: Vec<Point2d<T>>,
+    knots: Vec<T>,
+}
+
+const DEGREE: usize = 3;
+
+fn lerp<T: Float>(a: Point2d<T>, b: Point2d<T>, t: T) -> Point2d<T> {
+    Point2d::new(a.x() + (b.x() - a.x()) * t, a.y() + (b.y() - a.y()) * t)
+}
+
+/// Evaluates a cubic Bézier at `t`.
+fn bezier_point<T: Float>(points: &[Point2d<T>; 4], t: T) -> Point2d<T> {
+    let a = lerp(points[0], points[1], t);
+    let b = lerp(points[1], points[2], t);
+    let c = lerp(points[2], points[3], t);
+    lerp(lerp(a, b, t), lerp(b, c, t), t)
+}
+
+/// Returns the derivative of a cubic Bézier at `t`.
+fn bezier_tangent<T: Float>(points: &[Point2d<T>; 4], t: T) -> Vector2d<T> {
+    let three = T::from(3.0).unwrap();
+    let a = lerp(points[0], points[1], t);
+    let b = lerp(points[1], points[2], t);
+    let c = lerp(points[2], points[3], t);
+    let d = lerp(a, b, t);
+    let e = lerp(b, c, t);
+    Vector2d::new((e.x() - d.x()) * three, (e.y() - d.y()) * three)
+}
+
+impl<T: Float> CatmullRom2d<T> {
+    /// Creates a spline through `points`, or `None` if fewer than two points are given.
+    pub fn new(points: &[Point2d<T>], parameterization: Parameterization) -> Option<Self> {
+        if points.len() < 2 {
+            return None;
+        }
+        let n = points.len();
+        let two = T::from(2.0).unwrap();
+        let mirror = |a: Point2d<T>, b: Point2d<T>| Point2d::new(two * a.x() - b.x(), two * a.y() - b.y());
+        let first = mirror(points[0], points[1]);
+        let last = mirror(points[n - 1], points[n - 2]);
+        let at = |i: isize| -> Point2d<T> {
+            if i < 0 {
+                first
+            } else if i as usize >= n {
+                last
+            } else {
+                points[i as usize]
+            }
+        };
+        let alpha = parameterization.alpha::<T>();
+        // Knot interval between two points; coincident points fall back to a unit interval
+        let interval = |a: Point2d<T>, b: Point2d<T>| {
+            let d = a.distance_to(&b).powf(alpha);
+            if d > T::epsilon() { d } else { T::one() }
+        };
+        let three = T::from(3.0).unwrap();
+        let segments = (0..n - 1)
+            .map(|i| {
+                let i = i as isize;
+                let (p0, p1, p2, p3) = (at(i - 1), at(i), at(i + 1), at(i + 2));
+                let d0 = interval(p0, p1);
+                let d1 = interval(p1, p2);
+                let d2 = interval(p2, p3);
+                // Tangents of the Barry-Goldman formulation, scaled to the middle interval
+                let tangent = |a: Point2d<T>, b: Point2d<T>, c: Point2d<T>, da: T, db: T| {
+                    let x = ((b.x() - a.x()) / da - (c.x() - a.x()) / (da + db) + (c.x() - b.x()) / db) * d1;
+                    let y = ((b.y() - a.y()) / da - (c.y() - a.y()) / (da + db) + (c.y() - b.y()) / db) * d1;
+                    (x, y)
+                };
+                let (m1x, m1y) = tangent(p0, p1, p2, d0, d1);
+                let (m2x, m2y) = tangent(p1, p2, p3, d1, d2);
+                [
+                    p1,
+                    Point2d::new(p1.x() + m1x / three, p1.y() + m1y / three),
+                    Point2d::new(p2.x() - m2x / three, p2.y() - m2y / three),
+                    p2,
+                ]
+            })
+            .collect();
+        Some(CatmullRom2d { points: points.to_vec(), parameterization, segments })
+    }
+    pub fn points(&self) -> &[Point2d<T>] {
+        &self.points
+    }
+    pub fn parameterization(&self) -> Parameterization {
+        self.parameterization
+    }
+    /// Returns the parameter range `(0, n - 1)`.
+    pub fn parameter_range(&self) -> (T, T) {
+        (T::zero(), T::from(self.segments.len()).unwrap())
+    }
+    /// Splits `t` into a segment index and a local parameter in `[0, 1]`.
+    fn locate(&self, t: T) -> (usize, T) {
+        let last = self.segments.len() - 1;
+        let t = t.max(T::zero()).min(T::from(self.segments.len()).unwrap());
+        let index = t.floor().to_usize().unwrap_or(0).min(last);
+        (index, t - T::from(index).unwrap())
+    }
+    /// Returns the point at parameter `t`, clamped to the parameter range.
+    pub fn point_at(&self, t: T) -> Point2d<T> {
+        let (index, local) = self.locate(t);
+        bezier_point(&self.segments[index], local)
+    }
+    /// Returns the derivative of the curve with respect to `t`.
+    pub fn tangent_at(&self, t: T) -> Vector2d<T> {
+        let (index, local) = self.locate(t);
+        bezier_tangent(&self.segments[index], local)
+    }
+    /// Returns the spline as one cubic Bézier `[start, ctrl1, ctrl2, end]` per pair of points.
+    pub fn to_cubic_beziers(&self) -> Vec<[Point2d<T>; 4]> {
+        self.segments.clone()
+    }
+}
+
+impl<T: Float> BSpline2d<T> {
+    /// Creates a spline with the given knot vector, or `None` if there are fewer than four
+    /// control points, the knot vector does not hold `n + 4` values or decreases anywhere.
+    pub fn with_knots(control_points: &[Point2d<T>], knots: &[T]) -> Option<Self> {
+        if control_points.len() <= DEGREE || knots.len() != control_points.len() + DEGREE + 1 {
+            return None;
+        }
+        if knots.windows(2).any(|pair| pair[1] < pair[0]) || knots[DEGREE] >= knots[control_points.len()] {
+            return None;
+        }
+        Some(BSpline2d { control_points: control_points.to_vec(), knots: knots.to_vec() })
+    }
+    /// Creates a clamped uniform spline approximating `control_points`; it starts at the first
+    /// point and ends at the last one. Returns `None` for fewer than four points.
+    pub fn uniform(control_points: &[Point2d<T>]) -> Option<Self> {
+        let n = control_points.len();
+        if n <= DEGREE {
+            return None;
+        }
+        let spans = T::from(n - DEGREE).unwrap();
+        let knots: Vec<T> = (0..n + DEGREE + 1)
+            .map(|i| {
+                let i = i.clamp(DEGREE, n) - DEGREE;
+                T::from(i).unwrap() / spans
+            })
+            .collect();
+        Self::with_knots(control_points, &knots)
+    }
+    /// Creates a clamped spline passing through every point, using chord-length parameters
+    /// and averaged knots. Returns `None` for fewer than four points or if consecutive
+    /// points coincide.
+    pub fn interpolate(points: &[Point2d<T>]) -> Option<Self> {
+        let n = points.len();
+        if n <= DEGREE {
+            return None;
+        }
+        let mut params = Vec::with_capacity(n);
+        params.push(T::zero());
+        for pair in points.windows(2) {
+            let step = pair[0].distance_to(&pair[1]);
+            if step <= T::epsilon() {
+                return None;
+            }
+            params.push(*params.last().unwrap() + step);
+        }
+        let total = params[n - 1];
+        for param in params.iter_mut() {
+            *param = *param / total;
+        }
+        params[n - 1] = T::one();
+        let mut knots = vec![T::zero(); DEGREE + 1];
+        let three = T::from(DEGREE).unwrap();
+        for j in 1..n - DEGREE {
+            knots.push((params[j] + params[j + 1] + params[j + 2]) / three);
+        }
+        knots.extend(std::iter::repeat_n(T::one(), DEGREE + 1));
+        let mut spline = BSpline2d { control_points: points.to_vec(), knots };
+        // Each row of the collocation matrix has at most four non-zero entries around the diagonal
+        let band = DEGREE;
+        let width = 2 * band + 1;
+        let mut matrix = vec![vec![T::zero(); width]; n];
+        for (row, &param) in params.iter().enumerate() {
+            let span = spline.find_span(param);
+            let basis = spline.basis_functions(span, param);
+            for (k, value) in basis.iter().enumerate() {
+                let col = span - DEGREE + k;
+                matrix[row][col + band - row] = *value;
+            }
+        }
+        let mut rhs: Vec<(T, T)> = points.iter().map(|p| (p.x(), p.y())).collect();
+        // Banded Gaussian elimination; collocation matrices are totally positive, so no pivoting
+        for pivot in 0..n {
+            let diagonal = matrix[pivot][band];
+            if diagonal.abs() <= T::epsilon() {
+                return None;
+            }
+            for row in pivot + 1..(pivot + band + 1).min(n) {
+                let factor = matrix[row][pivot + band - row] / diagonal;
+                if factor.is_zero() {
+                    continue;
+                }
+                for col in pivot..(pivot + band + 1).min(n) {
+                    let value = matrix[pivot][col + band - pivot];
+                    matrix[row][col + band - row] = matrix[row][col + band - row] - factor * value;
+                }
+                rhs[row] = (rhs[row].0 - factor * rhs[pivot].0, rhs[row].1 - factor * rhs[pivot].1);
+            }
+        }
+        for pivot in (0..n).rev() {
+            let (mut x, mut y) = rhs[pivot];
+            for col in pivot + 1..(pivot + band + 1).min(n) {
+                let value = matrix[pivot][col + band - pivot];
+                x = x - value * rhs[col].0;
+                y = y - value * rhs[col].1;
+            }
+            let diagonal = matrix[pivot][band];
+            rhs[pivot] = (x / diagonal, y / diagonal);
+        }
+        spline.control_points = rhs.into_iter().map(|(x, y)| Point2d::new(x, y)).collect();
+        Some(spline)
+    }
+    pub fn control_points(&self) -> &[Point2d<T>] {
+        &self.control_points
+    }
+    pub fn knots(&self) -> &[T] {
+        &self.knots
+    }
+    /// Returns the range of parameters over which the curve is defined.
+    pub fn parameter_range(&self) -> (T, T) {
+        (self.knots[DEGREE], self.knots[self.control_points.len()])
+    }
+    /// Returns the index `i` of the knot span `[knots[i], knots[i + 1])` holding `t`.
+    fn find_span(&self, t: T) -> usize {
+        let n = self.control_points.len();
+        if t >= self.knots[n] {
+            // The end of the range belongs to the last non-empty span
+            let mut span = n - 1;
+            while span > DEGREE && self.knots[span] >= self.knots[n] {
+                span -= 1;
+            }
+            return span;
+        }
+        let mut span = DEGREE;
+        while span + 1 < n && t >= self.knots[span + 1] {
+            span += 1;
+        }
+        span
+    }
+    /// Returns the four basis functions that are non-zero on `span` at `t`.
+    fn basis_functions(&self, span: usize, t: T) -> [T; DEGREE + 1] {
+        let mut basis = [T::zero(); DEGREE + 1];
+        let mut left = [T::zero(); DEGREE + 1];
+        let mut right = [T::zero(); DEGREE + 1];
+        basis[0] = T::one();
+        for j in 1..=DEGREE {
+            left[j] = t - self.knots[span + 1 - j];
+            right[j] = self.knots[span + j] - t;
+            let mut saved = T::zero();
+            for r in 0..j {
+                let temp = basis[r] / (right[r + 1] + left[j - r]);
+                basis[r] = saved + right[r + 1] * temp;
+                saved = left[j - r] * temp;
+            }
+            basis[j] = saved;
+        }
+        basis
+    }
+    /// Evaluates the blossom of the polynomial piece on `span` at `args`.
+    ///
+    /// With all three arguments equal this is de Boor's algorithm; mixing the span's
+    /// end knots gives the Bézier control points of the piece.
+    fn blossom(&self, span: usize, args: [T; DEGREE]) -> Point2d<T> {
+        let mut points: Vec<Point2d<T>> = (0..=DEGREE).map(|j| self.control_points[span - DEGREE + j]).collect();
+        for (r, &arg) in args.iter().enumerate() {
+            let level = r + 1;
+            for j in (level..=DEGREE).rev() {
+                let i = span - DEGREE + j;
+                let denom = self.knots[i + DEGREE + 1 - level] - self.knots[i];
+                let alpha = if denom.is_zero() { T::zero() } else { (arg - self.knots[i]) / denom };
+                points[j] = lerp(points[j - 1], points[j], alpha);
+            }
+        }
+        points[DEGREE]
+    }
+    /// Returns the point at parameter `t`, clamped to the parameter range.
+    pub fn point_at(&self, t: T) -> Point2d<T> {
+        let (start, end) = self.parameter_range();
+        let t = t.max(start).min(end);
+        self.blossom(self.find_span(t), [t; DEGREE])
+    }
+    /// Returns the derivative of the curve with respect to `t`.
+    pub fn tangent_at(&self, t: T) -> Vector2d<T> {
+        let (start, end) = self.parameter_range();
+        let t = t.max(start).min(end);
+        let span = self.find_span(t);
+        let three = T::from(DEGREE).unwrap();
+        let a = self.blossom(span, [t, t, self.knots[span]]);
+        let b = self.blossom(span, [t, t, self.knots[span + 1]]);
+        let width = self.knots[span + 1] - self.knots[span];
+        Vector2d::new((b.x() - a.x()) * three / width, (b.y() - a.y()) * three / width)
+    }
+    /// Returns the curve as one cubic Bézier `[start, ctrl1, ctrl2, end]` per non-empty knot span.
+    pub fn to_cubic_beziers(&self) -> Vec<[Point2d<T>; 4]> {
+        (DEGREE..self.control_points.len())
+            .filter(|&span| self.knots[span + 1] > self.knots[span])
+            .map(|span| {
+                let a = self.knots[span];
+                let b = self.knots[span + 1];
+                [
+                    self.blossom(span, [a, a, a]),
+                    self.blossom(span, [a, a, b]),
+                    self.blossom(span, [a, b, b]),
+                    self.blossom(span, [b, b, b]),
+                ]
+            })
+            .collect()
+    }
+}
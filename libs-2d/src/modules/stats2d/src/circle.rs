@@ -0,0 +1,192 @@
+use matrix2d::linalg::solve3;
+use matrix2d::Matrix2;
+use point2d::Point2d;
+use vector2d::Vector2d;
+
+use crate::{residual_stats, ResidualStats};
+
+/// A circle fitted to points, with the signed distance of each point outside it and their
+/// statistics.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CircleFit {
+    pub center: Point2d<f64>,
+    pub radius: f64,
+    pub residuals: Vec<f64>,
+    pub stats: ResidualStats<f64>,
+}
+
+impl CircleFit {
+    fn new(points: &[Point2d<f64>], center: Point2d<f64>, radius: f64) -> Option<Self> {
+        if !(center.x().is_finite() && center.y().is_finite() && radius.is_finite() && radius > 0.0) {
+            return None;
+        }
+        let residuals: Vec<f64> = points.iter().map(|p| p.distance_to(&center) - radius).collect();
+        let stats = residual_stats(&residuals);
+        Some(CircleFit { center, radius, residuals, stats })
+    }
+}
+
+/// Central moments of the points, with `z = x^2 + y^2` of the centred coordinates.
+struct Moments {
+    mean: (f64, f64),
+    xx: f64,
+    yy: f64,
+    xy: f64,
+    xz: f64,
+    yz: f64,
+    zz: f64,
+}
+
+impl Moments {
+    fn new(points: &[Point2d<f64>]) -> Option<Self> {
+        if points.len() < 3 {
+            return None;
+        }
+        let count = points.len() as f64;
+        let mean = (
+            points.iter().map(|p| p.x()).sum::<f64>() / count,
+            points.iter().map(|p| p.y()).sum::<f64>() / count,
+        );
+        let mut moments = Moments { mean, xx: 0.0, yy: 0.0, xy: 0.0, xz: 0.0, yz: 0.0, zz: 0.0 };
+        for p in points {
+            let (x, y) = (p.x() - mean.0, p.y() - mean.1);
+            let z = x * x + y * y;
+            moments.xx += x * x / count;
+            moments.yy += y * y / count;
+            moments.xy += x * y / count;
+            moments.xz += x * z / count;
+            moments.yz += y * z / count;
+            moments.zz += z * z / count;
+        }
+        Some(moments)
+    }
+    /// Returns the center for the root `x` of the characteristic polynomial of the Pratt or
+    /// Taubin constraint, in the original coordinates, and its squared distance from the mean.
+    fn center(&self, x: f64) -> Option<(Point2d<f64>, f64)> {
+        let det = 2.0 * (x * x - x * (self.xx + self.yy) + self.xx * self.yy - self.xy * self.xy);
+        if det == 0.0 {
+            return None;
+        }
+        let cx = (self.xz * (self.yy - x) - self.yz * self.xy) / det;
+        let cy = (self.yz * (self.xx - x) - self.xz * self.xy) / det;
+        Some((Point2d::new(cx + self.mean.0, cy + self.mean.1), cx * cx + cy * cy))
+    }
+}
+
+/// Returns the root of the polynomial with the given coefficients, constant first, that
+/// Newton's method reaches from zero, which is the one the algebraic fits need.
+fn newton_root(coefficients: &[f64]) -> f64 {
+    let evaluate = |x: f64| coefficients.iter().rev().fold(0.0, |sum, c| sum * x + c);
+    let slope = |x: f64| coefficients.iter().enumerate().skip(1).rev().fold(0.0, |sum, (k, c)| sum * x + k as f64 * c);
+    let (mut x, mut y) = (0.0, coefficients[0]);
+    for _ in 0..100 {
+        let next = x - y / slope(x);
+        if next == x || !next.is_finite() {
+            break;
+        }
+        let value = evaluate(next);
+        if value.abs() >= y.abs() {
+            break;
+        }
+        (x, y) = (next, value);
+    }
+    x
+}
+
+/// Fits a circle by Kåsa's method, minimizing `sum (|p - c|^2 - r^2)^2`.
+///
+/// The fastest fit, and exact on exact data, but it shrinks the radius when the points cover
+/// only a short arc. Returns None if there are fewer than three points or they are collinear.
+pub fn fit_circle_kasa(points: &[Point2d<f64>]) -> Option<CircleFit> {
+    let m = Moments::new(points)?;
+    // With centred coordinates the linear system for the center decouples from r
+    let center = Matrix2::new(m.xx, m.xy, m.xy, m.yy).solve(&Vector2d::new(m.xz / 2.0, m.yz / 2.0))?;
+    let radius = (center.length_squared() + m.xx + m.yy).sqrt();
+    CircleFit::new(points, Point2d::new(center.x + m.mean.0, center.y + m.mean.1), radius)
+}
+
+/// Fits a circle by Pratt's method, the algebraic fit normalized by the gradient at the
+/// center, which removes most of Kåsa's bias on short arcs.
+///
+/// Returns None if there are fewer than three points or they are collinear.
+pub fn fit_circle_pratt(points: &[Point2d<f64>]) -> Option<CircleFit> {
+    let m = Moments::new(points)?;
+    let mz = m.xx + m.yy;
+    let cov = m.xx * m.yy - m.xy * m.xy;
+    let a2 = 4.0 * cov - 3.0 * mz * mz - m.zz;
+    let a1 = m.zz * mz + 4.0 * cov * mz - m.xz * m.xz - m.yz * m.yz - mz * mz * mz;
+    let a0 = m.xz * m.xz * m.yy + m.yz * m.yz * m.xx - m.zz * cov - 2.0 * m.xz * m.yz * m.xy + mz * mz * cov;
+    let x = newton_root(&[a0, a1, a2, 0.0, 4.0]);
+    let (center, distance) = m.center(x)?;
+    CircleFit::new(points, center, (distance + mz + 2.0 * x).sqrt())
+}
+
+/// Fits a circle by Taubin's method, the algebraic fit normalized by the mean squared
+/// gradient, which is nearly as accurate as the geometric fit and a good start for it.
+///
+/// Returns None if there are fewer than three points or they are collinear.
+pub fn fit_circle_taubin(points: &[Point2d<f64>]) -> Option<CircleFit> {
+    let m = Moments::new(points)?;
+    let mz = m.xx + m.yy;
+    let cov = m.xx * m.yy - m.xy * m.xy;
+    let var_z = m.zz - mz * mz;
+    let a2 = -3.0 * mz * mz - m.zz;
+    let a1 = var_z * mz + 4.0 * cov * mz - m.xz * m.xz - m.yz * m.yz;
+    let a0 = m.xz * (m.xz * m.yy - m.yz * m.xy) + m.yz * (m.yz * m.xx - m.xz * m.xy) - var_z * cov;
+    let x = newton_root(&[a0, a1, a2, 4.0 * mz]);
+    let (center, distance) = m.center(x)?;
+    CircleFit::new(points, center, (distance + mz).sqrt())
+}
+
+/// Fits a circle minimizing the sum of squared distances from the points to it, by
+/// Levenberg-Marquardt iterations from the Taubin fit.
+///
+/// Returns None if there are fewer than three points or they are collinear.
+pub fn fit_circle_geometric(points: &[Point2d<f64>], max_iterations: usize) -> Option<CircleFit> {
+    let start = fit_circle_taubin(points)?;
+    let (mut a, mut b, mut r) = (start.center.x(), start.center.y(), start.radius);
+    let cost = |a: f64, b: f64, r: f64| points.iter().map(|p| (p.distance_to(&Point2d::new(a, b)) - r).powi(2)).sum::<f64>();
+    let mut current = cost(a, b, r);
+    let mut lambda = 1e-3;
+    for _ in 0..max_iterations {
+        // The normal equations J^T J d = -J^T f for the residuals f = |p - c| - r
+        let (mut normal, mut gradient) = ([[0.0; 3]; 3], [0.0; 3]);
+        for p in points {
+            let (dx, dy) = (p.x() - a, p.y() - b);
+            let distance = dx.hypot(dy);
+            if distance == 0.0 {
+                continue;
+            }
+            let row = [-dx / distance, -dy / distance, -1.0];
+            let f = distance - r;
+            for i in 0..3 {
+                gradient[i] -= row[i] * f;
+                for j in 0..3 {
+                    normal[i][j] += row[i] * row[j];
+                }
+            }
+        }
+        let mut improved = false;
+        while lambda < 1e12 {
+            let mut damped = normal;
+            (0..3).for_each(|i| damped[i][i] *= 1.0 + lambda);
+            let Some([da, db, dr]) = solve3(damped, gradient) else {
+                lambda *= 10.0;
+                continue;
+            };
+            let trial = cost(a + da, b + db, r + dr);
+            if trial <= current {
+                (a, b, r) = (a + da, b + db, r + dr);
+                improved = current - trial > f64::EPSILON * current;
+                current = trial;
+                lambda /= 10.0;
+                break;
+            }
+            lambda *= 10.0;
+        }
+        if !improved {
+            break;
+        }
+    }
+    CircleFit::new(points, Point2d::new(a, b), r)
+}
@@ -0,0 +1,122 @@
+use matrix2d::linalg::{real_eigenvectors, solve3};
+use matrix2d::Matrix2;
+use point2d::Point2d;
+use vector2d::Vector2d;
+
+use crate::{residual_stats, ResidualStats};
+
+/// An ellipse fitted to points: its center, semi-axes with the major first, the angle of the
+/// major axis, and the conic `a x^2 + b xy + c y^2 + d x + e y + f = 0`, scaled so that
+/// `a + c = 1`, which is negative inside. The residuals are the Sampson distances
+/// `conic(p) / |gradient(p)|`, a first order estimate of the signed distance of each point
+/// outside the ellipse.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EllipseFit {
+    pub center: Point2d<f64>,
+    pub semi_axes: [f64; 2],
+    pub rotation: f64,
+    pub conic: [f64; 6],
+    pub residuals: Vec<f64>,
+    pub stats: ResidualStats<f64>,
+}
+
+fn outer(a: [f64; 3], b: [f64; 3]) -> [[f64; 3]; 3] {
+    a.map(|x| b.map(|y| x * y))
+}
+
+fn add(a: &mut [[f64; 3]; 3], b: [[f64; 3]; 3]) {
+    for (row, other) in a.iter_mut().zip(b) {
+        row.iter_mut().zip(other).for_each(|(x, y)| *x += y);
+    }
+}
+
+/// Fits an ellipse by Fitzgibbon's direct least squares method, minimizing the algebraic
+/// distance subject to `4 a c - b^2 = 1`, which guarantees an ellipse.
+///
+/// Follows Halíř and Flusser's numerically stable reduction to a 3x3 eigenproblem, on points
+/// centred and scaled to unit spread. Returns None if there are fewer than five points or they
+/// determine no ellipse, as when they are collinear.
+pub fn fit_ellipse(points: &[Point2d<f64>]) -> Option<EllipseFit> {
+    if points.len() < 5 {
+        return None;
+    }
+    let count = points.len() as f64;
+    let mx = points.iter().map(|p| p.x()).sum::<f64>() / count;
+    let my = points.iter().map(|p| p.y()).sum::<f64>() / count;
+    let spread = (points.iter().map(|p| (p.x() - mx).powi(2) + (p.y() - my).powi(2)).sum::<f64>() / count).sqrt();
+    if spread <= 0.0 || !spread.is_finite() {
+        return None;
+    }
+    // Scatter matrices of the quadratic terms d1 = (x^2, xy, y^2) and linear terms d2 = (x, y, 1)
+    let (mut s1, mut s2, mut s3) = ([[0.0; 3]; 3], [[0.0; 3]; 3], [[0.0; 3]; 3]);
+    for p in points {
+        let (x, y) = ((p.x() - mx) / spread, (p.y() - my) / spread);
+        let (d1, d2) = ([x * x, x * y, y * y], [x, y, 1.0]);
+        add(&mut s1, outer(d1, d1));
+        add(&mut s2, outer(d1, d2));
+        add(&mut s3, outer(d2, d2));
+    }
+    // The linear terms minimizing the error for given quadratic terms are t * quadratic,
+    // with t = -s3^-1 s2^T
+    let mut t = [[0.0; 3]; 3];
+    for column in 0..3 {
+        let solution = solve3(s3, [-s2[column][0], -s2[column][1], -s2[column][2]])?;
+        (0..3).for_each(|row| t[row][column] = solution[row]);
+    }
+    let mut reduced = s1;
+    for (i, row) in reduced.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value += (0..3).map(|k| s2[i][k] * t[k][j]).sum::<f64>();
+        }
+    }
+    // Premultiply by the inverse of the constraint matrix [[0, 0, 2], [0, -1, 0], [2, 0, 0]]
+    let [r0, r1, r2] = reduced;
+    let system = [r2.map(|v| v / 2.0), r1.map(|v| -v), r0.map(|v| v / 2.0)];
+    let (_, quadratic) = real_eigenvectors(system)
+        .into_iter()
+        .map(|(_, v)| (4.0 * v[0] * v[2] - v[1] * v[1], v))
+        .filter(|(constraint, _)| *constraint > 0.0)
+        .max_by(|a, b| a.0.total_cmp(&b.0))?;
+    let linear: [f64; 3] = std::array::from_fn(|row| (0..3).map(|k| t[row][k] * quadratic[k]).sum());
+    let [a, b, c] = quadratic;
+    let [d, e, f] = linear;
+    // Undo the normalization, substituting (x - mx) / spread for x and likewise for y, and
+    // multiply through by spread^2
+    let conic = [
+        a,
+        b,
+        c,
+        d * spread - 2.0 * a * mx - b * my,
+        e * spread - b * mx - 2.0 * c * my,
+        f * spread * spread + a * mx * mx + b * mx * my + c * my * my - d * spread * mx - e * spread * my,
+    ];
+    let scale = a + c;
+    let conic = conic.map(|v| v / scale);
+    let [a, b, c, d, e, f] = conic;
+    let center = Matrix2::new(2.0 * a, b, b, 2.0 * c).solve(&Vector2d::new(-d, -e))?;
+    let value = (d * center.x + e * center.y) / 2.0 + f;
+    let eigen = Matrix2::new(a, b / 2.0, b / 2.0, c).symmetric_eigen();
+    // The major axis lies along the eigenvector of the smaller eigenvalue
+    let semi_axes = [(-value / eigen.values[1]).sqrt(), (-value / eigen.values[0]).sqrt()];
+    if !semi_axes.iter().all(|s| s.is_finite() && *s > 0.0) {
+        return None;
+    }
+    let major = eigen.vectors.column(1);
+    let residuals: Vec<f64> = points
+        .iter()
+        .map(|p| {
+            let (x, y) = (p.x(), p.y());
+            let value = a * x * x + b * x * y + c * y * y + d * x + e * y + f;
+            value / (2.0 * a * x + b * y + d).hypot(b * x + 2.0 * c * y + e)
+        })
+        .collect();
+    let stats = residual_stats(&residuals);
+    Some(EllipseFit {
+        center: Point2d::new(center.x, center.y),
+        semi_axes,
+        rotation: major.y.atan2(major.x),
+        conic,
+        residuals,
+        stats,
+    })
+}
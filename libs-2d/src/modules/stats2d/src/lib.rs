@@ -0,0 +1,153 @@
+use line2d::Line2d;
+use matrix2d::Matrix2;
+use num_traits::Float;
+use point2d::Point2d;
+use vector2d::Vector2d;
+
+mod circle;
+mod ellipse;
+mod robust;
+pub use circle::{fit_circle_geometric, fit_circle_kasa, fit_circle_pratt, fit_circle_taubin, CircleFit};
+pub use ellipse::{fit_ellipse, EllipseFit};
+pub use robust::{huber_line_fit, ransac_line_fit, RobustLineFit};
+
+/// Anything with planar coordinates, so the statistics accept points and vectors alike.
+pub trait Coordinates<T> {
+    fn coordinates(&self) -> (T, T);
+}
+
+impl<T: Copy> Coordinates<T> for Point2d<T> {
+    fn coordinates(&self) -> (T, T) {
+        (self.0.x, self.0.y)
+    }
+}
+
+impl<T: Copy> Coordinates<T> for Vector2d<T> {
+    fn coordinates(&self) -> (T, T) {
+        (self.x, self.y)
+    }
+}
+
+/// The principal axes of a point set: its centroid, the unit directions of largest and
+/// smallest spread, and the variances along them, the larger first.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PrincipalAxes<T> {
+    pub centroid: Point2d<T>,
+    pub major: Vector2d<T>,
+    pub minor: Vector2d<T>,
+    pub variances: [T; 2],
+}
+
+/// Summary of the residuals of a fit: their mean, root mean square, standard deviation and
+/// largest absolute value.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ResidualStats<T> {
+    pub mean: T,
+    pub rms: T,
+    pub std_dev: T,
+    pub max_abs: T,
+}
+
+/// Returns the statistics of the residuals, all zero if there are none.
+pub fn residual_stats<T: Float>(residuals: &[T]) -> ResidualStats<T> {
+    let count = T::from(residuals.len().max(1)).unwrap();
+    let mean = residuals.iter().fold(T::zero(), |sum, &r| sum + r) / count;
+    let rms = (residuals.iter().fold(T::zero(), |sum, &r| sum + r * r) / count).sqrt();
+    let std_dev = (residuals.iter().fold(T::zero(), |sum, &r| sum + (r - mean) * (r - mean)) / count).sqrt();
+    let max_abs = residuals.iter().fold(T::zero(), |max, &r| max.max(r.abs()));
+    ResidualStats { mean, rms, std_dev, max_abs }
+}
+
+/// Returns the mean of the vectors, or None if there are none.
+pub fn mean<T: Float>(vectors: &[Vector2d<T>]) -> Option<Vector2d<T>> {
+    centroid(vectors).map(|c| c.0)
+}
+
+/// Returns the centroid of the points, or None if there are none.
+pub fn centroid<T: Float, P: Coordinates<T>>(points: &[P]) -> Option<Point2d<T>> {
+    let count = T::from(points.len())?;
+    let (sx, sy) = points.iter().fold((T::zero(), T::zero()), |(sx, sy), p| {
+        let (x, y) = p.coordinates();
+        (sx + x, sy + y)
+    });
+    (!points.is_empty()).then(|| Point2d::new(sx / count, sy / count))
+}
+
+/// Returns the centroid with each point counted in proportion to its weight, or None if the
+/// slices differ in length or the weights do not have a positive sum.
+pub fn weighted_centroid<T: Float, P: Coordinates<T>>(points: &[P], weights: &[T]) -> Option<Point2d<T>> {
+    if points.len() != weights.len() {
+        return None;
+    }
+    let (sx, sy, total) = points.iter().zip(weights).fold((T::zero(), T::zero(), T::zero()), |(sx, sy, total), (p, &w)| {
+        let (x, y) = p.coordinates();
+        (sx + w * x, sy + w * y, total + w)
+    });
+    (total > T::zero()).then(|| Point2d::new(sx / total, sy / total))
+}
+
+/// Returns the population covariance matrix `[[var x, cov xy], [cov xy, var y]]`, or None if
+/// there are no points.
+pub fn covariance<T: Float, P: Coordinates<T>>(points: &[P]) -> Option<Matrix2<T>> {
+    weighted_covariance(points, &vec![T::one(); points.len()]).map(|(_, covariance)| covariance)
+}
+
+/// Returns the weighted centroid and the covariance about it, weighted the same way, or None
+/// under the same conditions as `weighted_centroid`.
+pub fn weighted_covariance<T: Float, P: Coordinates<T>>(points: &[P], weights: &[T]) -> Option<(Point2d<T>, Matrix2<T>)> {
+    let center = weighted_centroid(points, weights)?;
+    // Summing about the centroid rather than using E[x^2] - E[x]^2 avoids cancellation
+    let (mut sxx, mut sxy, mut syy, mut total) = (T::zero(), T::zero(), T::zero(), T::zero());
+    for (p, &w) in points.iter().zip(weights) {
+        let (x, y) = p.coordinates();
+        let (dx, dy) = (x - center.x(), y - center.y());
+        sxx = sxx + w * dx * dx;
+        sxy = sxy + w * dx * dy;
+        syy = syy + w * dy * dy;
+        total = total + w;
+    }
+    Some((center, Matrix2::new(sxx / total, sxy / total, sxy / total, syy / total)))
+}
+
+/// Returns the principal axes of the points, or None if there are none.
+pub fn principal_axes<T: Float, P: Coordinates<T>>(points: &[P]) -> Option<PrincipalAxes<T>> {
+    weighted_principal_axes(points, &vec![T::one(); points.len()])
+}
+
+/// Returns the principal axes of the weighted points, or None under the same conditions as
+/// `weighted_centroid`.
+pub fn weighted_principal_axes<T: Float, P: Coordinates<T>>(points: &[P], weights: &[T]) -> Option<PrincipalAxes<T>> {
+    let (centroid, covariance) = weighted_covariance(points, weights)?;
+    let eigen = covariance.symmetric_eigen();
+    Some(PrincipalAxes {
+        centroid,
+        major: eigen.vectors.column(0),
+        minor: eigen.vectors.column(1),
+        variances: eigen.values,
+    })
+}
+
+/// Returns the segment along `axes.major` through the centroid spanning the projections of
+/// the points with positive weight.
+pub(crate) fn span<T: Float, P: Coordinates<T>>(axes: &PrincipalAxes<T>, points: &[P], weights: &[T]) -> Line2d<T> {
+    let (lo, hi) = points.iter().zip(weights).filter(|(_, w)| **w > T::zero()).fold(
+        (T::infinity(), T::neg_infinity()),
+        |(lo, hi), (p, _)| {
+            let (x, y) = p.coordinates();
+            let t = (x - axes.centroid.x()) * axes.major.x + (y - axes.centroid.y()) * axes.major.y;
+            (lo.min(t), hi.max(t))
+        },
+    );
+    let at = |t: T| Point2d::new(axes.centroid.x() + axes.major.x * t, axes.centroid.y() + axes.major.y * t);
+    Line2d::new(at(lo), at(hi))
+}
+
+/// Returns the line minimizing the sum of squared perpendicular distances to the points
+/// (total least squares), as the segment of that line spanning the points.
+///
+/// Returns None if there are fewer than two distinct points.
+pub fn fit_line<T: Float, P: Coordinates<T>>(points: &[P]) -> Option<Line2d<T>> {
+    let weights = vec![T::one(); points.len()];
+    let axes = weighted_principal_axes(points, &weights)?;
+    (axes.variances[0] > T::zero()).then(|| span(&axes, points, &weights))
+}
@@ -0,0 +1,131 @@
+use line2d::Line2d;
+use num_traits::Float;
+
+use crate::{span, weighted_principal_axes, Coordinates, PrincipalAxes};
+
+/// A line fitted in spite of outliers: the segment of the line spanning its inliers, the
+/// signed perpendicular distance of every point from it, and the indices of the inliers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RobustLineFit<T> {
+    pub line: Line2d<T>,
+    pub residuals: Vec<T>,
+    pub inliers: Vec<usize>,
+}
+
+fn signed_distances<T: Float, P: Coordinates<T>>(axes: &PrincipalAxes<T>, points: &[P]) -> Vec<T> {
+    points
+        .iter()
+        .map(|p| {
+            let (x, y) = p.coordinates();
+            (x - axes.centroid.x()) * axes.minor.x + (y - axes.centroid.y()) * axes.minor.y
+        })
+        .collect()
+}
+
+fn within<T: Float>(residuals: &[T], threshold: T) -> Vec<usize> {
+    (0..residuals.len()).filter(|&i| residuals[i].abs() <= threshold).collect()
+}
+
+fn weights_of<T: Float>(count: usize, inliers: &[usize]) -> Vec<T> {
+    let mut weights = vec![T::zero(); count];
+    inliers.iter().for_each(|&i| weights[i] = T::one());
+    weights
+}
+
+/// Fits a line minimizing the Huber loss of the perpendicular distances: quadratic within
+/// `delta` of the line and linear beyond, so distant points pull on it far less than in
+/// `fit_line`.
+///
+/// Iteratively reweighted total least squares, starting from the plain fit, for at most
+/// `max_iterations` rounds or until the weights settle. The inliers are the points within
+/// `delta`. Returns None if there are fewer than two distinct points.
+pub fn huber_line_fit<T: Float, P: Coordinates<T>>(
+    points: &[P],
+    delta: T,
+    max_iterations: usize,
+) -> Option<RobustLineFit<T>> {
+    let mut weights = vec![T::one(); points.len()];
+    let mut axes = weighted_principal_axes(points, &weights)?;
+    if axes.variances[0] <= T::zero() {
+        return None;
+    }
+    for _ in 0..max_iterations {
+        let residuals = signed_distances(&axes, points);
+        let next: Vec<T> = residuals.iter().map(|r| if r.abs() <= delta { T::one() } else { delta / r.abs() }).collect();
+        let settled = next.iter().zip(&weights).all(|(a, b)| (*a - *b).abs() <= T::epsilon().sqrt());
+        weights = next;
+        match weighted_principal_axes(points, &weights) {
+            Some(refit) if refit.variances[0] > T::zero() => axes = refit,
+            _ => break,
+        }
+        if settled {
+            break;
+        }
+    }
+    let residuals = signed_distances(&axes, points);
+    let inliers = within(&residuals, delta);
+    let line = span(&axes, points, &weights_of(points.len(), &inliers));
+    Some(RobustLineFit { line, residuals, inliers })
+}
+
+/// Fits a line to the largest subset of points within `threshold` of it, found by trying
+/// `iterations` lines through random pairs of points; the winner is refitted to its inliers
+/// by total least squares. The same `seed` always gives the same result.
+///
+/// Returns None if no pair of distinct points was drawn.
+pub fn ransac_line_fit<T: Float, P: Coordinates<T>>(
+    points: &[P],
+    threshold: T,
+    iterations: usize,
+    seed: u64,
+) -> Option<RobustLineFit<T>> {
+    let count = points.len();
+    if count < 2 {
+        return None;
+    }
+    let mut state = seed;
+    let mut next = move || {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (state >> 33) as usize % count
+    };
+    let mut best: Option<Vec<usize>> = None;
+    for _ in 0..iterations {
+        let (i, j) = (next(), next());
+        let ((x1, y1), (x2, y2)) = (points[i].coordinates(), points[j].coordinates());
+        let (dx, dy) = (x2 - x1, y2 - y1);
+        let length = dx.hypot(dy);
+        if length.is_zero() || !length.is_finite() {
+            continue;
+        }
+        let inliers: Vec<usize> = (0..count)
+            .filter(|&k| {
+                let (x, y) = points[k].coordinates();
+                ((x - x1) * dy - (y - y1) * dx).abs() / length <= threshold
+            })
+            .collect();
+        if best.as_ref().is_none_or(|most| inliers.len() > most.len()) {
+            best = Some(inliers);
+        }
+    }
+    let mut inliers = best?;
+    // Refitting can pick up or shed points near the threshold; repeat while it does
+    let mut axes = None;
+    for _ in 0..10 {
+        match weighted_principal_axes(points, &weights_of(count, &inliers)) {
+            Some(refit) if refit.variances[0] > T::zero() => {
+                let refit_inliers = within(&signed_distances(&refit, points), threshold);
+                axes = Some(refit);
+                if refit_inliers == inliers || refit_inliers.len() < 2 {
+                    break;
+                }
+                inliers = refit_inliers;
+            }
+            _ => break,
+        }
+    }
+    let axes = axes?;
+    let residuals = signed_distances(&axes, points);
+    let inliers = within(&residuals, threshold);
+    let line = span(&axes, points, &weights_of(count, &inliers));
+    Some(RobustLineFit { line, residuals, inliers })
+}
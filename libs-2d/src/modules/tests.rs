@@ -1,6 +1,21 @@
 mod vector2d_tests;
 mod point2d_tests;
 mod line2d_tests;
+mod path2d_tests;
+mod spline2d_tests;
+mod predicates2d_tests;
+mod polygon2d_tests;
+mod delaunay2d_tests;
+mod voronoi2d_tests;
+mod bounds2d_tests;
+mod simplify2d_tests;
+mod numeric2d_tests;
+mod matrix2d_tests;
+mod transform2d_tests;
+mod stats2d_tests;
+mod ellipse2d_tests;
+mod triangle2d_tests;
+mod rect2d_tests;
 
 use vector2d::Vector2d;
 /// use num_traits::{Float, Signed};
@@ -112,4 +127,4 @@ fn calculates_square_root_of_negative_vector_components() {
     let result = vector.sqrt();
     assert!(result.x.is_nan());
     assert!(result.y.is_nan());
-}
\ No newline at end of file
+}
@@ -0,0 +1,167 @@
+#[cfg(test)]
+mod tests {
+    use bounds2d::{convex_hull, diameter, min_area_box, min_enclosing_circle, width, BoundingCircle};
+    use point2d::Point2d;
+
+    fn random_points(count: usize, seed: u64) -> Vec<Point2d<f64>> {
+        let mut state = seed;
+        let mut next = move || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 53) as f64
+        };
+        (0..count).map(|_| Point2d::new(next() * 4.0 - 2.0, next() - 0.5)).collect()
+    }
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "expected {} but got {}", expected, actual);
+    }
+    fn rotated(points: &[Point2d<f64>], angle: f64) -> Vec<Point2d<f64>> {
+        points.iter().map(|p| p.rotate(angle)).collect()
+    }
+
+    #[test]
+    fn enclosing_circle_of_triangle_and_square() {
+        // An obtuse triangle is enclosed by the circle over its longest side
+        let obtuse = [Point2d::new(0.0, 0.0), Point2d::new(4.0, 0.0), Point2d::new(2.0, 0.5)];
+        let circle = min_enclosing_circle(&obtuse).unwrap();
+        assert_close(circle.radius, 2.0);
+        assert_close(circle.center.x(), 2.0);
+        let square = [
+            Point2d::new(0.0, 0.0),
+            Point2d::new(2.0, 0.0),
+            Point2d::new(2.0, 2.0),
+            Point2d::new(0.0, 2.0),
+            Point2d::new(1.0, 1.0),
+        ];
+        let circle = min_enclosing_circle(&square).unwrap();
+        assert_close(circle.radius, 2.0_f64.sqrt());
+        assert_eq!(circle.center, Point2d::new(1.0, 1.0));
+        assert!(min_enclosing_circle::<f64>(&[]).is_none());
+        assert_eq!(min_enclosing_circle(&[Point2d::new(3.0, 4.0)]), Some(BoundingCircle::new(Point2d::new(3.0, 4.0), 0.0)));
+    }
+    #[test]
+    fn enclosing_circle_contains_random_points() {
+        let points = random_points(500, 3);
+        let circle = min_enclosing_circle(&points).unwrap();
+        assert!(points.iter().all(|p| circle.contains(p)));
+        // The minimum circle touches at least two points
+        let touching = points.iter().filter(|p| (circle.center.distance_to(p) - circle.radius).abs() < 1e-9).count();
+        assert!(touching >= 2);
+        let (longest, _) = diameter(&points).unwrap();
+        assert!(circle.radius >= longest / 2.0 - 1e-12);
+    }
+    #[test]
+    fn convex_hull_drops_inner_and_collinear_points() {
+        let points = [
+            Point2d::new(0.0, 0.0),
+            Point2d::new(1.0, 0.0),
+            Point2d::new(2.0, 0.0),
+            Point2d::new(2.0, 2.0),
+            Point2d::new(1.0, 1.0),
+            Point2d::new(0.0, 2.0),
+            Point2d::new(0.0, 2.0),
+        ];
+        let hull = convex_hull(&points);
+        assert_eq!(hull, vec![Point2d::new(0.0, 0.0), Point2d::new(2.0, 0.0), Point2d::new(2.0, 2.0), Point2d::new(0.0, 2.0)]);
+    }
+    #[test]
+    fn diameter_and_width_of_rotated_rectangle() {
+        let rectangle = [
+            Point2d::new(0.0, 0.0),
+            Point2d::new(4.0, 0.0),
+            Point2d::new(4.0, 3.0),
+            Point2d::new(0.0, 3.0),
+            Point2d::new(2.0, 1.0),
+        ];
+        let points = rotated(&rectangle, 0.3);
+        let (longest, [a, b]) = diameter(&points).unwrap();
+        assert_close(longest, 5.0);
+        assert_close(a.distance_to(&b), 5.0);
+        let (narrowest, normal) = width(&points).unwrap();
+        assert_close(narrowest, 3.0);
+        assert_close(normal.length(), 1.0);
+        assert_close(normal.y.atan2(normal.x).rem_euclid(std::f64::consts::PI), 0.3 + std::f64::consts::FRAC_PI_2);
+    }
+    #[test]
+    fn degenerate_inputs_have_zero_width() {
+        let line = [Point2d::new(0.0, 0.0), Point2d::new(3.0, 4.0), Point2d::new(1.5, 2.0)];
+        assert_eq!(diameter(&line).unwrap().0, 5.0);
+        assert_eq!(width(&line).unwrap().0, 0.0);
+        let bounds = min_area_box(&line).unwrap();
+        assert_eq!(bounds.area(), 0.0);
+        assert_close(bounds.half_extents.0, 2.5);
+        assert!(width::<f64>(&[]).is_none());
+    }
+    #[test]
+    fn min_area_box_recovers_rotated_rectangle() {
+        let mut rectangle: Vec<Point2d<f64>> = random_points(200, 9);
+        rectangle.extend([Point2d::new(-2.0, -0.5), Point2d::new(2.0, -0.5), Point2d::new(2.0, 0.5), Point2d::new(-2.0, 0.5)]);
+        let points = rotated(&rectangle, 0.7);
+        let bounds = min_area_box(&points).unwrap();
+        assert_close(bounds.area(), 4.0);
+        assert_close(bounds.center.x(), 0.0);
+        assert_close(bounds.center.y(), 0.0);
+        assert!(points.iter().all(|p| bounds.contains(p)));
+        let mut sides = [bounds.half_extents.0, bounds.half_extents.1];
+        sides.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_close(sides[0], 0.5);
+        assert_close(sides[1], 2.0);
+    }
+    #[test]
+    fn min_area_box_is_no_larger_than_any_edge_aligned_box() {
+        let points = random_points(60, 21);
+        let bounds = min_area_box(&points).unwrap();
+        assert!(points.iter().all(|p| bounds.contains(p)));
+        let hull = convex_hull(&points);
+        for i in 0..hull.len() {
+            let angle = -(hull[(i + 1) % hull.len()] - hull[i]).y().atan2((hull[(i + 1) % hull.len()] - hull[i]).x());
+            let aligned = rotated(&points, angle);
+            let (min_x, max_x) = aligned.iter().fold((f64::MAX, f64::MIN), |(lo, hi), p| (lo.min(p.x()), hi.max(p.x())));
+            let (min_y, max_y) = aligned.iter().fold((f64::MAX, f64::MIN), |(lo, hi), p| (lo.min(p.y()), hi.max(p.y())));
+            assert!(bounds.area() <= (max_x - min_x) * (max_y - min_y) + 1e-12);
+        }
+        let corners = bounds.corners();
+        assert_close(corners[0].distance_to(&corners[2]), 2.0 * (bounds.half_extents.0.hypot(bounds.half_extents.1)));
+    }
+    #[test]
+    fn min_area_box_matches_brute_force_on_acute_hulls() {
+        // The smallest box over all hull edges, each box measured from every point
+        let brute_force = |points: &[Point2d<f64>]| {
+            let hull = convex_hull(points);
+            (0..hull.len())
+                .map(|i| {
+                    let edge = hull[(i + 1) % hull.len()] - hull[i];
+                    let aligned = rotated(points, -edge.y().atan2(edge.x()));
+                    let extent = |f: fn(&Point2d<f64>) -> f64| {
+                        let (lo, hi) = aligned.iter().fold((f64::MAX, f64::MIN), |(lo, hi), p| (lo.min(f(p)), hi.max(f(p))));
+                        hi - lo
+                    };
+                    extent(Point2d::x) * extent(Point2d::y)
+                })
+                .fold(f64::MAX, f64::min)
+        };
+        let kite = [Point2d::new(0.0, 0.0), Point2d::new(10.0, 1.0), Point2d::new(9.0, 3.0), Point2d::new(2.0, 2.0)];
+        let mut clouds = vec![kite.to_vec()];
+        // A handful of points has a hull of few vertices, usually some of them acute
+        clouds.extend((0..300).map(|seed| random_points(3 + seed as usize % 5, seed)));
+        for points in &clouds {
+            let bounds = min_area_box(points).unwrap();
+            assert!(points.iter().all(|p| bounds.contains(p)), "{points:?}");
+            let expected = brute_force(points);
+            assert!((bounds.area() - expected).abs() <= 1e-9 * expected.max(1.0), "{points:?}");
+        }
+    }
+
+    #[test]
+    fn min_area_box_stops_on_nearly_collinear_hulls() {
+        // Every projection onto these hull edges ties, which kept the calipers turning forever
+        let points = [
+            Point2d::new(4.232091708727133, 5.094074428837207),
+            Point2d::new(4.980451102690563, 5.576937819345467),
+            Point2d::new(4.827372504288081, 5.4781670260139315),
+        ];
+        assert_eq!(convex_hull(&points).len(), 3);
+        let bounds = min_area_box(&points).unwrap();
+        assert!(points.iter().all(|p| bounds.contains(p)));
+        assert!(bounds.area() < 1e-9);
+    }
+}
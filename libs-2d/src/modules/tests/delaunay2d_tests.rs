@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod tests {
+    use delaunay2d::TriangleMesh;
+    use point2d::Point2d;
+    use polygon2d::{ring_signed_area, Polygon2d};
+    use predicates2d::{incircle, orient2d};
+
+    /// Deterministic pseudo-random points in the unit square.
+    fn random_points(count: usize, seed: u64) -> Vec<Point2d<f64>> {
+        let mut state = seed;
+        let mut next = move || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 53) as f64
+        };
+        (0..count).map(|_| Point2d::new(next(), next())).collect()
+    }
+    fn grid(size: usize) -> Vec<Point2d<f64>> {
+        (0..size * size).map(|i| Point2d::new((i % size) as f64, (i / size) as f64)).collect()
+    }
+    fn total_area(mesh: &TriangleMesh) -> f64 {
+        (0..mesh.len()).map(|t| ring_signed_area(&mesh.triangle(t))).sum()
+    }
+    fn assert_valid(mesh: &TriangleMesh) {
+        for (t, triangle) in mesh.triangles().iter().enumerate() {
+            let [a, b, c] = mesh.triangle(t);
+            assert!(orient2d(&a, &b, &c) > 0.0, "triangle {} is not counter-clockwise", t);
+            for i in 0..3 {
+                if let Some(other) = mesh.neighbors()[t][i] {
+                    let j = mesh.neighbors()[other].iter().position(|&n| n == Some(t)).unwrap();
+                    assert_eq!(mesh.triangles()[other][j], triangle[(i + 1) % 3]);
+                    assert_eq!(mesh.triangles()[other][(j + 1) % 3], triangle[i]);
+                    assert_eq!(mesh.is_constrained(other, j), mesh.is_constrained(t, i));
+                }
+            }
+        }
+    }
+    fn assert_delaunay(mesh: &TriangleMesh) {
+        for t in 0..mesh.len() {
+            let [a, b, c] = mesh.triangle(t);
+            for i in 0..3 {
+                if let Some(other) = mesh.neighbors()[t][i] {
+                    if mesh.is_constrained(t, i) {
+                        continue;
+                    }
+                    let far = mesh.triangles()[other].into_iter().find(|p| !mesh.triangles()[t].contains(p)).unwrap();
+                    assert!(incircle(&a, &b, &c, &mesh.points()[far]) <= 0.0, "edge {} of triangle {} is not Delaunay", i, t);
+                }
+            }
+        }
+    }
+    fn has_edge(mesh: &TriangleMesh, a: usize, b: usize) -> bool {
+        mesh.edges().iter().any(|&[u, v]| (u, v) == (a, b) || (u, v) == (b, a))
+    }
+
+    #[test]
+    fn delaunay_of_random_points_is_valid() {
+        let points = random_points(300, 7);
+        let mesh = TriangleMesh::delaunay(&points);
+        assert_valid(&mesh);
+        assert_delaunay(&mesh);
+        // Euler: a triangulation of n points with h hull points has 2n - 2 - h triangles
+        let hull = mesh.neighbors().iter().flatten().filter(|n| n.is_none()).count();
+        assert_eq!(mesh.len(), 2 * points.len() - 2 - hull);
+        assert_eq!(mesh.edges().len(), 3 * points.len() - 3 - hull);
+    }
+    #[test]
+    fn delaunay_of_grid_covers_the_square() {
+        let mesh = TriangleMesh::delaunay(&grid(6));
+        assert_valid(&mesh);
+        assert_delaunay(&mesh);
+        assert_eq!(mesh.len(), 50);
+        assert!((total_area(&mesh) - 25.0).abs() < 1e-12);
+    }
+    #[test]
+    fn collinear_and_repeated_points() {
+        let line: Vec<Point2d<f64>> = (0..5).map(|i| Point2d::new(i as f64, 2.0 * i as f64)).collect();
+        assert!(TriangleMesh::delaunay(&line).is_empty());
+        let mut points = line.clone();
+        points.push(Point2d::new(0.0, 1.0));
+        points.push(Point2d::new(2.0, 4.0));
+        let mesh = TriangleMesh::delaunay(&points);
+        assert_valid(&mesh);
+        assert_eq!(mesh.len(), 4);
+        assert_eq!(mesh.points().len(), 7);
+        assert!(mesh.triangles().iter().flatten().all(|&i| i != 6));
+    }
+    #[test]
+    fn constraint_edge_replaces_delaunay_edges() {
+        // The Delaunay triangulation connects the two middle points, which crosses 0 - 1
+        let points = vec![
+            Point2d::new(0.0, 0.0),
+            Point2d::new(4.0, 0.0),
+            Point2d::new(2.0, 0.5),
+            Point2d::new(2.0, -0.5),
+            Point2d::new(2.0, 3.0),
+            Point2d::new(2.0, -3.0),
+        ];
+        assert!(!has_edge(&TriangleMesh::delaunay(&points), 0, 1));
+        let mesh = TriangleMesh::constrained(&points, &[[0, 1]]).unwrap();
+        assert_valid(&mesh);
+        assert_delaunay(&mesh);
+        assert!(has_edge(&mesh, 0, 1));
+        let constrained = (0..mesh.len()).flat_map(|t| (0..3).map(move |i| (t, i))).filter(|&(t, i)| mesh.is_constrained(t, i));
+        assert_eq!(constrained.count(), 2);
+    }
+    #[test]
+    fn constraint_through_a_point_is_split() {
+        let points = vec![
+            Point2d::new(0.0, 0.0),
+            Point2d::new(1.0, 0.0),
+            Point2d::new(2.0, 0.0),
+            Point2d::new(1.0, 1.0),
+            Point2d::new(1.0, -1.0),
+        ];
+        let mesh = TriangleMesh::constrained(&points, &[[0, 2]]).unwrap();
+        assert_valid(&mesh);
+        assert!(has_edge(&mesh, 0, 1) && has_edge(&mesh, 1, 2));
+    }
+    #[test]
+    fn crossing_constraints_are_rejected() {
+        let points = vec![Point2d::new(0.0, 0.0), Point2d::new(2.0, 2.0), Point2d::new(0.0, 2.0), Point2d::new(2.0, 0.0)];
+        assert!(TriangleMesh::constrained(&points, &[[0, 1], [2, 3]]).is_none());
+        assert!(TriangleMesh::constrained(&points, &[[0, 9]]).is_none());
+    }
+    #[test]
+    fn polygon_with_hole_is_triangulated_inside() {
+        let outer = vec![Point2d::new(0.0, 0.0), Point2d::new(4.0, 0.0), Point2d::new(4.0, 4.0), Point2d::new(0.0, 4.0)];
+        let hole = vec![Point2d::new(1.0, 1.0), Point2d::new(1.0, 3.0), Point2d::new(3.0, 3.0), Point2d::new(3.0, 1.0)];
+        let polygon = Polygon2d::new(outer, vec![hole]);
+        let mesh = TriangleMesh::from_polygon(&polygon).unwrap();
+        assert_valid(&mesh);
+        assert!((total_area(&mesh) - 12.0).abs() < 1e-12);
+        assert_eq!(mesh.len(), 8);
+        for t in 0..mesh.len() {
+            let [a, b, c] = mesh.triangle(t);
+            assert!(polygon.contains(&Point2d::new((a.x() + b.x() + c.x()) / 3.0, (a.y() + b.y() + c.y()) / 3.0)));
+        }
+    }
+    #[test]
+    fn concave_polygon_keeps_its_boundary() {
+        let points = vec![
+            Point2d::new(0.0, 0.0),
+            Point2d::new(10.0, 0.0),
+            Point2d::new(10.0, 10.0),
+            Point2d::new(5.0, 0.5),
+            Point2d::new(0.0, 10.0),
+        ];
+        let polygon = Polygon2d::from_exterior(points.clone());
+        let mesh = TriangleMesh::from_polygon(&polygon).unwrap();
+        assert_valid(&mesh);
+        assert_eq!(mesh.len(), 3);
+        assert!((total_area(&mesh) - polygon.area()).abs() < 1e-12);
+        for i in 0..points.len() {
+            assert!(has_edge(&mesh, i, (i + 1) % points.len()));
+        }
+    }
+}
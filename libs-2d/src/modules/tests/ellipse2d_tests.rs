@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use ellipse2d::Ellipse2d;
+    use line2d::Line2d;
+    use point2d::Point2d;
+    use vector2d::Vector2d;
+
+    fn tilted() -> Ellipse2d<f64> {
+        Ellipse2d::new(Point2d::new(4.0, -2.0), Vector2d::new(6.0, 2.5), 0.7).unwrap()
+    }
+    fn conic_value(conic: &[f64; 6], p: &Point2d<f64>) -> f64 {
+        let (x, y) = (p.x(), p.y());
+        conic[0] * x * x + conic[1] * x * y + conic[2] * y * y + conic[3] * x + conic[4] * y + conic[5]
+    }
+    #[test]
+    fn perimeter_approximations_agree_with_the_exact_value() {
+        let circle = Ellipse2d::circle(Point2d::new(1.0, 1.0), 3.0).unwrap();
+        assert!((circle.perimeter() - 6.0 * PI).abs() < 1e-12 && (circle.area() - 9.0 * PI).abs() < 1e-12);
+        assert_eq!(circle.perimeter_series(1), circle.perimeter());
+        // A 2:1 ellipse, whose perimeter is 9.688448220547675... for semi-axes 2 and 1
+        let ellipse = Ellipse2d::new(Point2d::new(0.0, 0.0), Vector2d::new(1.0, 2.0), 0.3f64).unwrap();
+        assert!((ellipse.perimeter() - 9.688448220547675).abs() < 1e-12);
+        assert!((ellipse.perimeter_ramanujan() / ellipse.perimeter() - 1.0).abs() < 1e-9);
+        assert!((ellipse.perimeter_series(12) - ellipse.perimeter()).abs() < 1e-12);
+        assert!((ellipse.perimeter_series(2) - ellipse.perimeter()).abs() > 1e-4);
+        // A nearly flat ellipse is close to four times its long radius
+        let flat = Ellipse2d::new(Point2d::new(0.0, 0.0), Vector2d::new(10.0, 1e-6), 0.0f64).unwrap();
+        assert!((flat.perimeter() - 40.0).abs() < 1e-6);
+        assert!((flat.perimeter_ramanujan() / flat.perimeter() - 1.0).abs() < 5e-4);
+        assert!(Ellipse2d::new(Point2d::new(0.0, 0.0), Vector2d::new(1.0, 0.0), 0.0).is_none());
+    }
+    #[test]
+    fn points_tangents_foci_and_containment() {
+        let ellipse = tilted();
+        let [f1, f2] = ellipse.foci();
+        for i in 0..16 {
+            let angle = i as f64 * 0.4;
+            let point = ellipse.point_at_angle(angle);
+            // The distances to the foci sum to the major axis
+            assert!((point.distance_to(&f1) + point.distance_to(&f2) - 12.0).abs() < 1e-12);
+            let tangent = ellipse.tangent_at_angle(angle);
+            let ahead = ellipse.point_at_angle(angle + 1e-6);
+            let direction = Vector2d::new(ahead.x() - point.x(), ahead.y() - point.y());
+            assert!((tangent.x * direction.y - tangent.y * direction.x).abs() < 1e-9);
+            assert!(tangent.x * direction.x + tangent.y * direction.y > 0.0);
+            let outward = Point2d::new(point.x() + (point.x() - 4.0) * 1e-9, point.y() + (point.y() + 2.0) * 1e-9);
+            let inward = Point2d::new(point.x() - (point.x() - 4.0) * 1e-9, point.y() - (point.y() + 2.0) * 1e-9);
+            assert!(ellipse.contains(&inward) && !ellipse.contains(&outward));
+        }
+        assert!((f1.distance_to(&f2) / 12.0 - ellipse.eccentricity()).abs() < 1e-12);
+        // With the second radius the larger, the foci move to the second axis
+        let upright = Ellipse2d::new(Point2d::new(0.0, 0.0), Vector2d::new(3.0, 5.0), 0.0).unwrap();
+        assert_eq!(upright.foci(), [Point2d::new(0.0, -4.0), Point2d::new(0.0, 4.0)]);
+    }
+    #[test]
+    fn lines_cross_at_points_of_the_ellipse() {
+        let ellipse = tilted();
+        let line = Line2d::new(Point2d::new(-10.0, -3.0), Point2d::new(15.0, 1.0));
+        let crossings = ellipse.line_intersections(&line);
+        assert_eq!(crossings.len(), 2);
+        assert!(crossings[0].0 < crossings[1].0);
+        for (t, point) in &crossings {
+            assert!(line.point_at(*t).distance_to(point) < 1e-12);
+            assert!(conic_value(&ellipse.to_conic(), point).abs() < 1e-12);
+        }
+        // The segment from the center outward crosses once; its line twice
+        let half = Line2d::new(Point2d::new(4.0, -2.0), Point2d::new(20.0, -2.0));
+        assert_eq!(ellipse.intersections(&half).len(), 1);
+        assert_eq!(ellipse.line_intersections(&half).len(), 2);
+        let far = Line2d::new(Point2d::new(-10.0, 30.0), Point2d::new(10.0, 30.0));
+        assert!(ellipse.line_intersections(&far).is_empty());
+        let circle = Ellipse2d::circle(Point2d::new(0.0, 0.0), 1.0).unwrap();
+        let tangent = Line2d::new(Point2d::new(-2.0, 1.0), Point2d::new(2.0, 1.0));
+        assert_eq!(circle.line_intersections(&tangent), vec![(0.5, Point2d::new(0.0, 1.0))]);
+    }
+    #[test]
+    fn bounds_and_conic_round_trip() {
+        let ellipse = tilted();
+        let (min, max) = ellipse.bounds();
+        let samples: Vec<Point2d<f64>> = (0..100_000).map(|i| ellipse.point_at_angle(i as f64 * 2.0 * PI / 1e5)).collect();
+        let lowest = samples.iter().fold(f64::INFINITY, |m, p| m.min(p.y()));
+        let rightmost = samples.iter().fold(f64::NEG_INFINITY, |m, p| m.max(p.x()));
+        assert!(samples.iter().all(|p| p.x() >= min.x() - 1e-12 && p.y() <= max.y() + 1e-12));
+        assert!((lowest - min.y()).abs() < 1e-8 && (rightmost - max.x()).abs() < 1e-8);
+        let conic = ellipse.to_conic();
+        assert!((conic[0] + conic[2] - 1.0).abs() < 1e-15);
+        assert!(conic_value(&conic, &ellipse.center) < 0.0);
+        let back = Ellipse2d::from_conic(conic.map(|c| c * -3.0)).unwrap();
+        assert!(back.center.distance_to(&ellipse.center) < 1e-12);
+        assert!((back.radii.x - 6.0).abs() < 1e-12 && (back.radii.y - 2.5).abs() < 1e-12);
+        assert!((back.rotation - 0.7).sin().abs() < 1e-12);
+        // The same ellipse with its radii swapped gives the major axis first
+        let swapped = Ellipse2d::new(ellipse.center, Vector2d::new(2.5, 6.0), 0.7 - PI / 2.0).unwrap();
+        let back = Ellipse2d::from_conic(swapped.to_conic()).unwrap();
+        assert!((back.radii.x - 6.0).abs() < 1e-12 && (back.rotation - 0.7).sin().abs() < 1e-12);
+        // The short radius of a flat ellipse comes from the product of the eigenvalues, not
+        // their difference, so the long radius survives the round trip
+        for (minor, rotation) in [(1e-6, 0.0), (1e-12, 0.0), (1e-12, PI / 2.0)] {
+            let flat = Ellipse2d::new(Point2d::new(0.0, 0.0), Vector2d::new(1.0, minor), rotation).unwrap();
+            let back = Ellipse2d::from_conic(flat.to_conic()).unwrap();
+            assert!((back.radii.x - 1.0).abs() < 1e-12 && (back.radii.y / minor - 1.0).abs() < 1e-12);
+        }
+        // x^2 - y^2 = 1 is a hyperbola and x^2 + y^2 + 1 = 0 has no real points
+        assert!(Ellipse2d::from_conic([1.0, 0.0, -1.0, 0.0, 0.0, -1.0]).is_none());
+        assert!(Ellipse2d::from_conic([1.0, 0.0, 1.0, 0.0, 0.0, 1.0]).is_none());
+    }
+}
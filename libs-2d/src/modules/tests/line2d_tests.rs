@@ -30,6 +30,57 @@ mod tests {
         assert_eq!(angle.to_degrees(), 225.0);  // Changed to counterclockwise angle
         assert!(!clockwise, "Expected angle to be clockwise");
     }
+    #[test]
+    fn intersection_returns_crossing_point_and_parameters() {
+        let line1 = Line2d::new(Point2d::new(0.0, 0.0), Point2d::new(4.0, 4.0));
+        let line2 = Line2d::new(Point2d::new(0.0, 4.0), Point2d::new(4.0, 0.0));
+        let hit = line1.intersection(&line2).expect("segments cross");
+        assert!((hit.t - 0.5).abs() < 1e-12);
+        assert!((hit.u - 0.5).abs() < 1e-12);
+        assert!(hit.point.is_approx_equal(&Point2d::new(2.0, 2.0)));
+    }
+    #[test]
+    fn intersection_returns_none_for_disjoint_segments() {
+        let line1 = Line2d::new(Point2d::new(0.0, 0.0), Point2d::new(1.0, 1.0));
+        let line2 = Line2d::new(Point2d::new(3.0, 0.0), Point2d::new(2.0, 1.0));
+        assert!(line1.intersection(&line2).is_none());
+        assert!(line1.line_intersection(&line2).is_some());
+    }
+    #[test]
+    fn intersection_detects_touching_end_points() {
+        let line1 = Line2d::new(Point2d::new(0.0, 0.0), Point2d::new(2.0, 0.0));
+        let line2 = Line2d::new(Point2d::new(2.0, 0.0), Point2d::new(2.0, 5.0));
+        let hit = line1.intersection(&line2).expect("segments touch");
+        assert_eq!(hit.t, 1.0);
+        assert_eq!(hit.u, 0.0);
+    }
+    #[test]
+    fn intersections_returns_overlap_end_points_for_collinear_segments() {
+        let line1 = Line2d::new(Point2d::new(0.0, 0.0), Point2d::new(4.0, 0.0));
+        let line2 = Line2d::new(Point2d::new(5.0, 0.0), Point2d::new(2.0, 0.0));
+        let hits = line1.intersections(&line2);
+        assert_eq!(hits.len(), 2);
+        assert!(hits[0].point.is_approx_equal(&Point2d::new(2.0, 0.0)));
+        assert!(hits[1].point.is_approx_equal(&Point2d::new(4.0, 0.0)));
+        assert!((hits[0].u - 1.0).abs() < 1e-12);
+        assert!((hits[1].u - 1.0 / 3.0).abs() < 1e-12);
+        assert!(line1.intersection(&line2).is_none());
+    }
+    #[test]
+    fn intersections_is_empty_for_parallel_segments() {
+        let line1 = Line2d::new(Point2d::new(0.0, 0.0), Point2d::new(4.0, 0.0));
+        let line2 = Line2d::new(Point2d::new(0.0, 1.0), Point2d::new(4.0, 1.0));
+        assert!(line1.intersections(&line2).is_empty());
+    }
+    #[test]
+    fn closest_point_clamps_to_segment_end_points() {
+        let line = Line2d::new(Point2d::new(0.0, 0.0), Point2d::new(4.0, 0.0));
+        let (t, point) = line.closest_point(&Point2d::new(6.0, 3.0));
+        assert_eq!(t, 1.0);
+        assert!(point.is_approx_equal(&Point2d::new(4.0, 0.0)));
+        assert_eq!(line.distance_to_point(&Point2d::new(2.0, -3.0)), 3.0);
+        assert_eq!(line.distance_to_line(&Point2d::new(9.0, 2.0)), 2.0);
+    }
     /*
     #[test]
     fn angle_between_degrees_returns_90_for_perpendicular_lines_cw() {
@@ -101,4 +152,47 @@ mod tests {
         assert!(!line1.is_angle_clockwise(&line2));
     }
     */
-}
\ No newline at end of file
+
+    #[test]
+    fn exact_intersection_of_nearly_parallel_segments() {
+        use num_traits::Zero;
+        use vector2d::{BigRational, Vector2d};
+        let rational = |x: f64, y: f64| Point2d(Vector2d::from_f64(&Vector2d::new(x, y)).unwrap());
+        let segment = |a: Point2d<BigRational>, b: Point2d<BigRational>| Line2d { pt1: a, pt2: b };
+        // The directions differ by about 1e-12 radians, below the tolerance of `intersection`
+        let (a0, a1) = ((0.0, 0.0), (1.0, 1.0));
+        let (b0, b1) = ((0.0, 1e-12), (1.0, 1.0 - 1e-12));
+        let floats = Line2d::new(Point2d::new(a0.0, a0.1), Point2d::new(a1.0, a1.1));
+        assert!(floats.intersection(&Line2d::new(Point2d::new(b0.0, b0.1), Point2d::new(b1.0, b1.1))).is_none());
+        let line1 = segment(rational(a0.0, a0.1), rational(a1.0, a1.1));
+        let line2 = segment(rational(b0.0, b0.1), rational(b1.0, b1.1));
+        let hit = line1.exact_intersection(&line2).expect("segments cross");
+        // The point lies exactly on both segments
+        for line in [&line1, &line2] {
+            let (from, to) = (&line.pt1.0, &line.pt2.0);
+            assert!((&hit.point.0 - from).cross(&(to - from)).is_zero());
+        }
+        assert_eq!(hit.point, Point2d(&line2.pt1.0 + &(&(&line2.pt2.0 - &line2.pt1.0) * hit.u.clone())));
+        // Near the middle, shifted because 1 - 1e-12 is itself rounded to the nearest f64
+        let approximate = hit.point.0.to_f64();
+        assert!((approximate.x - 0.5).abs() < 1e-3 && approximate.x == approximate.y);
+    }
+    #[test]
+    fn exact_intersection_of_touching_and_overlapping_segments() {
+        use num_traits::{One, Zero};
+        use vector2d::{BigRational, Vector2d};
+        let integer = |n: i64| BigRational::from_integer(n.into());
+        let point = |x: i64, y: i64| Point2d(Vector2d { x: integer(x), y: integer(y) });
+        let segment = |a, b| Line2d::<BigRational> { pt1: a, pt2: b };
+        let base = segment(point(0, 0), point(4, 0));
+        let touching = base.exact_intersection(&segment(point(6, 0), point(4, 0))).expect("segments touch");
+        assert_eq!((touching.t, touching.u, touching.point), (BigRational::one(), BigRational::one(), point(4, 0)));
+        assert!(base.exact_intersection(&segment(point(5, 0), point(2, 0))).is_none());
+        assert!(base.exact_intersection(&segment(point(0, 1), point(4, 1))).is_none());
+        assert!(base.exact_intersection(&segment(point(1, 1), point(3, 2))).is_none());
+        let dot = base.exact_intersection(&segment(point(3, 0), point(3, 0))).expect("point on segment");
+        assert_eq!((dot.t, dot.u), (BigRational::new(3.into(), 4.into()), BigRational::zero()));
+        let (t, closest) = base.exact_closest_point(&point(-2, 3));
+        assert_eq!((t, closest), (BigRational::zero(), point(0, 0)));
+    }
+}
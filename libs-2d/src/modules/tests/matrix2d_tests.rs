@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod tests {
+    use matrix2d::{Matrix2, Svd, SymmetricEigen};
+    use vector2d::Vector2d;
+
+    fn random_matrices(count: usize, seed: u64) -> Vec<Matrix2<f64>> {
+        let mut state = seed;
+        let mut next = move || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 53) as f64 * 20.0 - 10.0
+        };
+        (0..count).map(|_| Matrix2::new(next(), next(), next(), next())).collect()
+    }
+    fn assert_matrix_close(actual: &Matrix2<f64>, expected: &Matrix2<f64>) {
+        let difference = *actual - *expected;
+        let entries = [difference.m11, difference.m12, difference.m21, difference.m22];
+        let error = entries.iter().map(|v| v.abs()).fold(0.0, f64::max);
+        assert!(error < 1e-9, "expected {:?} but got {:?}", expected, actual);
+    }
+    fn is_orthogonal(m: &Matrix2<f64>) -> bool {
+        let product = *m * m.transpose();
+        (product.m11 - 1.0).abs() < 1e-12 && (product.m22 - 1.0).abs() < 1e-12 && product.m12.abs() < 1e-12
+    }
+
+    #[test]
+    fn products_inverse_and_transpose() {
+        let m = Matrix2::new(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(m.determinant(), -2.0);
+        assert_eq!(m.trace(), 5.0);
+        assert_eq!(m * Vector2d::new(1.0, -1.0), Vector2d::new(-1.0, -1.0));
+        assert_eq!(m * Matrix2::identity(), m);
+        assert_eq!(m.transpose(), Matrix2::from_columns(m.row(0), m.row(1)));
+        assert_eq!(Matrix2::from_rows(m.row(0), m.row(1)), m);
+        assert_matrix_close(&(m * m.inverse().unwrap()), &Matrix2::identity());
+        assert!(Matrix2::new(1.0, 2.0, 2.0, 4.0).inverse().is_none());
+        let turned = Matrix2::rotation(std::f64::consts::FRAC_PI_2) * Vector2d::new(1.0, 0.0);
+        assert!(turned.x.abs() < 1e-15 && (turned.y - 1.0).abs() < 1e-15);
+    }
+    #[test]
+    fn solve_pivots_on_the_larger_entry() {
+        // Without pivoting the tiny leading entry would swamp the result with rounding error
+        let m = Matrix2::new(1e-20f64, 1.0, 1.0, 1.0);
+        let x = m.solve(&Vector2d::new(1.0, 2.0)).unwrap();
+        assert!((x.x - 1.0).abs() < 1e-15 && (x.y - 1.0).abs() < 1e-15);
+        for m in random_matrices(50, 3) {
+            let expected = Vector2d::new(0.5, -2.0);
+            let x = m.solve(&(m * expected)).unwrap();
+            assert!((x.x - expected.x).abs() < 1e-9 && (x.y - expected.y).abs() < 1e-9);
+        }
+        assert!(Matrix2::new(1.0, 2.0, 2.0, 4.0).solve(&Vector2d::new(1.0, 1.0)).is_none());
+        assert!(Matrix2::new(0.0, 1.0, 0.0, 1.0).solve(&Vector2d::new(1.0, 1.0)).is_none());
+    }
+    #[test]
+    fn symmetric_eigen_decomposition() {
+        let m = Matrix2::new(2.0f64, 1.0, 1.0, 2.0);
+        let SymmetricEigen { values, vectors } = m.symmetric_eigen();
+        assert!((values[0] - 3.0).abs() < 1e-12 && (values[1] - 1.0).abs() < 1e-12);
+        let first = vectors.column(0);
+        assert!((first.x - first.y).abs() < 1e-12);
+        for m in random_matrices(50, 5) {
+            let symmetric = Matrix2::new(m.m11, m.m12, m.m12, m.m22);
+            let SymmetricEigen { values, vectors } = symmetric.symmetric_eigen();
+            assert!(values[0] >= values[1] && is_orthogonal(&vectors));
+            assert_matrix_close(&(vectors * Matrix2::diagonal(values[0], values[1]) * vectors.transpose()), &symmetric);
+        }
+    }
+    #[test]
+    fn svd_reconstructs_the_matrix() {
+        for m in random_matrices(100, 7).into_iter().chain([Matrix2::zero(), Matrix2::diagonal(-2.0, 3.0)]) {
+            let Svd { u, singular_values: [s1, s2], v } = m.svd();
+            assert!(s1 >= s2 && s2 >= 0.0);
+            assert!(is_orthogonal(&u) && is_orthogonal(&v));
+            assert!(v.determinant() > 0.0);
+            assert_eq!(u.determinant() > 0.0, m.determinant() >= 0.0);
+            assert_matrix_close(&(u * Matrix2::diagonal(s1, s2) * v.transpose()), &m);
+            assert!((s1 * s2 - m.determinant().abs()).abs() < 1e-9);
+        }
+        assert_eq!(Matrix2::new(0.0, 3.0, -4.0, 0.0).svd().singular_values, [4.0, 3.0]);
+    }
+    #[test]
+    fn polar_decomposition() {
+        for m in random_matrices(50, 9) {
+            let (rotation, stretch) = m.polar();
+            assert!(is_orthogonal(&rotation));
+            assert_eq!(stretch.m12, stretch.m21);
+            let SymmetricEigen { values, .. } = stretch.symmetric_eigen();
+            assert!(values[1] >= -1e-12);
+            assert_matrix_close(&(rotation * stretch), &m);
+        }
+        let (rotation, stretch) = (Matrix2::rotation(0.3) * Matrix2::diagonal(2.0, 0.5)).polar();
+        assert_matrix_close(&rotation, &Matrix2::rotation(0.3));
+        assert_matrix_close(&stretch, &Matrix2::diagonal(2.0, 0.5));
+    }
+    #[test]
+    fn three_by_three_systems_and_eigenvectors() {
+        use matrix2d::linalg::{real_eigenvectors, smallest_eigenvector, solve3, symmetric_eigen};
+        let apply = |m: [[f64; 3]; 3], v: [f64; 3]| m.map(|row| row[0] * v[0] + row[1] * v[1] + row[2] * v[2]);
+        let close = |a: [f64; 3], b: [f64; 3]| a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-9);
+        // The first pivot is zero, so rows have to be swapped
+        let a = [[0.0, 2.0, 1.0], [1.0, 1.0, 0.0], [3.0, 0.0, 1.0]];
+        assert!(close(solve3(a, [5.0, 3.0, 4.0]).unwrap(), [1.0, 2.0, 1.0]));
+        assert_eq!(solve3([[2.0f32, 0.0, 0.0], [0.0, 4.0, 0.0], [0.0, 0.0, 1.0]], [2.0, 2.0, 3.0]), Some([1.0, 0.5, 3.0]));
+        assert!(solve3([[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [0.0, 0.0, 1.0]], [1.0, 2.0, 3.0]).is_none());
+        let symmetric = [[2.0, 1.0, 0.0], [1.0, 2.0, 0.0], [0.0, 0.0, 5.0]];
+        let (values, vectors) = symmetric_eigen(symmetric);
+        for (k, value) in values.into_iter().enumerate() {
+            let vector = vectors.map(|row| row[k]);
+            assert!(close(apply(symmetric, vector), vector.map(|x| x * value)));
+        }
+        let smallest = smallest_eigenvector(symmetric);
+        assert!((smallest[0] + smallest[1]).abs() < 1e-12 && (smallest[0].abs() - 0.5f64.sqrt()).abs() < 1e-12);
+        // Not symmetric, with a real eigenvalue for each diagonal entry
+        let upper = [[2.0, 1.0, 0.0], [0.0, 3.0, 4.0], [0.0, 0.0, 1.0]];
+        let pairs = real_eigenvectors(upper);
+        assert_eq!(pairs.len(), 3);
+        for (value, vector) in pairs {
+            assert!(close(apply(upper, vector), vector.map(|x| x * value)), "{} {:?}", value, vector);
+        }
+        // A quarter turn in the plane leaves only the axis
+        let turn = real_eigenvectors([[0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 2.0]]);
+        assert_eq!(turn.len(), 1);
+        assert!((turn[0].0 - 2.0).abs() < 1e-12 && (turn[0].1[2].abs() - 1.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,304 @@
+#[cfg(test)]
+mod tests {
+    use num_traits::{Float, Num, NumCast, One, ToPrimitive, Zero};
+    use numeric2d::{derivative, gradient, jacobian, Certainty, Dual, Fixed32, Fixed64, Interval64};
+    use std::cmp::Ordering;
+    use vector2d::BigRational;
+    use point2d::Point2d;
+    use vector2d::Vector2d;
+
+    fn q16(value: f64) -> Fixed32 {
+        Fixed32::from_f64(value).unwrap()
+    }
+    fn q32(value: f64) -> Fixed64 {
+        Fixed64::from_f64(value).unwrap()
+    }
+    fn random_floats(count: usize, seed: u64) -> Vec<f64> {
+        let mut state = seed;
+        (0..count)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                let unit = (state >> 11) as f64 / (1u64 << 53) as f64;
+                (unit - 0.5) * 10f64.powi((state % 7) as i32 - 3)
+            })
+            .collect()
+    }
+    fn encloses(interval: Interval64, exact: &BigRational) -> bool {
+        let (lo, hi) = (BigRational::from_float(interval.lo()).unwrap(), BigRational::from_float(interval.hi()).unwrap());
+        lo <= *exact && *exact <= hi
+    }
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-9, "expected {:?} but got {:?}", expected, actual);
+        }
+    }
+    fn assert_near<F: Float + ToPrimitive>(actual: F, expected: f64, tolerance: f64) {
+        let actual = actual.to_f64().unwrap();
+        assert!((actual - expected).abs() <= tolerance, "expected {} but got {}", expected, actual);
+    }
+
+    #[test]
+    fn arithmetic_rounds_and_saturates() {
+        assert_eq!(q16(1.5) * q16(2.25), q16(3.375));
+        assert_eq!(q16(7.0) / q16(2.0), q16(3.5));
+        assert_eq!(q16(-7.5) % q16(2.0), q16(-1.5));
+        assert_eq!(Fixed32::from_bits(1) * q16(0.5), Fixed32::from_bits(1));
+        assert_eq!(Fixed32::max_value() + Fixed32::one(), Fixed32::infinity());
+        assert_eq!(q16(30000.0) * q16(4.0), Fixed32::max_value());
+        assert_eq!(q16(-1.0) / Fixed32::zero(), Fixed32::neg_infinity());
+        assert_eq!(-Fixed32::min_value(), Fixed32::max_value());
+        assert!(Fixed32::from_f64(40000.0).is_none());
+        assert!(Fixed32::from_f64(f64::NAN).is_none());
+        assert_eq!(Fixed32::from_bits(0x18000).to_bits(), 0x18000);
+    }
+    #[test]
+    fn rounding_matches_floats() {
+        for value in [-2.5, -1.75, -1.0, -0.25, 0.0, 0.5, 1.25, 2.5, 3.75] {
+            let fixed = q16(value);
+            assert_eq!(fixed.floor(), q16(value.floor()));
+            assert_eq!(fixed.ceil(), q16(value.ceil()));
+            assert_eq!(fixed.round(), q16(value.round()));
+            assert_eq!(fixed.trunc(), q16(value.trunc()));
+            assert_eq!(fixed.fract(), q16(value.fract()));
+        }
+    }
+    #[test]
+    fn square_roots_are_exact_integer_results() {
+        // floor(sqrt(2) * 2^16)
+        assert_eq!(q16(2.0).sqrt().to_bits(), 92681);
+        assert_eq!(q16(6.25).sqrt(), q16(2.5));
+        assert_eq!(q16(3.0).hypot(q16(4.0)), q16(5.0));
+        // Rounded down from the exact root even where f64 would lose the low bits
+        let side = q32(1e9).to_bits() as u128;
+        let diagonal = q32(1e9).hypot(q32(1e9)).to_bits() as u128;
+        assert!(diagonal * diagonal <= 2 * side * side && (diagonal + 1) * (diagonal + 1) > 2 * side * side);
+        assert_eq!(q16(-4.0).sqrt(), Fixed32::zero());
+        assert_near(q32(2.0).cbrt(), 2f64.cbrt(), 1e-9);
+        assert_near(q32(-27.0).cbrt(), -3.0, 1e-9);
+    }
+    #[test]
+    fn trigonometry_follows_cordic_to_the_last_bit() {
+        for i in -40..=40 {
+            let angle = i as f64 * 0.173;
+            assert_near(q16(angle).sin(), angle.sin(), 2.0 / 65536.0);
+            assert_near(q16(angle).cos(), angle.cos(), 2.0 / 65536.0);
+            assert_near(q32(angle).sin(), angle.sin(), 2e-9);
+            assert_near(q32(angle).cos(), angle.cos(), 2e-9);
+            assert_near(q32(angle).atan(), angle.atan(), 2e-9);
+            let (y, x) = (angle.sin() * 3.0, (angle * 1.7).cos() * 2.0);
+            assert_near(q32(y).atan2(q32(x)), y.atan2(x), 2e-9);
+        }
+        for value in [-1.0, -0.6, 0.0, 0.3, 0.99, 1.0] {
+            assert_near(q32(value).asin(), value.asin(), 2e-9);
+            assert_near(q32(value).acos(), value.acos(), 2e-9);
+        }
+        assert_near(q32(1.0).tan(), 1f64.tan(), 2e-9);
+        assert_eq!(q16(-1.0).atan2(Fixed32::zero()), -q16(1.0).atan2(Fixed32::zero()));
+        assert_eq!(q16(2.0).asin(), Fixed32::zero());
+    }
+    #[test]
+    fn exponentials_and_logarithms() {
+        // Compared against the value the input rounded to
+        for x in [-10.0, -1.0, -0.01, 0.0, 0.5, 1.0, 3.0, 10.0].map(q32) {
+            let value = x.to_f64().unwrap();
+            assert_near(x.exp(), value.exp(), 1e-9 * value.exp().max(1.0));
+            assert_near(x.tanh(), value.tanh(), 2e-9);
+            assert_near(x.sinh(), value.sinh(), 1e-9 * value.cosh());
+            assert_near(x.cosh(), value.cosh(), 1e-9 * value.cosh());
+        }
+        for x in [0.001, 0.5, 1.0, 2.0, 1000.0, 2e9].map(q32) {
+            let value = x.to_f64().unwrap();
+            assert_near(x.ln(), value.ln(), 1e-9);
+            assert_near(x.log2(), value.log2(), 1e-9);
+            assert_near(x.log10(), value.log10(), 1e-9);
+        }
+        assert_near(q32(2.0).powf(q32(0.5)), 2f64.sqrt(), 1e-9);
+        assert_eq!(q16(-2.0).powf(q16(3.0)), q16(-8.0));
+        assert_eq!(q16(2.0).powi(-2), q16(0.25));
+        assert_eq!(q16(20.0).exp(), Fixed32::max_value());
+        assert_eq!(q16(-20.0).exp(), Fixed32::zero());
+        assert_eq!(Fixed32::zero().ln(), Fixed32::neg_infinity());
+    }
+    #[test]
+    fn conversions_and_formatting() {
+        let value: Fixed32 = NumCast::from(2.75f32).unwrap();
+        assert_eq!(value, q16(2.75));
+        assert_eq!(q16(-2.75).to_i64(), Some(-2));
+        assert_eq!(q16(-2.75).to_u64(), None);
+        assert_eq!(Fixed32::from_str_radix("-1.5", 10).ok(), Some(q16(-1.5)));
+        assert!(Fixed32::from_str_radix("1e9", 10).is_err());
+        assert_eq!(format!("{} {:?}", q16(0.25), q16(-3.0)), "0.25 -3.0");
+        assert_near(q16(180.0).to_radians(), std::f64::consts::PI, 1e-4);
+    }
+    #[test]
+    fn vectors_of_fixed_point_components() {
+        let v = Vector2d::new(q16(3.0), q16(4.0));
+        assert_eq!(v.length(), q16(5.0));
+        let unit = v.normalized().unwrap();
+        assert_eq!((unit.x(), unit.y()), (q16(0.6), q16(0.8)));
+        let turned = Vector2d::new(q32(2.0), q32(0.0)).rotate(q32(std::f64::consts::FRAC_PI_2));
+        assert_near(turned.x, 0.0, 1e-8);
+        assert_near(turned.y, 2.0, 1e-8);
+        assert_near(v.angle(), 4f64.atan2(3.0), 2.0 / 65536.0);
+        let (a, b) = (Point2d::new(q16(1.0), q16(1.0)), Point2d::new(q16(4.0), q16(5.0)));
+        assert_eq!(a.distance_to(&b), q16(5.0));
+    }
+    #[test]
+    fn interval_arithmetic_encloses_exact_results() {
+        let values = random_floats(400, 11);
+        let exact = |x: f64| BigRational::from_float(x).unwrap();
+        for pair in values.chunks(2) {
+            let (a, b) = (Interval64::point(pair[0]), Interval64::point(pair[1]));
+            let (x, y) = (exact(pair[0]), exact(pair[1]));
+            assert!(encloses(a + b, &(&x + &y)));
+            assert!(encloses(a - b, &(&x - &y)));
+            assert!(encloses(a * b, &(&x * &y)));
+            assert!(encloses(a / b, &(&x / &y)));
+            // Directed rounding moves each bound by at most one float
+            assert!((a * b).hi() <= (pair[0] * pair[1]).next_up() && (a * b).lo() >= (pair[0] * pair[1]).next_down());
+        }
+        // Exact operations stay points
+        assert_eq!(Interval64::point(1.5) * Interval64::point(2.0), Interval64::point(3.0));
+        assert_eq!(Interval64::point(0.75) - Interval64::point(0.25), Interval64::point(0.5));
+        let third = Interval64::point(1.0) / Interval64::point(3.0);
+        assert_eq!(third.hi(), third.lo().next_up());
+        let mixed = Interval64::new(-1.0, 2.0).unwrap();
+        assert_eq!(mixed * mixed, Interval64::new(-2.0, 4.0).unwrap());
+        assert_eq!(mixed.powi(2), Interval64::new(0.0, 4.0).unwrap());
+        assert_eq!((Interval64::point(1.0) / mixed).lo(), f64::NEG_INFINITY);
+        assert!(Interval64::new(2.0, 1.0).is_none());
+    }
+    #[test]
+    fn interval_equality_agrees_with_ordering() {
+        let span = Interval64::new(1.0, 2.0).unwrap();
+        assert_eq!(span, Interval64::new(1.0, 2.0).unwrap());
+        assert_eq!(span.partial_cmp(&span), Some(Ordering::Equal));
+        assert!(span <= Interval64::new(1.0, 2.0).unwrap());
+        // Overlapping intervals that differ are neither equal nor ordered
+        let wider = Interval64::new(1.0, 3.0).unwrap();
+        assert_ne!(span, wider);
+        assert_eq!(span.partial_cmp(&wider), None);
+        assert_eq!(Interval64::point(2.0).partial_cmp(&Interval64::point(2.0)), Some(Ordering::Equal));
+        assert_eq!(span.partial_cmp(&Interval64::new(2.0, 3.0).unwrap()), None);
+        assert_eq!(span.partial_cmp(&Interval64::new(2.5, 3.0).unwrap()), Some(Ordering::Less));
+        let nan = Interval64::point(-1.0).sqrt();
+        assert!(nan != nan && nan.partial_cmp(&nan).is_none());
+    }
+    #[test]
+    fn interval_functions_bracket_their_values() {
+        let two = Interval64::point(2.0);
+        let root = two.sqrt();
+        let exact_two = BigRational::from_float(2.0).unwrap();
+        let square = |x: f64| BigRational::from_float(x).unwrap().pow(2);
+        assert!(square(root.lo()) <= exact_two && square(root.hi()) >= exact_two);
+        assert_eq!(Interval64::point(6.25).sqrt(), Interval64::point(2.5));
+        assert!(Interval64::pi().contains(std::f64::consts::PI) && Interval64::pi().lo() < Interval64::pi().hi());
+        // Extremes inside the interval are included, not just the values at the ends
+        let span = Interval64::new(1.0, 2.0).unwrap();
+        assert_eq!(span.sin().hi(), 1.0);
+        assert!(span.sin().lo() < 1f64.sin() && span.sin().lo() > 0.84);
+        assert_eq!(Interval64::new(3.0, 3.5).unwrap().cos().lo(), -1.0);
+        assert_eq!(span.tan(), Interval64::new(f64::NEG_INFINITY, f64::INFINITY).unwrap());
+        assert!(Interval64::new(-1.0, 1.0).unwrap().tan().contains(1f64.tan()));
+        let angle = Interval64::point(1.0).atan2(Interval64::new(-1.0, 1.0).unwrap());
+        assert!(angle.contains(std::f64::consts::FRAC_PI_4) && angle.contains(3.0 * std::f64::consts::FRAC_PI_4));
+        assert!(Interval64::new(-2.0, -1.0).unwrap().ln().is_nan());
+        assert!(Interval64::point(1.0).exp().contains(std::f64::consts::E));
+    }
+    #[test]
+    fn certified_orientation_with_exact_fallback() {
+        let point = |x: f64, y: f64| Vector2d::new(Interval64::point(x), Interval64::point(y));
+        let cross_sign = |a: &Vector2d<Interval64>, b: &Vector2d<Interval64>, c: &Vector2d<Interval64>| {
+            (b - a).cross(&(c - a)).sign()
+        };
+        let (a, b) = (point(0.0, 0.0), point(1.0, 1.0));
+        assert_eq!(cross_sign(&a, &b, &point(0.0, 1.0)), Certainty::Certain(Ordering::Greater));
+        assert_eq!(cross_sign(&a, &b, &point(3.0, 3.0)), Certainty::Certain(Ordering::Equal));
+        // Too close to the line for the filter, so the exact predicate decides
+        let (p, q, r) = ((0.1, 0.1), (0.3, 0.3), (0.7, 0.7f64.next_up()));
+        let filtered = cross_sign(&point(p.0, p.1), &point(q.0, q.1), &point(r.0, r.1));
+        assert_eq!(filtered, Certainty::Uncertain);
+        let exact = predicates2d::orient2d(&Point2d::new(p.0, p.1), &Point2d::new(q.0, q.1), &Point2d::new(r.0, r.1));
+        assert!(filtered.certain().is_none() && exact != 0.0);
+        // The float methods of vectors and points accept intervals as well
+        let length = point(3.0, 4.0).length();
+        assert!(length.contains(5.0) && length.width() <= 1e-14);
+        let (from, to) = (Point2d(point(1.0, 1.0)), Point2d(point(4.0, 5.0)));
+        let distance = from.distance_to(&to);
+        assert!(distance.contains(5.0));
+        assert!(point(1.0, 0.0).rotate(Interval64::pi() / Interval64::point(2.0)).y.contains(1.0));
+    }
+    #[test]
+    fn dual_numbers_match_finite_differences() {
+        let functions: [fn(Dual<f64>) -> Dual<f64>; 8] = [
+            |x| x.sin() * x.exp(),
+            |x| (x * x + Dual::constant(1.0)).sqrt().ln(),
+            |x| x.atan2(Dual::constant(0.5)) / x.cosh(),
+            |x| x.powf(x) + x.powi(3),
+            |x| x.hypot(Dual::constant(2.0)).tanh(),
+            |x| (x / Dual::constant(4.0)).asin() + x.cbrt(),
+            |x| x.sin_cos().1 * x.tan().recip(),
+            |x| x.mul_add(x, x.log10()).abs(),
+        ];
+        for f in functions {
+            for x in [0.3, 0.9, 1.7] {
+                let (value, slope) = derivative(f, x);
+                let h = 1e-6;
+                let numeric = (f(Dual::constant(x + h)).value - f(Dual::constant(x - h)).value) / (2.0 * h);
+                assert_eq!(value, f(Dual::constant(x)).value);
+                assert!((slope - numeric).abs() <= 1e-6 * numeric.abs().max(1.0), "{} vs {}", slope, numeric);
+            }
+        }
+        assert!(Dual::variable(2.0) < Dual::new(3.0, -1.0) && Dual::new(1.0, 5.0) == Dual::constant(1.0));
+    }
+    #[test]
+    fn vector_methods_are_differentiable() {
+        let vector = |v: &[Dual<f64>]| Vector2d::new(v[0], v[1]);
+        let (length, slope) = gradient(|v| vector(v).length(), &[3.0, 4.0]);
+        assert_eq!(length, 5.0);
+        assert_close(&slope, &[0.6, 0.8]);
+        // The angle turns fastest across the radius, at 1 / r per unit
+        let (_, slope) = gradient(|v| vector(v).angle(), &[3.0, 4.0]);
+        assert_close(&slope, &[-4.0 / 25.0, 3.0 / 25.0]);
+        let target = Point2d::new(Dual::constant(1.0), Dual::constant(1.0));
+        let (distance, slope) = gradient(|v| Point2d(vector(v)).distance_to(&target), &[4.0, 5.0]);
+        assert_eq!(distance, 5.0);
+        assert_close(&slope, &[0.6, 0.8]);
+        // Rotating (r, 0) by theta maps polar to cartesian coordinates
+        let (r, theta) = (2.0, 0.7);
+        let (values, rows) = jacobian(
+            |v| {
+                let turned = Vector2d::new(v[0], Dual::constant(0.0)).rotate(v[1]);
+                vec![turned.x, turned.y]
+            },
+            &[r, theta],
+        );
+        assert_close(&values, &[r * theta.cos(), r * theta.sin()]);
+        assert_close(&rows[0], &[theta.cos(), -r * theta.sin()]);
+        assert_close(&rows[1], &[theta.sin(), r * theta.cos()]);
+    }
+    #[test]
+    fn gradient_descent_finds_the_geometric_median() {
+        let anchors = [(0.0, 0.0), (10.0, 0.0), (0.0, 10.0)];
+        let cost = |v: &[Dual<f64>]| {
+            let p = Point2d(Vector2d::new(v[0], v[1]));
+            anchors
+                .iter()
+                .map(|&(x, y)| p.distance_to(&Point2d::new(Dual::constant(x), Dual::constant(y))))
+                .fold(Dual::constant(0.0), |sum, d| sum + d)
+        };
+        let mut at = vec![5.0, 5.0];
+        for _ in 0..2000 {
+            let (_, slope) = gradient(cost, &at);
+            at[0] -= 0.05 * slope[0];
+            at[1] -= 0.05 * slope[1];
+        }
+        // Every angle of the triangle is below 120 degrees, so the median is the point that
+        // sees each pair of anchors at 120 degrees, where the unit vectors towards them cancel
+        let (_, slope) = gradient(cost, &at);
+        assert!(slope[0].abs() < 1e-6 && slope[1].abs() < 1e-6);
+        let expected = 10.0 / (3.0 + 3f64.sqrt());
+        assert!((at[0] - expected).abs() < 1e-6 && (at[1] - expected).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,235 @@
+#[cfg(test)]
+mod tests {
+    use line2d::Line2d;
+    use point2d::Point2d;
+    use vector2d::Vector2d;
+    use path2d::{Path2d, PathParseError, PathSegment};
+
+    fn square() -> Path2d<f64> {
+        let mut path = Path2d::new();
+        path.move_to(Point2d::new(0.0, 0.0));
+        path.line_to(Point2d::new(2.0, 0.0));
+        path.line_to(Point2d::new(2.0, 2.0));
+        path.line_to(Point2d::new(0.0, 2.0));
+        path.close();
+        path
+    }
+
+    #[test]
+    fn length_of_closed_square_includes_closing_edge() {
+        assert!((square().length(1e-6) - 8.0).abs() < 1e-12);
+    }
+    #[test]
+    fn length_of_half_circle_arc_matches_pi_r() {
+        let mut path = Path2d::new();
+        path.move_to(Point2d::new(0.0, 0.0));
+        path.arc_to(Vector2d::new(1.0, 1.0), 0.0, false, true, Point2d::new(2.0, 0.0));
+        let length = path.length(1e-7);
+        assert!((length - std::f64::consts::PI).abs() < 1e-3, "length was {}", length);
+    }
+    #[test]
+    fn arcs_flatten_finely_without_a_positive_tolerance() {
+        let mut path = Path2d::new();
+        path.move_to(Point2d::new(0.0, 0.0));
+        path.arc_to(Vector2d::new(1.0, 1.0), 0.0, false, true, Point2d::new(2.0, 0.0));
+        for tolerance in [0.0, -1.0, f64::NAN] {
+            let polyline = &path.flatten(tolerance)[0];
+            assert!(polyline.len() > 1000, "{} points for tolerance {}", polyline.len(), tolerance);
+            assert!((path.length(tolerance) - std::f64::consts::PI).abs() < 1e-5);
+        }
+    }
+    #[test]
+    fn point_at_length_walks_along_segments() {
+        let path = square();
+        let point = path.point_at_length(3.0, 1e-6).unwrap();
+        assert!(point.is_approx_equal(&Point2d::new(2.0, 1.0)));
+        let end = path.point_at_length(100.0, 1e-6).unwrap();
+        assert!(end.is_approx_equal(&Point2d::new(0.0, 0.0)));
+        assert!(Path2d::<f64>::new().point_at_length(1.0, 1e-6).is_none());
+    }
+    #[test]
+    fn bounds_include_curve_extrema() {
+        let mut path = Path2d::new();
+        path.move_to(Point2d::new(0.0, 0.0));
+        path.quad_to(Point2d::new(1.0, 2.0), Point2d::new(2.0, 0.0));
+        let (min, max) = path.bounds().unwrap();
+        assert!(min.is_approx_equal(&Point2d::new(0.0, 0.0)));
+        assert!(max.is_approx_equal(&Point2d::new(2.0, 1.0)));
+    }
+    #[test]
+    fn bounds_include_arc_extrema() {
+        let mut path: Path2d<f64> = Path2d::new();
+        path.move_to(Point2d::new(0.0, 0.0));
+        path.arc_to(Vector2d::new(1.0, 1.0), 0.0, false, false, Point2d::new(2.0, 0.0));
+        let (min, max) = path.bounds().unwrap();
+        assert!((max.y() - 1.0).abs() < 1e-12, "max was {:?}", max);
+        assert!(min.y().abs() < 1e-12);
+    }
+    #[test]
+    fn cubic_meets_line_at_both_roots() {
+        let mut path = Path2d::new();
+        path.move_to(Point2d::new(0.0, 0.0));
+        path.cubic_to(Point2d::new(0.0, 1.0), Point2d::new(1.0, 1.0), Point2d::new(1.0, 0.0));
+        // The curve's height is 3 t (1 - t), which is 0.5 at t = (1 -+ 1 / sqrt(3)) / 2
+        let line = Line2d::new(Point2d::new(-1.0, 0.5), Point2d::new(2.0, 0.5));
+        let hits = path.line_intersections(&line, 1e-9);
+        assert_eq!(hits.len(), 2, "{:?}", hits);
+        for (hit, sign) in hits.iter().zip([-1.0, 1.0]) {
+            let t = (1.0 + sign / 3f64.sqrt()) / 2.0;
+            let x = 3.0 * t * t - 2.0 * t * t * t;
+            assert_eq!(hit.segment, 1);
+            assert!((hit.t - t).abs() < 1e-6, "t was {} not {}", hit.t, t);
+            assert!((hit.u - (x + 1.0) / 3.0).abs() < 1e-6);
+            assert!(hit.point.distance_to(&Point2d::new(x, 0.5)) < 1e-6);
+        }
+    }
+    #[test]
+    fn circles_drawn_with_arcs_cross_twice() {
+        let circle = |cx: f64| {
+            let mut path = Path2d::new();
+            path.move_to(Point2d::new(cx + 1.0, 0.0));
+            path.arc_to(Vector2d::new(1.0, 1.0), 0.0, false, true, Point2d::new(cx - 1.0, 0.0));
+            path.arc_to(Vector2d::new(1.0, 1.0), 0.0, false, true, Point2d::new(cx + 1.0, 0.0));
+            path.close();
+            path
+        };
+        let hits = circle(0.0).intersections(&circle(1.0), 1e-9);
+        assert_eq!(hits.len(), 2, "{:?}", hits);
+        let height = 3f64.sqrt() / 2.0;
+        for expected in [Point2d::new(0.5, height), Point2d::new(0.5, -height)] {
+            assert!(hits.iter().any(|hit| hit.point.distance_to(&expected) < 1e-7), "{:?}", hits);
+        }
+        // Arc parameters are fractions of the swept angle: each crossing is 60 degrees into the
+        // half circle of one path and 120 degrees into that of the other
+        let pairs: Vec<_> = hits.iter().map(|hit| (hit.segment, hit.other_segment)).collect();
+        assert_eq!(pairs, [(1, 1), (2, 2)]);
+        for (hit, (t, u)) in hits.iter().zip([(1.0 / 3.0, 2.0 / 3.0), (2.0 / 3.0, 1.0 / 3.0)]) {
+            assert!((hit.t - t).abs() < 1e-6 && (hit.u - u).abs() < 1e-6, "{:?}", hit);
+        }
+    }
+    #[test]
+    fn coinciding_curves_give_the_ends_of_the_overlap() {
+        let mut circle = Path2d::new();
+        circle.move_to(Point2d::new(1.0, 0.0));
+        circle.arc_to(Vector2d::new(1.0, 1.0), 0.0, false, true, Point2d::new(-1.0, 0.0));
+        circle.arc_to(Vector2d::new(1.0, 1.0), 0.0, false, true, Point2d::new(1.0, 0.0));
+        // The quarter of the upper half circle from 45 to 135 degrees, drawn backwards
+        let (x, y) = (0.5f64.sqrt(), 0.5f64.sqrt());
+        let mut quarter = Path2d::new();
+        quarter.move_to(Point2d::new(-x, y));
+        quarter.arc_to(Vector2d::new(1.0, 1.0), 0.0, false, false, Point2d::new(x, y));
+        let hits = circle.intersections(&quarter, 1e-9);
+        assert_eq!(hits.len(), 2, "{:?}", hits);
+        for (hit, (t, u)) in hits.iter().zip([(0.25, 1.0), (0.75, 0.0)]) {
+            assert_eq!((hit.segment, hit.other_segment), (1, 1));
+            assert!((hit.t - t).abs() < 1e-6 && (hit.u - u).abs() < 1e-6, "{:?}", hit);
+        }
+    }
+    #[test]
+    fn nested_curves_with_overlapping_bounds_do_not_meet() {
+        let arch = |x0: f64, peak: f64, x1: f64| {
+            let mut path = Path2d::new();
+            path.move_to(Point2d::new(x0, 0.0));
+            path.quad_to(Point2d::new((x0 + x1) / 2.0, peak), Point2d::new(x1, 0.0));
+            path
+        };
+        assert!(arch(0.0, 2.0, 2.0).intersections(&arch(0.2, 1.6, 1.8), 1e-9).is_empty());
+        assert_eq!(arch(0.0, 2.0, 2.0).intersections(&arch(0.2, 4.0, 1.8), 1e-9).len(), 2);
+    }
+    #[test]
+    fn flatten_closes_closed_subpaths() {
+        let polylines = square().flatten(1e-6);
+        assert_eq!(polylines.len(), 1);
+        assert_eq!(polylines[0].len(), 5);
+        assert_eq!(polylines[0][0], polylines[0][4]);
+    }
+    #[test]
+    fn reversed_square_runs_backwards_and_stays_closed() {
+        let reversed = square().reversed();
+        assert_eq!(reversed.segments()[0], PathSegment::MoveTo(Point2d::new(0.0, 2.0)));
+        assert_eq!(reversed.segments()[3], PathSegment::LineTo(Point2d::new(0.0, 0.0)));
+        assert_eq!(reversed.segments()[4], PathSegment::Close);
+        assert_eq!(reversed.reversed(), square());
+    }
+    #[test]
+    fn reversed_arc_flips_sweep_flag() {
+        let path: Path2d<f64> = "M0 0 A1 1 0 0 1 2 0".parse().unwrap();
+        let reversed = path.reversed();
+        match reversed.segments()[1] {
+            PathSegment::ArcTo { sweep, to, .. } => {
+                assert!(!sweep);
+                assert_eq!(to, Point2d::new(0.0, 0.0));
+            }
+            segment => panic!("unexpected segment {:?}", segment),
+        }
+    }
+    #[test]
+    fn transform_scales_arc_radii() {
+        let path: Path2d<f64> = "M0 0 A1 1 0 0 1 2 0".parse().unwrap();
+        let scaled = path.scale(2.0, 1.0);
+        match scaled.segments()[1] {
+            PathSegment::ArcTo { radii, to, .. } => {
+                assert!((radii.x - 2.0).abs() < 1e-12);
+                assert!((radii.y - 1.0).abs() < 1e-12);
+                assert_eq!(to, Point2d::new(4.0, 0.0));
+            }
+            segment => panic!("unexpected segment {:?}", segment),
+        }
+        let mirrored = path.scale(1.0, -1.0);
+        let (min, max) = mirrored.bounds().unwrap();
+        assert!((max.y() - 1.0).abs() < 1e-12 && min.y().abs() < 1e-12);
+    }
+    #[test]
+    fn from_svg_resolves_relative_and_shorthand_commands() {
+        let path: Path2d<f64> = Path2d::from_svg("m10,10 h5 v5 l-5-5z M0 0 q1 1 2 0 t2 0").unwrap();
+        assert_eq!(path.segments()[1], PathSegment::LineTo(Point2d::new(15.0, 10.0)));
+        assert_eq!(path.segments()[2], PathSegment::LineTo(Point2d::new(15.0, 15.0)));
+        assert_eq!(path.segments()[3], PathSegment::LineTo(Point2d::new(10.0, 10.0)));
+        assert_eq!(path.segments()[4], PathSegment::Close);
+        assert_eq!(
+            path.segments()[7],
+            PathSegment::QuadTo { ctrl: Point2d::new(3.0, -1.0), to: Point2d::new(4.0, 0.0) }
+        );
+    }
+    #[test]
+    fn from_svg_handles_compact_numbers_and_flags() {
+        let path: Path2d<f64> = Path2d::from_svg("M.5.5L1e1-2a1 1 0 011 1").unwrap();
+        assert_eq!(path.segments()[0], PathSegment::MoveTo(Point2d::new(0.5, 0.5)));
+        assert_eq!(path.segments()[1], PathSegment::LineTo(Point2d::new(10.0, -2.0)));
+        match path.segments()[2] {
+            PathSegment::ArcTo { large_arc, sweep, to, .. } => {
+                assert!(!large_arc && sweep);
+                assert_eq!(to, Point2d::new(11.0, -1.0));
+            }
+            segment => panic!("unexpected segment {:?}", segment),
+        }
+    }
+    #[test]
+    fn from_svg_reports_errors() {
+        assert_eq!(Path2d::<f64>::from_svg("L1 1"), Err(PathParseError::MissingMoveTo { position: 0 }));
+        assert_eq!(Path2d::<f64>::from_svg("M1"), Err(PathParseError::ExpectedNumber { position: 2 }));
+        assert_eq!(Path2d::<f64>::from_svg("M0 0 A1 1 0 2 0 1 1"), Err(PathParseError::ExpectedFlag { position: 12 }));
+        assert_eq!(
+            Path2d::<f64>::from_svg("M0 0 #"),
+            Err(PathParseError::UnexpectedCharacter { position: 5, found: '#' })
+        );
+    }
+    #[test]
+    fn to_svg_round_trips() {
+        let data = "M0 0 C1 2 3 4 5 6 A2 1 30 1 0 7 8 Z";
+        let path: Path2d<f64> = data.parse().unwrap();
+        let reparsed: Path2d<f64> = path.to_svg().parse().unwrap();
+        assert_eq!(path.segments().len(), reparsed.segments().len());
+        assert_eq!(square().to_svg(), "M0 0 L2 0 L2 2 L0 2 Z");
+        // A path drawn without an opening move starts at the origin
+        let mut open = Path2d::new();
+        open.line_to(Point2d::new(3.0, 4.0));
+        open.quad_to(Point2d::new(5.0, 0.0), Point2d::new(6.0, 1.0));
+        assert_eq!(open.to_svg(), "M0 0 L3 4 Q5 0 6 1");
+        let reparsed: Path2d<f64> = open.to_svg().parse().unwrap();
+        assert_eq!(reparsed.segments()[0], PathSegment::MoveTo(Point2d::new(0.0, 0.0)));
+        assert_eq!(&reparsed.segments()[1..], open.segments());
+        assert!((reparsed.length(1e-9) - open.length(1e-9)).abs() < 1e-12);
+        assert_eq!(Path2d::<f64>::new().to_svg(), "");
+    }
+}
@@ -0,0 +1,346 @@
+#[cfg(test)]
+mod tests {
+    use point2d::Point2d;
+    use polygon2d::{offset_polyline, ring_signed_area, BooleanOp, EndType, JoinType, MultiPolygon2d, Polygon2d};
+
+    fn rect(x0: f64, y0: f64, x1: f64, y1: f64) -> Polygon2d<f64> {
+        Polygon2d::from_exterior(vec![
+            Point2d::new(x0, y0),
+            Point2d::new(x1, y0),
+            Point2d::new(x1, y1),
+            Point2d::new(x0, y1),
+        ])
+    }
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "expected {} but got {}", expected, actual);
+    }
+    /// Sums the signed areas of index triangles, checking that none is clockwise.
+    fn triangles_area<T: num_traits::Float>(polygon: &Polygon2d<T>, triangles: &[[usize; 3]]) -> T {
+        let vertices = polygon.vertices();
+        triangles.iter().fold(T::zero(), |sum, triangle| {
+            let area = ring_signed_area(&triangle.map(|i| vertices[i]));
+            assert!(area >= T::zero());
+            sum + area
+        })
+    }
+    fn assert_oriented(result: &MultiPolygon2d<f64>) {
+        for polygon in result.polygons() {
+            assert!(polygon.is_ccw());
+            assert_eq!(&polygon.oriented(), polygon);
+        }
+    }
+
+    #[test]
+    fn area_and_contains_respect_holes() {
+        let polygon = Polygon2d::new(
+            rect(0.0, 0.0, 4.0, 4.0).exterior().to_vec(),
+            vec![rect(1.0, 1.0, 2.0, 2.0).exterior().to_vec()],
+        );
+        assert_close(polygon.area(), 15.0);
+        assert_close(polygon.perimeter(), 20.0);
+        assert!(polygon.contains(&Point2d::new(3.0, 3.0)));
+        assert!(!polygon.contains(&Point2d::new(1.5, 1.5)));
+    }
+    #[test]
+    fn new_drops_repeated_closing_point() {
+        let polygon = Polygon2d::from_exterior(vec![
+            Point2d::new(0.0, 0.0),
+            Point2d::new(0.0, 1.0),
+            Point2d::new(1.0, 0.0),
+            Point2d::new(0.0, 0.0),
+        ]);
+        assert_eq!(polygon.exterior().len(), 3);
+        assert!(!polygon.is_ccw());
+        assert!(polygon.oriented().is_ccw());
+    }
+    #[test]
+    fn overlapping_squares_combine_correctly() {
+        let a = rect(0.0, 0.0, 2.0, 2.0);
+        let b = rect(1.0, 1.0, 3.0, 3.0);
+        let union = a.union(&b);
+        assert_eq!(union.len(), 1);
+        assert_eq!(union.polygons()[0].exterior().len(), 8);
+        assert_close(union.area(), 7.0);
+        assert_close(a.intersection(&b).area(), 1.0);
+        assert_close(a.difference(&b).area(), 3.0);
+        let xor = a.xor(&b);
+        assert_eq!(xor.len(), 2);
+        assert_close(xor.area(), 6.0);
+        assert_oriented(&union);
+        assert_oriented(&xor);
+    }
+    #[test]
+    fn squares_sharing_an_edge_merge_into_one_rectangle() {
+        let a = rect(0.0, 0.0, 1.0, 1.0);
+        let b = rect(1.0, 0.0, 2.0, 1.0);
+        let union = a.union(&b);
+        assert_eq!(union.len(), 1);
+        assert_eq!(union.polygons()[0].exterior().len(), 4);
+        assert_close(union.area(), 2.0);
+        assert!(a.intersection(&b).is_empty());
+        assert_close(a.difference(&b).area(), 1.0);
+    }
+    #[test]
+    fn squares_touching_at_a_corner_stay_separate() {
+        let a = rect(0.0, 0.0, 1.0, 1.0);
+        let b = rect(1.0, 1.0, 2.0, 2.0);
+        let union = a.union(&b);
+        assert_eq!(union.len(), 2);
+        assert_close(union.area(), 2.0);
+        assert!(a.intersection(&b).is_empty());
+    }
+    #[test]
+    fn difference_of_contained_square_creates_hole() {
+        let outer = rect(0.0, 0.0, 4.0, 4.0);
+        let inner = rect(1.0, 1.0, 3.0, 3.0);
+        let result = outer.difference(&inner);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result.polygons()[0].interiors().len(), 1);
+        assert_close(result.area(), 12.0);
+        assert_oriented(&result);
+        assert!(!result.contains(&Point2d::new(2.0, 2.0)));
+        assert!(inner.difference(&outer).is_empty());
+    }
+    #[test]
+    fn identical_polygons_are_degenerate_but_handled() {
+        let a = rect(0.0, 0.0, 1.0, 1.0);
+        assert_close(a.union(&a).area(), 1.0);
+        assert_close(a.intersection(&a).area(), 1.0);
+        assert!(a.difference(&a).is_empty());
+        assert!(a.xor(&a).is_empty());
+    }
+    #[test]
+    fn operations_work_on_polygons_with_holes() {
+        let frame = Polygon2d::new(
+            rect(0.0, 0.0, 4.0, 4.0).exterior().to_vec(),
+            vec![rect(1.0, 1.0, 3.0, 3.0).exterior().to_vec()],
+        );
+        let bar = rect(2.0, -1.0, 5.0, 5.0);
+        let cut = frame.boolean(&bar, BooleanOp::Difference);
+        assert_close(cut.area(), 6.0);
+        let joined = frame.boolean(&bar, BooleanOp::Union);
+        assert_eq!(joined.len(), 1);
+        assert_close(joined.area(), 12.0 + 18.0 - 6.0);
+        assert_eq!(joined.polygons()[0].interiors().len(), 1);
+        assert_oriented(&joined);
+    }
+    #[test]
+    fn triangles_crossing_at_non_grid_points() {
+        let a = Polygon2d::from_exterior(vec![Point2d::new(0.0, 0.0), Point2d::new(3.0, 0.0), Point2d::new(0.0, 3.0)]);
+        let b = Polygon2d::from_exterior(vec![Point2d::new(0.0, 1.0), Point2d::new(3.0, 1.0), Point2d::new(0.1, 2.9)]);
+        let union = a.union(&b).area();
+        let intersection = a.intersection(&b).area();
+        assert_close(union + intersection, a.area() + b.area());
+        assert_close(a.xor(&b).area(), union - intersection);
+    }
+    #[test]
+    fn spikes_crossed_at_non_grid_points() {
+        // The spike runs out and back along the same edge, which the side of `a` crosses at y = -5/6
+        let a = rect(0.0, -2.0, 8.0, 0.0);
+        let spike = [(-1.0, 0.5), (3.0, 0.0), (9.0, -1.0), (3.0, 0.0), (-1.0, -0.5)];
+        let b = Polygon2d::from_exterior(spike.iter().map(|&(x, y)| Point2d::new(x, y)).collect());
+        let union = a.union(&b).area();
+        let intersection = a.intersection(&b).area();
+        assert_close(union + intersection, a.area() + b.area());
+        assert_close(a.difference(&b).area(), a.area() - intersection);
+    }
+
+    #[test]
+    fn large_polygons_overlay_quickly() {
+        // Wavy circles with many short edges, which a quadratic overlay takes minutes on
+        let wavy = |n: usize, cx: f64| {
+            let points = (0..n).map(|i| {
+                let angle = i as f64 / n as f64 * std::f64::consts::TAU;
+                Point2d::new(cx + 8.0 * angle.cos(), 8.0 * angle.sin() + 0.01 * (angle * 997.0).sin())
+            });
+            Polygon2d::from_exterior(points.collect())
+        };
+        let (a, b) = (wavy(20000, 0.0), wavy(20001, 3.0));
+        let union = a.union(&b);
+        let intersection = a.intersection(&b);
+        assert_eq!((union.len(), intersection.len()), (1, 1));
+        assert_close(union.area() + intersection.area(), a.area() + b.area());
+    }
+
+    #[test]
+    fn rings_touching_at_a_vertex_keep_their_holes() {
+        let polygon =
+            |points: &[(f64, f64)]| Polygon2d::from_exterior(points.iter().map(|&(x, y)| Point2d::new(x, y)).collect());
+        // A self-intersecting first ring, whose result has a hole touching its exterior
+        let a = polygon(&[(-0.75, 0.5), (1.0, -1.5), (0.5, -0.5), (2.25, -0.25), (2.0, 0.0), (2.25, 0.0)]);
+        let b = polygon(&[(0.5, 1.25), (0.25, -0.25), (0.5, -1.0), (1.25, 0.5), (1.0, 0.0), (1.75, -0.75), (2.5, 0.0)]);
+        let c = polygon(&[(1.75, 0.0), (2.0, 0.25), (2.25, 0.25), (-0.5, 2.5), (-1.0, -0.5), (0.75, -0.75)]);
+        let d = polygon(&[(3.0, 0.75), (2.0, 1.25), (2.5, 2.5), (0.0, 0.25), (0.5, -0.75)]);
+        for (a, b) in [(a, b), (c.clone(), d.clone())] {
+            let union = a.union(&b);
+            let intersection = a.intersection(&b);
+            let (a_only, b_only) = (a.difference(&b), b.difference(&a));
+            for result in [&union, &intersection, &a_only, &b_only, &a.xor(&b)] {
+                assert_oriented(result);
+                assert!(result.0.iter().all(|polygon| polygon.area() > 0.0), "{result:?}");
+            }
+            assert_close(union.area(), intersection.area() + a_only.area() + b_only.area());
+            assert_close(a.xor(&b).area(), a_only.area() + b_only.area());
+        }
+        // Both rings of the second pair are simple, so their areas add up directly
+        assert_close(c.union(&d).area() + c.intersection(&d).area(), c.area() + d.area());
+        assert_close(c.difference(&d).area() + c.intersection(&d).area(), c.area());
+    }
+
+    #[test]
+    fn offset_square_with_each_join() {
+        let square = rect(0.0, 0.0, 2.0, 2.0);
+        assert_close(square.offset(1.0, JoinType::Miter(2.0)).area(), 16.0);
+        let cut = (2.0_f64.sqrt() - 1.0).powi(2);
+        assert_close(square.offset(1.0, JoinType::Square).area(), 16.0 - 4.0 * cut);
+        // A right-angle miter is sqrt(2) times the distance, so a limit of 1.2 squares it off
+        assert_close(square.offset(1.0, JoinType::Miter(1.2)).area(), 16.0 - 4.0 * cut);
+        let round = square.offset(1.0, JoinType::Round(0.001)).area();
+        let exact = 12.0 + std::f64::consts::PI;
+        assert!(round < exact && round > exact - 0.01, "round join area {}", round);
+        assert_oriented(&square.offset(1.0, JoinType::Round(0.001)));
+    }
+    #[test]
+    fn negative_offset_shrinks_and_can_vanish() {
+        let square = rect(0.0, 0.0, 4.0, 4.0);
+        let shrunk = square.offset(-1.0, JoinType::Miter(2.0));
+        assert_eq!(shrunk.len(), 1);
+        assert_close(shrunk.area(), 4.0);
+        assert!(shrunk.contains(&Point2d::new(2.0, 2.0)));
+        assert!(!shrunk.contains(&Point2d::new(0.5, 2.0)));
+        assert!(square.offset(-3.0, JoinType::Round(0.01)).is_empty());
+    }
+    #[test]
+    fn offset_concave_polygon_removes_loops() {
+        let l_shape = Polygon2d::from_exterior(vec![
+            Point2d::new(0.0, 0.0),
+            Point2d::new(2.0, 0.0),
+            Point2d::new(2.0, 1.0),
+            Point2d::new(1.0, 1.0),
+            Point2d::new(1.0, 2.0),
+            Point2d::new(0.0, 2.0),
+        ]);
+        let grown = l_shape.offset(0.5, JoinType::Miter(2.0));
+        assert_eq!(grown.len(), 1);
+        assert_close(grown.area(), 8.0);
+        assert_oriented(&grown);
+        let shrunk = l_shape.offset(-0.25, JoinType::Miter(2.0));
+        assert_eq!(shrunk.len(), 1);
+        assert_close(shrunk.area(), 1.25);
+    }
+    #[test]
+    fn offset_holes_shrink_as_exterior_grows() {
+        let frame = Polygon2d::new(
+            rect(0.0, 0.0, 4.0, 4.0).exterior().to_vec(),
+            vec![rect(1.0, 1.0, 3.0, 3.0).exterior().to_vec()],
+        );
+        let grown = frame.offset(0.5, JoinType::Miter(2.0));
+        assert_eq!(grown.polygons()[0].interiors().len(), 1);
+        assert_close(grown.area(), 24.0);
+        let filled = frame.offset(1.5, JoinType::Miter(2.0));
+        assert!(filled.polygons()[0].interiors().is_empty());
+        assert_close(filled.area(), 49.0);
+    }
+    #[test]
+    fn offset_merges_polygons_that_grow_together() {
+        let pair = MultiPolygon2d::new(vec![rect(0.0, 0.0, 1.0, 1.0), rect(1.5, 0.0, 2.5, 1.0)]);
+        let grown = pair.offset(0.5, JoinType::Miter(2.0));
+        assert_eq!(grown.len(), 1);
+        assert_close(grown.area(), 7.0);
+    }
+    #[test]
+    fn offset_polyline_with_each_end() {
+        let segment = [Point2d::new(0.0, 0.0), Point2d::new(10.0, 0.0)];
+        assert_close(offset_polyline(&segment, 1.0, JoinType::Square, EndType::Butt).area(), 20.0);
+        assert_close(offset_polyline(&segment, 1.0, JoinType::Square, EndType::Square).area(), 24.0);
+        let round = offset_polyline(&segment, 1.0, JoinType::Square, EndType::Round(0.001)).area();
+        let exact = 20.0 + std::f64::consts::PI;
+        assert!(round < exact && round > exact - 0.01, "round end area {}", round);
+        let corner = [Point2d::new(0.0, 0.0), Point2d::new(4.0, 0.0), Point2d::new(4.0, 4.0)];
+        let bent = offset_polyline(&corner, 1.0, JoinType::Miter(2.0), EndType::Butt);
+        assert_eq!(bent.len(), 1);
+        assert_close(bent.area(), 16.0);
+    }
+    #[test]
+    fn offset_self_crossing_polyline_encloses_hole() {
+        let path = [
+            Point2d::new(0.0, 0.0),
+            Point2d::new(4.0, 0.0),
+            Point2d::new(4.0, 4.0),
+            Point2d::new(2.0, 4.0),
+            Point2d::new(2.0, -2.0),
+        ];
+        let outline = offset_polyline(&path, 0.5, JoinType::Miter(2.0), EndType::Butt);
+        assert_eq!(outline.len(), 1);
+        assert_oriented(&outline);
+        let holes = outline.polygons()[0].interiors();
+        assert_eq!(holes.len(), 1);
+        assert_close(ring_signed_area(&holes[0]), -3.0);
+    }
+    #[test]
+    fn triangulate_concave_polygon() {
+        // A comb with four teeth
+        let mut points = vec![Point2d::new(0.0, 0.0), Point2d::new(8.0, 0.0)];
+        for tooth in (0..4).rev() {
+            let x = tooth as f64 * 2.0;
+            points.extend([Point2d::new(x + 2.0, 3.0), Point2d::new(x + 1.0, 3.0), Point2d::new(x + 1.0, 1.0)]);
+        }
+        points.pop();
+        let comb = Polygon2d::from_exterior(points);
+        let triangles = comb.triangulate();
+        assert_eq!(triangles.len(), comb.exterior().len() - 2);
+        assert_close(triangles_area(&comb, &triangles), comb.area());
+        // Clockwise input gives the same counter-clockwise triangles
+        let reversed = Polygon2d::from_exterior(comb.exterior().iter().rev().copied().collect());
+        assert_close(triangles_area(&reversed, &reversed.triangulate()), comb.area());
+    }
+    #[test]
+    fn triangulate_polygon_with_holes() {
+        let polygon = Polygon2d::new(
+            rect(0.0, 0.0, 10.0, 4.0).exterior().to_vec(),
+            vec![rect(1.0, 1.0, 3.0, 3.0).exterior().to_vec(), rect(6.0, 1.5, 9.0, 3.0).exterior().to_vec()],
+        );
+        let triangles = polygon.triangulate();
+        assert_eq!(polygon.vertices().len(), 12);
+        // Each bridge adds two vertices, so n vertices and h holes give n + 2h - 2 triangles
+        assert_eq!(triangles.len(), 12 + 4 - 2);
+        assert_close(triangles_area(&polygon, &triangles), 40.0 - 4.0 - 4.5);
+    }
+    #[test]
+    fn triangulate_skips_degenerate_points() {
+        let square = Polygon2d::from_exterior(vec![
+            Point2d::new(0.0f32, 0.0),
+            Point2d::new(1.0, 0.0),
+            Point2d::new(2.0, 0.0),
+            Point2d::new(2.0, 2.0),
+            Point2d::new(2.0, 2.0),
+            Point2d::new(0.0, 2.0),
+        ]);
+        let triangles = square.triangulate();
+        assert!((triangles_area(&square, &triangles) - 4.0).abs() < 1e-6);
+        assert!(triangles.iter().all(|triangle| triangle.iter().all(|&i| i < square.vertices().len())));
+        assert!(Polygon2d::from_exterior(vec![Point2d::new(0.0, 0.0), Point2d::new(1.0, 1.0)]).triangulate().is_empty());
+    }
+    #[test]
+    fn triangulate_large_polygon_by_z_order() {
+        // Past 80 vertices ears are looked up along a z-order curve
+        let mut state = 11u64;
+        let mut next = move || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 53) as f64
+        };
+        let mut star = |count: usize, radius: f64, spikes: f64| -> Vec<Point2d<f64>> {
+            (0..count)
+                .map(|i| {
+                    let (angle, r) = (i as f64 * std::f64::consts::TAU / count as f64, radius + spikes * next());
+                    Point2d::new(r * angle.cos(), r * angle.sin())
+                })
+                .collect()
+        };
+        let (exterior, hole) = (star(2000, 50.0, 50.0), star(300, 10.0, 20.0));
+        let polygon = Polygon2d::new(exterior, vec![hole]);
+        let triangles = polygon.triangulate();
+        assert_eq!(triangles.len(), 2300 + 2 - 2);
+        assert!((triangles_area(&polygon, &triangles) - polygon.area()).abs() < 1e-9 * polygon.area());
+    }
+}
@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use point2d::Point2d;
+    use predicates2d::{incircle, orient2d, orientation, segments_intersect, Orientation};
+
+    #[test]
+    fn orientation_classifies_turns() {
+        let a = Point2d::new(0.0, 0.0);
+        let b = Point2d::new(1.0, 0.0);
+        assert_eq!(orientation(&a, &b, &Point2d::new(0.0, 1.0)), Orientation::CounterClockwise);
+        assert_eq!(orientation(&a, &b, &Point2d::new(0.0, -1.0)), Orientation::Clockwise);
+        assert_eq!(orientation(&a, &b, &Point2d::new(7.0, 0.0)), Orientation::Collinear);
+    }
+    #[test]
+    fn orient2d_is_exact_for_nearly_collinear_points() {
+        // Naive evaluation of these points gives the wrong sign or zero
+        let a = Point2d::new(0.5, 0.5);
+        let b = Point2d::new(12.0, 12.0);
+        let c = Point2d::new(24.0, 24.0);
+        assert_eq!(orient2d(&a, &b, &c), 0.0);
+        let d = Point2d::new(0.5 + f64::EPSILON * 4.0, 0.5);
+        assert!(orient2d(&d, &b, &c) < 0.0);
+        let e = Point2d::new(0.5, 0.5 + f64::EPSILON * 4.0);
+        assert!(orient2d(&e, &b, &c) > 0.0);
+    }
+    #[test]
+    fn orient2d_is_antisymmetric_on_tiny_perturbations() {
+        let base = Point2d::new(1.0, 1.0);
+        let far = Point2d::new(1e15, 1e15);
+        let mut x: f64 = 2.0;
+        for i in 0..16 {
+            let p = Point2d::new(x, 2.0);
+            x = x.next_up();
+            let forward = orient2d(&base, &far, &p);
+            let backward = orient2d(&far, &base, &p);
+            assert_eq!(forward > 0.0, backward < 0.0);
+            assert_eq!(forward == 0.0, i == 0);
+        }
+    }
+    #[test]
+    fn segments_intersect_handles_touching_and_overlapping_segments() {
+        let p = |x: f64, y: f64| Point2d::new(x, y);
+        assert!(segments_intersect(&p(0.0, 0.0), &p(2.0, 2.0), &p(0.0, 2.0), &p(2.0, 0.0)));
+        assert!(segments_intersect(&p(0.0, 0.0), &p(2.0, 0.0), &p(2.0, 0.0), &p(3.0, 5.0)));
+        assert!(segments_intersect(&p(0.0, 0.0), &p(2.0, 0.0), &p(1.0, 0.0), &p(3.0, 0.0)));
+        assert!(!segments_intersect(&p(0.0, 0.0), &p(1.0, 0.0), &p(2.0, 0.0), &p(3.0, 0.0)));
+        assert!(!segments_intersect(&p(0.0, 0.0), &p(1.0, 1.0), &p(0.0, 1.0), &p(0.4, 0.6)));
+    }
+    #[test]
+    fn incircle_is_exact_for_cocircular_points() {
+        let a = Point2d::new(1.0, 0.0);
+        let b = Point2d::new(0.0, 1.0);
+        let c = Point2d::new(-1.0, 0.0);
+        assert!(incircle(&a, &b, &c, &Point2d::new(0.0, 0.0)) > 0.0);
+        assert!(incircle(&a, &b, &c, &Point2d::new(2.0, 0.0)) < 0.0);
+        assert_eq!(incircle(&a, &b, &c, &Point2d::new(0.0, -1.0)), 0.0);
+        // Shifted far from the origin the differences round, but the sign stays exact
+        let shift = |p: &Point2d<f64>| Point2d::new(p.x() + 1e9, p.y() + 1e9);
+        assert_eq!(incircle(&shift(&a), &shift(&b), &shift(&c), &shift(&Point2d::new(0.0, -1.0))), 0.0);
+        let inside = Point2d::new(1e9, (1e9_f64 - 1.0).next_up());
+        assert!(incircle(&shift(&a), &shift(&b), &shift(&c), &inside) > 0.0);
+    }
+    #[test]
+    fn integer_orientation_and_area_are_exact_at_large_coordinates() {
+        use predicates2d::integer::{doubled_area, is_collinear, orient2d, orientation, MAX_COORDINATE};
+        let m = MAX_COORDINATE;
+        let (a, b) = (Point2d::new(-m, -m), Point2d::new(m, m));
+        assert!(is_collinear(&a, &b, &Point2d::new(0, 0)));
+        // One unit off the diagonal is still detected at the extremes of the range
+        assert_eq!(orientation(&a, &b, &Point2d::new(m - 1, m)), Orientation::CounterClockwise);
+        assert_eq!(orientation(&a, &b, &Point2d::new(m, m - 1)), Orientation::Clockwise);
+        assert_eq!(orient2d(&a, &Point2d::new(m, -m), &b), (2 * m as i128).pow(2));
+        let square = [Point2d::new(-m, -m), Point2d::new(m, -m), Point2d::new(m, m), Point2d::new(-m, m)];
+        assert_eq!(doubled_area(&square), 2 * (2 * m as i128).pow(2));
+        let clockwise: Vec<Point2d<i64>> = square.iter().rev().copied().collect();
+        assert_eq!(doubled_area(&clockwise), -doubled_area(&square));
+    }
+    #[test]
+    fn integer_segment_intersection_is_rational() {
+        use predicates2d::integer::{segment_intersection, segments_intersect, RationalPoint2d};
+        let p = |x: i64, y: i64| Point2d::new(x, y);
+        let hit = segment_intersection(&p(0, 0), &p(3, 1), &p(0, 1), &p(3, 0)).unwrap().unwrap();
+        assert_eq!(hit, RationalPoint2d::new(3, 1, 2).unwrap());
+        assert_eq!(hit.to_f64(), Point2d::new(1.5, 0.5));
+        assert_eq!(hit.to_point(), None);
+        // Touching end to end gives the shared end point; an overlapping stretch gives none
+        let touch = segment_intersection(&p(0, 0), &p(2, 0), &p(2, 0), &p(5, 0)).unwrap().unwrap();
+        assert_eq!(touch.to_point(), Some(p(2, 0)));
+        assert_eq!(segment_intersection(&p(0, 0), &p(2, 0), &p(1, 0), &p(5, 0)), Ok(None));
+        assert!(segments_intersect(&p(0, 0), &p(2, 0), &p(1, 0), &p(5, 0)));
+        assert_eq!(segment_intersection(&p(0, 0), &p(1, 1), &p(0, 1), &p(0, 2)), Ok(None));
+        // Nanometre coordinates a metre apart keep their exact intersection
+        let hit = segment_intersection(&p(0, 0), &p(1_000_000_001, 1_000_000_000), &p(0, 7), &p(1_000_000_000, 3));
+        let RationalPoint2d { x, y, denominator } = hit.unwrap().unwrap();
+        assert_eq!(1_000_000_001 * y - 1_000_000_000 * x, 0);
+        assert_eq!(1_000_000_000 * (y - 7 * denominator) + 4 * x, 0);
+    }
+}
@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use point2d::Point2d;
+    use rect2d::{Anchor, Rect2d, RotatedRect2d};
+    use vector2d::{Size2d, Vector2d};
+
+    fn assert_point_close(actual: &Point2d<f64>, expected: &Point2d<f64>) {
+        assert!(actual.distance_to(expected) < 1e-12, "{actual:?} is not close to {expected:?}");
+    }
+    fn polygon_area(points: &[Point2d<f64>]) -> f64 {
+        let n = points.len();
+        (0..n).map(|i| points[i].x() * points[(i + 1) % n].y() - points[(i + 1) % n].x() * points[i].y()).sum::<f64>() / 2.0
+    }
+    #[test]
+    fn corners_anchors_and_vectors() {
+        let rect = Rect2d::from_points(&Point2d::new(6.0, 1.0), &Point2d::new(2.0, 4.0));
+        assert_eq!(rect, Rect2d::new(Point2d::new(2.0, 1.0), 4.0, 3.0));
+        assert_eq!((rect.max(), rect.center(), rect.area()), (Point2d::new(6.0, 4.0), Point2d::new(4.0, 2.5), 12.0));
+        let corners = [Point2d::new(2.0, 1.0), Point2d::new(6.0, 1.0), Point2d::new(6.0, 4.0), Point2d::new(2.0, 4.0)];
+        assert_eq!(rect.corners(), corners);
+        assert_eq!(rect.anchor(Anchor::Top), Point2d::new(4.0, 1.0));
+        assert_eq!(rect.anchor(Anchor::BottomRight), rect.max());
+        assert_eq!(Rect2d::from_center(&rect.center(), 4.0, 3.0), rect);
+        let (origin, size) = rect.to_vectors();
+        assert_eq!(size, Vector2d::new(4.0, 3.0));
+        assert_eq!(Rect2d::from_vectors(origin, size), rect);
+        assert_eq!(Rect2d::from_size(rect.origin, rect.size()), rect);
+        assert_eq!(rect.size(), Size2d::from_vector(size));
+        assert!(Rect2d::new(Point2d::new(0.0, 0.0), 0.0, 5.0).is_empty());
+        assert_eq!(Rect2d::new(Point2d::new(0.0, 0.0), -2.0, 5.0).area(), 0.0);
+    }
+    #[test]
+    fn intersection_union_and_containment() {
+        let a = Rect2d::new(Point2d::new(0.0, 0.0), 4.0, 4.0);
+        let b = Rect2d::new(Point2d::new(2.0, 3.0), 5.0, 2.0);
+        assert_eq!(a.intersection(&b), Some(Rect2d::new(Point2d::new(2.0, 3.0), 2.0, 1.0)));
+        assert_eq!(a.union(&b), Rect2d::new(Point2d::new(0.0, 0.0), 7.0, 5.0));
+        assert!(a.union(&b).contains_rect(&a) && a.union(&b).contains_rect(&b) && !a.contains_rect(&b));
+        // Rectangles sharing only an edge or a corner do not intersect
+        let beside = Rect2d::new(Point2d::new(4.0, 1.0), 2.0, 2.0);
+        let diagonal = Rect2d::new(Point2d::new(4.0, 4.0), 1.0, 1.0);
+        assert!(!a.intersects(&beside) && !a.intersects(&diagonal));
+        assert!(a.contains(&Point2d::new(4.0, 2.0)) && !a.contains(&Point2d::new(4.0 + 1e-12, 2.0)));
+    }
+    #[test]
+    fn layout_helpers() {
+        let rect = Rect2d::new(Point2d::new(10.0, 20.0), 100.0, 50.0);
+        assert_eq!(rect.inset(5.0, 10.0), Rect2d::new(Point2d::new(15.0, 30.0), 90.0, 30.0));
+        assert_eq!(rect.inset(5.0, 10.0).outset(5.0, 10.0), rect);
+        // Insetting past the middle collapses to the center
+        assert_eq!(rect.inset(60.0, 0.0), Rect2d::new(Point2d::new(60.0, 20.0), 0.0, 50.0));
+        let label = Rect2d::new(Point2d::new(0.0, 0.0), 20.0, 10.0);
+        assert_eq!(label.align_within(&rect, Anchor::Center).center(), rect.center());
+        assert_eq!(label.align_within(&rect, Anchor::BottomRight).max(), rect.max());
+        assert_eq!(label.align_within(&rect, Anchor::Top).origin, Point2d::new(50.0, 20.0));
+        let grid = rect.grid(2, 3, 5.0);
+        assert_eq!(grid.len(), 6);
+        assert_eq!(grid[0], Rect2d::new(Point2d::new(10.0, 20.0), 30.0, 22.5));
+        assert_eq!(grid[4], Rect2d::new(Point2d::new(45.0, 47.5), 30.0, 22.5));
+        assert_eq!(grid[5].max(), rect.max());
+    }
+    #[test]
+    fn rotated_rectangles() {
+        let rect = Rect2d::new(Point2d::new(-2.0, -1.0), 4.0, 2.0);
+        let turned = RotatedRect2d::from_rect(&rect, PI / 2.0);
+        let expected = [Point2d::new(1.0, -2.0), Point2d::new(1.0, 2.0), Point2d::new(-1.0, 2.0), Point2d::new(-1.0, -2.0)];
+        for (corner, expected) in turned.corners().iter().zip(&expected) {
+            assert_point_close(corner, expected);
+        }
+        assert_point_close(&turned.anchor(Anchor::Right), &Point2d::new(0.0, 2.0));
+        assert!(turned.contains(&Point2d::new(0.9, 1.9)) && !turned.contains(&Point2d::new(1.5, 0.0)));
+        let bounds = turned.bounding_rect();
+        assert_point_close(&bounds.origin, &Point2d::new(-1.0, -2.0));
+        assert!((bounds.width - 2.0).abs() < 1e-12 && (bounds.height - 4.0).abs() < 1e-12);
+        // A cross of the rectangle and its quarter turn overlaps in a 2 by 2 square
+        let cross = RotatedRect2d::from_rect(&rect, 0.0).intersection(&turned);
+        assert!((polygon_area(&cross) - 4.0).abs() < 1e-12);
+        // A square turned by 45 degrees over itself overlaps in a regular octagon
+        let square = RotatedRect2d::new(Point2d::new(0.0, 0.0), 2.0, 2.0, 0.0);
+        let diamond = RotatedRect2d::new(Point2d::new(0.0, 0.0), 2.0, 2.0, PI / 4.0);
+        let octagon = square.intersection(&diamond);
+        assert_eq!(octagon.len(), 8);
+        assert!((polygon_area(&octagon) - 8.0 * (2.0f64.sqrt() - 1.0)).abs() < 1e-12);
+        let far = RotatedRect2d::new(Point2d::new(10.0, 0.0), 2.0, 2.0, 0.3);
+        assert!(square.intersection(&far).is_empty());
+        // Grid cells turn with the rectangle and tile it
+        let cells = diamond.grid(2, 2, 0.0);
+        assert!(cells.iter().all(|cell| cell.rotation == diamond.rotation && (cell.area() - 1.0).abs() < 1e-12));
+        assert_point_close(&cells[0].anchor(Anchor::TopLeft), &diamond.anchor(Anchor::TopLeft));
+        assert_point_close(&cells[3].anchor(Anchor::BottomRight), &diamond.anchor(Anchor::BottomRight));
+        assert_eq!(diamond.inset(0.5, 0.5).area(), 1.0);
+    }
+}
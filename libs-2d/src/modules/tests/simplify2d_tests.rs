@@ -0,0 +1,172 @@
+#[cfg(test)]
+mod tests {
+    use point2d::Point2d;
+    use simplify2d::{douglas_peucker, douglas_peucker_preserve_topology, visvalingam_whyatt, visvalingam_whyatt_preserve_topology};
+
+    fn random_walk(count: usize, seed: u64) -> Vec<Point2d<f64>> {
+        let mut state = seed;
+        let mut next = move || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 53) as f64
+        };
+        let (mut x, mut y, mut heading) = (0.0, 0.0, 0.0);
+        (0..count)
+            .map(|_| {
+                heading += next() - 0.5;
+                x += heading.cos();
+                y += heading.sin();
+                Point2d::new(x, y)
+            })
+            .collect()
+    }
+    fn segment_distance(p: &Point2d<f64>, a: &Point2d<f64>, b: &Point2d<f64>) -> f64 {
+        let (dx, dy) = (b.x() - a.x(), b.y() - a.y());
+        let t = (((p.x() - a.x()) * dx + (p.y() - a.y()) * dy) / (dx * dx + dy * dy)).clamp(0.0, 1.0);
+        p.distance_to(&Point2d::new(a.x() + dx * t, a.y() + dy * t))
+    }
+    fn cross(a: &Point2d<f64>, b: &Point2d<f64>, c: &Point2d<f64>) -> f64 {
+        (b.x() - a.x()) * (c.y() - a.y()) - (b.y() - a.y()) * (c.x() - a.x())
+    }
+    /// Counts pairs of non-adjacent segments of the kept polyline that cross.
+    fn crossings(points: &[Point2d<f64>], kept: &[usize]) -> usize {
+        let segments: Vec<[Point2d<f64>; 2]> = kept.windows(2).map(|w| [points[w[0]], points[w[1]]]).collect();
+        let mut count = 0;
+        for k in 0..segments.len() {
+            for l in k + 2..segments.len() {
+                let ([a, b], [c, d]) = (segments[k], segments[l]);
+                if cross(&a, &b, &c) * cross(&a, &b, &d) < 0.0 && cross(&c, &d, &a) * cross(&c, &d, &b) < 0.0 {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    #[test]
+    fn short_inputs_are_kept() {
+        let points = [Point2d::new(0.0, 0.0), Point2d::new(1.0, 1.0)];
+        assert_eq!(douglas_peucker(&points, 10.0), vec![0, 1]);
+        assert_eq!(visvalingam_whyatt(&points[..1], 10.0), vec![0]);
+        assert!(douglas_peucker::<f64>(&[], 1.0).is_empty());
+    }
+    #[test]
+    fn douglas_peucker_keeps_corners_of_a_noisy_path() {
+        let points: Vec<Point2d<f64>> = (0..=20)
+            .map(|i| {
+                let noise = if i % 2 == 0 { 0.01 } else { -0.01 };
+                if i <= 10 { Point2d::new(i as f64, noise) } else { Point2d::new(10.0 + noise, (i - 10) as f64) }
+            })
+            .collect();
+        assert_eq!(douglas_peucker(&points, 0.1), vec![0, 10, 20]);
+        assert_eq!(douglas_peucker(&points, 0.001).len(), points.len());
+    }
+    #[test]
+    fn douglas_peucker_stays_within_tolerance() {
+        let points = random_walk(5000, 7);
+        let kept = douglas_peucker(&points, 2.0);
+        assert_eq!((kept[0], kept[kept.len() - 1]), (0, points.len() - 1));
+        assert!(kept.len() < points.len() / 4);
+        for w in kept.windows(2) {
+            assert!(w[0] < w[1]);
+            for p in &points[w[0] + 1..w[1]] {
+                assert!(segment_distance(p, &points[w[0]], &points[w[1]]) <= 2.0);
+            }
+        }
+    }
+    #[test]
+    fn visvalingam_whyatt_removes_small_triangles_first() {
+        // The bumps at 1 and 3 have areas 0.2 and 1; the corner at 4 spans a large triangle
+        let points = [
+            Point2d::new(0.0, 0.0),
+            Point2d::new(1.0, 0.2),
+            Point2d::new(2.0, 0.0),
+            Point2d::new(3.0, 1.0),
+            Point2d::new(4.0, 0.0),
+            Point2d::new(4.0, 4.0),
+        ];
+        assert_eq!(visvalingam_whyatt(&points, 0.5), vec![0, 2, 3, 4, 5]);
+        assert_eq!(visvalingam_whyatt(&points, 1.5), vec![0, 3, 4, 5]);
+        assert_eq!(visvalingam_whyatt(&points, 100.0), vec![0, 5]);
+        let walk = random_walk(5000, 3);
+        let kept = visvalingam_whyatt(&walk, 4.0);
+        for w in kept.windows(3) {
+            assert!(cross(&walk[w[0]], &walk[w[1]], &walk[w[2]]).abs() / 2.0 >= 4.0);
+        }
+    }
+    #[test]
+    fn preserving_topology_avoids_new_crossings() {
+        // The path later dips under the bump's apex, which the bump's chord would cut through
+        let points = [
+            Point2d::new(0.0, 0.0),
+            Point2d::new(20.0, 1.0),
+            Point2d::new(40.0, 0.0),
+            Point2d::new(40.0, -20.0),
+            Point2d::new(25.0, -20.0),
+            Point2d::new(20.0, 0.5),
+            Point2d::new(15.0, -20.0),
+            Point2d::new(0.0, -20.0),
+        ];
+        assert_eq!(crossings(&points, &(0..points.len()).collect::<Vec<_>>()), 0);
+        assert!(crossings(&points, &douglas_peucker(&points, 2.0)) > 0);
+        assert!(crossings(&points, &visvalingam_whyatt(&points, 30.0)) > 0);
+        let kept = douglas_peucker_preserve_topology(&points, 2.0);
+        assert_eq!(crossings(&points, &kept), 0);
+        assert!(kept.contains(&1));
+        assert_eq!(crossings(&points, &visvalingam_whyatt_preserve_topology(&points, 30.0)), 0);
+    }
+    #[test]
+    fn preserving_topology_on_long_walks() {
+        let points = random_walk(3000, 5);
+        let own = crossings(&points, &(0..points.len()).collect::<Vec<_>>());
+        assert!(own > 0);
+        let runs = [
+            (douglas_peucker_preserve_topology(&points, 3.0), douglas_peucker(&points, 3.0)),
+            (visvalingam_whyatt_preserve_topology(&points, 9.0), visvalingam_whyatt(&points, 9.0)),
+        ];
+        for (kept, plain) in &runs {
+            // Crossings of the walk itself survive, but none are added
+            assert!(crossings(&points, kept) <= own);
+            // and the walk's own crossings cost no points around them
+            assert!(kept.len() * 5 < plain.len() * 6, "{} points kept, {} without topology", kept.len(), plain.len());
+        }
+    }
+    #[test]
+    fn preserving_topology_on_a_lap_trace() {
+        // A long drive to a track and 20 laps around it, each lap wobbling across the others; the
+        // drive spans far more than the laps, whose short segments must still be told apart
+        let (approach, count) = (1000, 200_000);
+        let mut points: Vec<Point2d<f64>> = (0..approach)
+            .map(|i| {
+                let away = 10.0 * (approach - i) as f64;
+                Point2d::new(100.0 - away, -away)
+            })
+            .collect();
+        points.extend((0..count).map(|i| {
+            let angle = i as f64 * 20.0 * std::f64::consts::TAU / count as f64;
+            let radius = 100.0 + (3.0 * angle + (i * 20 / count) as f64).sin();
+            Point2d::new(radius * angle.cos(), radius * angle.sin())
+        }));
+        let plain = douglas_peucker(&points, 0.01);
+        let kept = douglas_peucker_preserve_topology(&points, 0.01);
+        assert!(plain.iter().all(|i| kept.binary_search(i).is_ok()));
+        assert!(kept.len() < points.len() / 4, "{} of {} points kept", kept.len(), points.len());
+        assert_eq!((kept[0], kept[kept.len() - 1]), (0, points.len() - 1));
+    }
+    #[test]
+    fn closed_ring_keeps_its_shape() {
+        let ring: Vec<Point2d<f64>> = (0..=64)
+            .map(|i| {
+                let angle = i as f64 * std::f64::consts::TAU / 64.0;
+                Point2d::new(angle.cos() * 10.0, angle.sin() * 10.0)
+            })
+            .collect();
+        let kept = douglas_peucker_preserve_topology(&ring, 0.5);
+        assert_eq!((kept[0], kept[kept.len() - 1]), (0, 64));
+        assert!(kept.len() > 4 && kept.len() < 20);
+        for w in kept.windows(2) {
+            for p in &ring[w[0] + 1..w[1]] {
+                assert!(segment_distance(p, &ring[w[0]], &ring[w[1]]) <= 0.5);
+            }
+        }
+    }
+}
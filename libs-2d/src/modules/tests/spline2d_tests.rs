@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod tests {
+    use point2d::Point2d;
+    use spline2d::{BSpline2d, CatmullRom2d, Parameterization};
+
+    fn samples() -> Vec<Point2d<f64>> {
+        vec![
+            Point2d::new(0.0, 0.0),
+            Point2d::new(1.0, 2.0),
+            Point2d::new(3.0, 2.5),
+            Point2d::new(4.0, 0.5),
+            Point2d::new(6.0, 1.0),
+        ]
+    }
+
+    #[test]
+    fn catmull_rom_passes_through_every_point() {
+        for parameterization in [Parameterization::Uniform, Parameterization::Centripetal, Parameterization::Chordal] {
+            let spline = CatmullRom2d::new(&samples(), parameterization).unwrap();
+            for (i, point) in samples().iter().enumerate() {
+                assert!(spline.point_at(i as f64).distance_to(point) < 1e-12);
+            }
+        }
+    }
+    #[test]
+    fn catmull_rom_uniform_tangent_is_half_the_neighbour_difference() {
+        let spline = CatmullRom2d::new(&samples(), Parameterization::Uniform).unwrap();
+        let tangent = spline.tangent_at(2.0);
+        assert!((tangent.x - 1.5).abs() < 1e-12);
+        assert!((tangent.y + 0.75).abs() < 1e-12);
+    }
+    #[test]
+    fn catmull_rom_requires_two_points() {
+        assert!(CatmullRom2d::new(&[Point2d::new(1.0, 1.0)], Parameterization::Centripetal).is_none());
+    }
+    #[test]
+    fn catmull_rom_beziers_match_the_curve() {
+        let spline = CatmullRom2d::new(&samples(), Parameterization::Centripetal).unwrap();
+        let beziers = spline.to_cubic_beziers();
+        assert_eq!(beziers.len(), 4);
+        assert_eq!(beziers[1][0], samples()[1]);
+        assert_eq!(beziers[1][3], samples()[2]);
+    }
+    #[test]
+    fn uniform_bspline_is_clamped_to_end_points() {
+        let spline = BSpline2d::uniform(&samples()).unwrap();
+        assert_eq!(spline.knots(), &[0.0, 0.0, 0.0, 0.0, 0.5, 1.0, 1.0, 1.0, 1.0]);
+        assert!(spline.point_at(0.0).distance_to(&samples()[0]) < 1e-12);
+        assert!(spline.point_at(1.0).distance_to(&samples()[4]) < 1e-12);
+        // The start tangent points along the first leg of the control polygon
+        let tangent = spline.tangent_at(0.0);
+        assert!((tangent.x * 2.0 - tangent.y).abs() < 1e-9);
+    }
+    #[test]
+    fn bspline_interpolation_passes_through_points() {
+        let points = samples();
+        let spline = BSpline2d::interpolate(&points).unwrap();
+        let (start, end) = spline.parameter_range();
+        assert_eq!((start, end), (0.0, 1.0));
+        let mut total = 0.0;
+        let lengths: Vec<f64> = points.windows(2).map(|pair| pair[0].distance_to(&pair[1])).collect();
+        let sum: f64 = lengths.iter().sum();
+        assert!(spline.point_at(0.0).distance_to(&points[0]) < 1e-9);
+        for (i, length) in lengths.iter().enumerate() {
+            total += length;
+            let point = spline.point_at(total / sum);
+            assert!(point.distance_to(&points[i + 1]) < 1e-9, "point {} was {:?}", i + 1, point);
+        }
+    }
+    #[test]
+    fn bspline_beziers_match_the_curve() {
+        let spline = BSpline2d::with_knots(&samples(), &[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]).unwrap();
+        let beziers = spline.to_cubic_beziers();
+        assert_eq!(beziers.len(), 2);
+        assert!(beziers[0][0].distance_to(&spline.point_at(3.0)) < 1e-12);
+        assert!(beziers[1][0].distance_to(&spline.point_at(4.0)) < 1e-12);
+        assert!(beziers[1][3].distance_to(&spline.point_at(5.0)) < 1e-12);
+        // An unclamped uniform cubic B-spline starts at (P0 + 4 P1 + P2) / 6
+        let expected = Point2d::new((0.0 + 4.0 + 3.0) / 6.0, (0.0 + 8.0 + 2.5) / 6.0);
+        assert!(beziers[0][0].distance_to(&expected) < 1e-12);
+    }
+    #[test]
+    fn bspline_rejects_invalid_knots() {
+        assert!(BSpline2d::with_knots(&samples(), &[0.0, 1.0, 2.0]).is_none());
+        assert!(BSpline2d::with_knots(&samples(), &[0.0, 1.0, 2.0, 3.0, 2.0, 5.0, 6.0, 7.0, 8.0]).is_none());
+        assert!(BSpline2d::uniform(&samples()[..3]).is_none());
+    }
+}
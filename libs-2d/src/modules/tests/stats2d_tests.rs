@@ -0,0 +1,192 @@
+#[cfg(test)]
+mod tests {
+    use point2d::Point2d;
+    use stats2d::{
+        centroid, covariance, fit_circle_geometric, fit_circle_kasa, fit_circle_pratt, fit_circle_taubin, fit_ellipse,
+        fit_line, huber_line_fit, mean, principal_axes, ransac_line_fit, weighted_centroid,
+    };
+    use vector2d::Vector2d;
+
+    fn random_unit(count: usize, seed: u64) -> Vec<f64> {
+        let mut state = seed;
+        (0..count)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                (state >> 11) as f64 / (1u64 << 53) as f64
+            })
+            .collect()
+    }
+    /// Points along `y = 0.5 x + 2` for x in 0..40, offset across the line by up to `noise`.
+    fn noisy_line(count: usize, noise: f64, seed: u64) -> Vec<Point2d<f64>> {
+        let values = random_unit(2 * count, seed);
+        let normal = (-0.5 / 1.25f64.sqrt(), 1.0 / 1.25f64.sqrt());
+        (0..count)
+            .map(|i| {
+                let x = values[2 * i] * 40.0;
+                let offset = (values[2 * i + 1] - 0.5) * 2.0 * noise;
+                Point2d::new(x + normal.0 * offset, 0.5 * x + 2.0 + normal.1 * offset)
+            })
+            .collect()
+    }
+    /// The perpendicular distance from `(x, y)` to `y = 0.5 x + 2`.
+    fn distance_to_truth(x: f64, y: f64) -> f64 {
+        (0.5 * x - y + 2.0).abs() / 1.25f64.sqrt()
+    }
+    #[test]
+    fn centroids_and_covariance_match_direct_sums() {
+        let points = [Point2d::new(0.0, 0.0), Point2d::new(4.0, 0.0), Point2d::new(4.0, 2.0), Point2d::new(0.0, 2.0)];
+        assert_eq!(centroid(&points), Some(Point2d::new(2.0, 1.0)));
+        let vectors: Vec<Vector2d<f64>> = points.iter().map(|p| p.0).collect();
+        assert_eq!(mean(&vectors), Some(Vector2d::new(2.0, 1.0)));
+        assert_eq!(centroid::<f64, Point2d<f64>>(&[]), None);
+        let weighted = weighted_centroid(&points, &[3.0, 1.0, 0.0, 0.0]).unwrap();
+        assert_eq!(weighted, Point2d::new(1.0, 0.0));
+        assert_eq!(weighted_centroid(&points, &[0.0; 4]), None);
+        assert_eq!(weighted_centroid(&points, &[1.0; 3]), None);
+        let c = covariance(&vectors).unwrap();
+        assert_eq!((c.m11, c.m12, c.m21, c.m22), (4.0, 0.0, 0.0, 1.0));
+        // Far from the origin, E[x^2] - E[x]^2 would lose every digit
+        let shifted: Vec<Point2d<f64>> = points.iter().map(|p| Point2d::new(p.x() + 1e9, p.y() - 1e9)).collect();
+        assert_eq!(covariance(&shifted), Some(c));
+    }
+    #[test]
+    fn principal_axes_follow_the_spread() {
+        let angle: f64 = 0.6;
+        let (cos, sin) = (angle.cos(), angle.sin());
+        let values = random_unit(2000, 3);
+        let points: Vec<Point2d<f64>> = values
+            .chunks(2)
+            .map(|v| {
+                let (u, w) = ((v[0] - 0.5) * 30.0, (v[1] - 0.5) * 3.0);
+                Point2d::new(5.0 + u * cos - w * sin, -1.0 + u * sin + w * cos)
+            })
+            .collect();
+        let axes = principal_axes(&points).unwrap();
+        // The axis may point either way along the line
+        assert!((axes.major.x * sin - axes.major.y * cos).abs() < 0.02);
+        assert!((axes.major.x * axes.minor.x + axes.major.y * axes.minor.y).abs() < 1e-12);
+        // A uniform spread of width w has variance w^2 / 12
+        assert!((axes.variances[0] / 75.0 - 1.0).abs() < 0.1 && (axes.variances[1] / 0.75 - 1.0).abs() < 0.1);
+        assert!(axes.centroid.distance_to(&Point2d::new(5.0, -1.0)) < 0.5);
+    }
+    #[test]
+    fn total_least_squares_fits_lines_in_any_direction() {
+        let points = noisy_line(200, 0.2, 5);
+        let line = fit_line(&points).unwrap();
+        for end in [line.pt1, line.pt2] {
+            assert!(distance_to_truth(end.x(), end.y()) < 0.05);
+        }
+        // The segment spans the data, whose x runs over 0..40
+        assert!(line.length() > 38.0 && line.length() < 45.0);
+        // Ordinary regression of y on x fails on a vertical line; total least squares does not
+        let vertical: Vec<Point2d<f64>> = (0..10).map(|i| Point2d::new(3.0, i as f64)).collect();
+        let line = fit_line(&vertical).unwrap();
+        assert!((line.x1() - 3.0).abs() < 1e-12 && (line.x2() - 3.0).abs() < 1e-12 && (line.length() - 9.0).abs() < 1e-12);
+        assert!(fit_line(&[Point2d::new(1.0, 1.0); 5]).is_none());
+    }
+    #[test]
+    fn robust_fits_ignore_outliers() {
+        let mut points = noisy_line(150, 0.1, 7);
+        let junk = random_unit(100, 8);
+        points.extend(junk.chunks(2).map(|v| Point2d::new(v[0] * 40.0, v[1] * 60.0 - 10.0)));
+        let plain = fit_line(&points).unwrap();
+        let ransac = ransac_line_fit(&points, 0.3, 200, 1).unwrap();
+        let huber = huber_line_fit(&points, 0.3, 50).unwrap();
+        let error = |line: &line2d::Line2d<f64>| {
+            distance_to_truth(line.x1(), line.y1()).max(distance_to_truth(line.x2(), line.y2()))
+        };
+        assert!(error(&plain) > 1.0);
+        assert!(error(&ransac.line) < 0.1 && error(&huber.line) < 0.3);
+        assert_eq!(ransac.residuals.len(), points.len());
+        assert!((0..150).all(|i| ransac.inliers.contains(&i)));
+        assert!(ransac.inliers.len() < 165);
+        assert!(ransac.inliers.iter().all(|&i| ransac.residuals[i].abs() <= 0.3));
+        assert_eq!(ransac_line_fit(&points, 0.3, 200, 1), Some(ransac));
+        assert!(ransac_line_fit(&points[..1], 0.3, 200, 1).is_none());
+    }
+    /// Points on the ellipse with center (12, -4), semi-axes 9 and 5 and the major axis at
+    /// 0.5 radians, at parameters from `start` to `end`, moved along the normal by up to `noise`.
+    fn noisy_ellipse(count: usize, start: f64, end: f64, noise: f64, seed: u64) -> Vec<Point2d<f64>> {
+        let values = random_unit(count, seed);
+        let (cos, sin) = (0.5f64.cos(), 0.5f64.sin());
+        (0..count)
+            .map(|i| {
+                let t = start + (end - start) * i as f64 / (count - 1) as f64;
+                let (u, v) = (9.0 * t.cos(), 5.0 * t.sin());
+                let (nu, nv) = (5.0 * t.cos(), 9.0 * t.sin());
+                let offset = (values[i] - 0.5) * 2.0 * noise / nu.hypot(nv);
+                let (u, v) = (u + nu * offset, v + nv * offset);
+                Point2d::new(12.0 + u * cos - v * sin, -4.0 + u * sin + v * cos)
+            })
+            .collect()
+    }
+    /// Points on the circle of radius 5 about (3, -2) at angles from `start` to `end`, moved
+    /// radially by up to `noise`.
+    fn noisy_arc(count: usize, start: f64, end: f64, noise: f64, seed: u64) -> Vec<Point2d<f64>> {
+        let values = random_unit(count, seed);
+        (0..count)
+            .map(|i| {
+                let angle = start + (end - start) * i as f64 / (count - 1) as f64;
+                let radius = 5.0 + (values[i] - 0.5) * 2.0 * noise;
+                Point2d::new(3.0 + radius * angle.cos(), -2.0 + radius * angle.sin())
+            })
+            .collect()
+    }
+    #[test]
+    fn circle_fits_are_exact_on_exact_points() {
+        let points = noisy_arc(12, 0.3, 2.0, 0.0, 1);
+        let fits = [
+            fit_circle_kasa(&points).unwrap(),
+            fit_circle_pratt(&points).unwrap(),
+            fit_circle_taubin(&points).unwrap(),
+            fit_circle_geometric(&points, 50).unwrap(),
+        ];
+        for fit in &fits {
+            assert!(fit.center.distance_to(&Point2d::new(3.0, -2.0)) < 1e-8 && (fit.radius - 5.0).abs() < 1e-8);
+            assert!(fit.stats.max_abs < 1e-8 && fit.residuals.len() == 12);
+        }
+        let line: Vec<Point2d<f64>> = (0..6).map(|i| Point2d::new(i as f64, 2.0 * i as f64)).collect();
+        assert!(fit_circle_kasa(&line).is_none() && fit_circle_taubin(&line).is_none());
+        assert!(fit_circle_pratt(&line).is_none() && fit_circle_geometric(&line, 50).is_none());
+        assert!(fit_circle_kasa(&points[..2]).is_none());
+    }
+    #[test]
+    fn circle_fits_on_a_short_noisy_arc() {
+        // A probe reaching only a sixth of a hole is the hard case for algebraic fits
+        let points = noisy_arc(60, 1.0, 2.0, 0.05, 2);
+        let kasa = fit_circle_kasa(&points).unwrap();
+        let pratt = fit_circle_pratt(&points).unwrap();
+        let taubin = fit_circle_taubin(&points).unwrap();
+        let geometric = fit_circle_geometric(&points, 100).unwrap();
+        assert!(kasa.radius < taubin.radius && (pratt.radius - taubin.radius).abs() < 0.05);
+        for fit in [&pratt, &taubin, &geometric] {
+            assert!((fit.radius - 5.0).abs() < 0.5 && fit.center.distance_to(&Point2d::new(3.0, -2.0)) < 0.5);
+        }
+        // The geometric fit minimizes exactly the quantity reported as rms
+        for fit in [&kasa, &pratt, &taubin] {
+            assert!(geometric.stats.rms <= fit.stats.rms);
+        }
+        let stats = geometric.stats;
+        // At the optimum the derivative by the radius vanishes, so the residuals sum to zero
+        assert!(stats.rms < 0.05 && stats.mean.abs() < 1e-9 && stats.max_abs < 0.06);
+        assert!((stats.rms * stats.rms - stats.std_dev * stats.std_dev - stats.mean * stats.mean).abs() < 1e-12);
+    }
+    #[test]
+    fn ellipse_fit_recovers_the_shape() {
+        let fit = fit_ellipse(&noisy_ellipse(20, 0.0, 5.0, 0.0, 3)).unwrap();
+        assert!(fit.center.distance_to(&Point2d::new(12.0, -4.0)) < 1e-8);
+        assert!((fit.semi_axes[0] - 9.0).abs() < 1e-8 && (fit.semi_axes[1] - 5.0).abs() < 1e-8);
+        // The major axis has no preferred direction
+        assert!((fit.rotation - 0.5).sin().abs() < 1e-8);
+        assert!((fit.conic[0] + fit.conic[2] - 1.0).abs() < 1e-12 && fit.stats.max_abs < 1e-8);
+        let noisy = fit_ellipse(&noisy_ellipse(200, 0.0, 6.2, 0.05, 4)).unwrap();
+        assert!(noisy.center.distance_to(&Point2d::new(12.0, -4.0)) < 0.05);
+        assert!((noisy.semi_axes[0] - 9.0).abs() < 0.05 && (noisy.semi_axes[1] - 5.0).abs() < 0.05);
+        assert!(noisy.stats.max_abs < 0.06 && noisy.stats.rms > 0.01);
+        let circle = fit_ellipse(&noisy_arc(10, 0.0, 6.0, 0.0, 5)).unwrap();
+        assert!((circle.semi_axes[0] - 5.0).abs() < 1e-8 && (circle.semi_axes[1] - 5.0).abs() < 1e-8);
+        let line: Vec<Point2d<f64>> = (0..8).map(|i| Point2d::new(i as f64, 1.0 - i as f64)).collect();
+        assert!(fit_ellipse(&line).is_none());
+        assert!(fit_ellipse(&noisy_arc(4, 0.0, 3.0, 0.0, 6)).is_none());
+    }
+}
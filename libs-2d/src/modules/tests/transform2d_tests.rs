@@ -0,0 +1,246 @@
+#[cfg(test)]
+mod tests {
+    use point2d::Point2d;
+    use matrix2d::Matrix2;
+    use transform2d::{
+        estimate_affine, estimate_rigid, estimate_similarity, icp, ransac, AffineTransform2d, Homography2d, IcpMethod,
+        IcpOptions, RansacOptions, TransformKind,
+    };
+    use vector2d::Vector2d;
+
+    fn random_points(count: usize, seed: u64) -> Vec<Point2d<f64>> {
+        let mut state = seed;
+        let mut next = move || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 53) as f64
+        };
+        (0..count).map(|_| Point2d::new(next() * 100.0, next() * 60.0)).collect()
+    }
+    fn assert_point_close(actual: &Point2d<f64>, expected: &Point2d<f64>, tolerance: f64) {
+        assert!(actual.distance_to(expected) <= tolerance, "expected {:?} but got {:?}", expected, actual);
+    }
+    /// A camera looking at a whiteboard from the side, with a noticeable perspective.
+    fn camera() -> Homography2d<f64> {
+        Homography2d::new([[0.9, 0.2, 30.0], [-0.1, 1.1, 12.0], [0.002, -0.001, 1.0]])
+    }
+
+    #[test]
+    fn homography_applies_inverts_and_composes() {
+        let h = camera();
+        let inverse = h.inverse().unwrap();
+        for p in random_points(20, 1) {
+            let image = h.apply(&p).unwrap();
+            assert_point_close(&inverse.apply(&image).unwrap(), &p, 1e-9);
+            assert_point_close(&h.compose(&inverse).apply(&image).unwrap(), &image, 1e-9);
+        }
+        let twice = h.compose(&h);
+        let p = Point2d::new(10.0, 20.0);
+        assert_point_close(&twice.apply(&p).unwrap(), &h.apply(&h.apply(&p).unwrap()).unwrap(), 1e-9);
+        assert_eq!(Homography2d::identity().apply(&p), Some(p));
+        // The line 0.002 x - 0.001 y + 1 = 0 is sent to infinity
+        assert!(h.apply(&Point2d::new(-500.0, 0.0)).is_none());
+        assert!(Homography2d::new([[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [0.0, 0.0, 1.0]]).inverse().is_none());
+    }
+    #[test]
+    fn four_point_pairs_are_mapped_exactly() {
+        // Rectify a photographed whiteboard to a 120 x 90 rectangle
+        let photo = [
+            Point2d::new(12.0, 8.0),
+            Point2d::new(140.0, 20.0),
+            Point2d::new(131.0, 101.0),
+            Point2d::new(18.0, 95.0),
+        ];
+        let board = [Point2d::new(0.0, 0.0), Point2d::new(120.0, 0.0), Point2d::new(120.0, 90.0), Point2d::new(0.0, 90.0)];
+        let h = Homography2d::from_four_points(&photo, &board).unwrap();
+        for (p, q) in photo.iter().zip(&board) {
+            assert_point_close(&h.apply(p).unwrap(), q, 1e-9);
+        }
+        assert_eq!(h.matrix[2][2], 1.0);
+        let line = [Point2d::new(0.0, 0.0), Point2d::new(1.0, 1.0), Point2d::new(2.0, 2.0), Point2d::new(0.0, 5.0)];
+        assert!(Homography2d::from_four_points(&line, &board).is_none());
+    }
+    #[test]
+    fn least_squares_estimate_recovers_the_transform() {
+        let h = camera();
+        let from = random_points(40, 2);
+        let to: Vec<Point2d<f64>> = from.iter().map(|p| h.apply(p).unwrap()).collect();
+        let estimate = Homography2d::estimate(&from, &to).unwrap();
+        for p in &from {
+            assert_point_close(&estimate.apply(p).unwrap(), &h.apply(p).unwrap(), 1e-8);
+        }
+        assert_eq!(estimate, estimate.normalized());
+        // The same four pairs as the exact construction give the same transform
+        let four = Homography2d::estimate(&from[..4], &to[..4]).unwrap();
+        assert_point_close(&four.apply(&from[10]).unwrap(), &to[10], 1e-6);
+        assert!(Homography2d::estimate(&from[..3], &to[..3]).is_none());
+        assert!(Homography2d::estimate(&from, &to[1..]).is_none());
+    }
+    #[test]
+    fn least_squares_estimate_averages_noise() {
+        let h = camera();
+        let from = random_points(200, 3);
+        let noise = random_points(200, 4);
+        // Offsets of up to 0.1 in each coordinate of the measured image points
+        let to: Vec<Point2d<f64>> = from
+            .iter()
+            .zip(&noise)
+            .map(|(p, n)| {
+                let q = h.apply(p).unwrap();
+                Point2d::new(q.x() + (n.x() / 100.0 - 0.5) * 0.2, q.y() + (n.y() / 60.0 - 0.5) * 0.2)
+            })
+            .collect();
+        let estimate = Homography2d::estimate(&from, &to).unwrap();
+        for p in random_points(20, 5) {
+            assert_point_close(&estimate.apply(&p).unwrap(), &h.apply(&p).unwrap(), 0.05);
+        }
+    }
+    #[test]
+    fn affine_transforms_invert_and_compose() {
+        let a = AffineTransform2d::new(Matrix2::new(2.0, 0.5, -0.3, 1.5), Vector2d::new(4.0, -2.0));
+        let b = AffineTransform2d::similarity(0.4, 3.0, Vector2d::new(1.0, 1.0));
+        let p = Point2d::new(3.0, 7.0);
+        assert_point_close(&a.inverse().unwrap().apply(&a.apply(&p)), &p, 1e-12);
+        assert_point_close(&a.compose(&b).apply(&p), &a.apply(&b.apply(&p)), 1e-12);
+        assert_point_close(&a.to_homography().apply(&p).unwrap(), &a.apply(&p), 1e-12);
+        assert!((b.rotation_angle() - 0.4).abs() < 1e-12 && (b.area_scale() - 9.0).abs() < 1e-12);
+        assert!(AffineTransform2d::new(Matrix2::zero(), Vector2d::new(1.0, 0.0)).inverse().is_none());
+    }
+    #[test]
+    fn rigid_similarity_and_affine_fits_recover_exact_transforms() {
+        let from = random_points(30, 6);
+        let rigid = AffineTransform2d::rigid(-2.5, Vector2d::new(40.0, 3.0));
+        let to: Vec<Point2d<f64>> = from.iter().map(|p| rigid.apply(p)).collect();
+        let fit = estimate_rigid(&from, &to).unwrap();
+        assert!((fit.transform.rotation_angle() + 2.5).abs() < 1e-12 && fit.rms < 1e-10);
+        assert_eq!(fit.residuals.len(), from.len());
+        let similarity = AffineTransform2d::similarity(1.2, 0.25, Vector2d::new(-7.0, 9.0));
+        let to: Vec<Point2d<f64>> = from.iter().map(|p| similarity.apply(p)).collect();
+        let fit = estimate_similarity(&from, &to).unwrap();
+        assert!((fit.transform.area_scale().sqrt() - 0.25).abs() < 1e-12 && fit.rms < 1e-10);
+        // A rigid fit to scaled data finds the rotation but leaves residuals
+        let fit = estimate_rigid(&from, &to).unwrap();
+        assert!((fit.transform.rotation_angle() - 1.2).abs() < 1e-12 && fit.rms > 1.0);
+        let affine = AffineTransform2d::new(Matrix2::new(1.0, 0.8, 0.1, 0.6), Vector2d::new(5.0, 5.0));
+        let to: Vec<Point2d<f64>> = from.iter().map(|p| affine.apply(p)).collect();
+        let fit = estimate_affine(&from, &to).unwrap();
+        assert!(fit.rms < 1e-10);
+        assert!(estimate_similarity(&from, &to).unwrap().rms > 1.0);
+        let line: Vec<Point2d<f64>> = (0..5).map(|i| Point2d::new(i as f64 * 0.1, i as f64 * 0.3)).collect();
+        assert!(estimate_affine(&line, &line).is_none());
+        assert!(estimate_rigid(&from[..1], &to[..1]).is_none());
+    }
+    #[test]
+    fn ransac_rejects_mismatched_pairs() {
+        let from = random_points(100, 7);
+        let truth = AffineTransform2d::rigid(0.7, Vector2d::new(-20.0, 35.0));
+        let junk = random_points(100, 8);
+        // Every third pair is a wrong match; the rest carry small measurement noise
+        let to: Vec<Point2d<f64>> = from
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let q = truth.apply(p);
+                if i % 3 == 0 { junk[i] } else { Point2d::new(q.x() + (junk[i].x() / 100.0 - 0.5) * 0.02, q.y()) }
+            })
+            .collect();
+        assert!(estimate_rigid(&from, &to).unwrap().rms > 5.0);
+        let options = RansacOptions::new(0.1);
+        let fit = ransac(TransformKind::Rigid, &from, &to, &options).unwrap();
+        let expected: Vec<usize> = (0..100).filter(|i| i % 3 != 0).collect();
+        assert_eq!(fit.inliers, expected);
+        assert!((fit.transform.rotation_angle() - 0.7).abs() < 1e-3 && fit.rms < 0.01);
+        assert_eq!(fit.residuals.len(), 100);
+        assert_eq!(ransac(TransformKind::Rigid, &from, &to, &options), Some(fit));
+        let affine = ransac(TransformKind::Affine, &from, &to, &RansacOptions { seed: 3, ..options }).unwrap();
+        assert_eq!(affine.inliers, expected);
+        assert!(ransac(TransformKind::Affine, &from[..2], &to[..2], &options).is_none());
+    }
+    #[test]
+    fn ransac_keeps_the_sample_fit_when_its_inliers_do_not_refit() {
+        // A point just off a line of points: samples holding it fit, but all the points together
+        // are too close to collinear for an affine fit
+        let mut from: Vec<Point2d<f64>> = (0..100).map(|i| Point2d::new(i as f64 * 0.1, 0.0)).collect();
+        from.push(Point2d::new(5.0, 0.002));
+        let truth = AffineTransform2d::rigid(0.3, Vector2d::new(1.0, 2.0));
+        let to: Vec<Point2d<f64>> = from.iter().map(|p| truth.apply(p)).collect();
+        assert!(estimate_affine(&from, &to).is_none());
+        let fit = ransac(TransformKind::Affine, &from, &to, &RansacOptions::new(1e-6)).unwrap();
+        assert_eq!(fit.inliers, (0..101).collect::<Vec<_>>());
+        assert!(fit.rms < 1e-9 && (fit.transform.rotation_angle() - 0.3).abs() < 1e-6);
+    }
+    /// Points every `step` along the outline of a 10 by 6 room, starting `offset` along it.
+    fn room(step: f64, offset: f64) -> Vec<Point2d<f64>> {
+        let count = (32.0 / step) as usize;
+        (0..count)
+            .map(|i| {
+                let s = offset + i as f64 * step;
+                match s {
+                    s if s < 10.0 => Point2d::new(s, 0.0),
+                    s if s < 16.0 => Point2d::new(10.0, s - 10.0),
+                    s if s < 26.0 => Point2d::new(26.0 - s, 6.0),
+                    s => Point2d::new(0.0, 32.0 - s),
+                }
+            })
+            .collect()
+    }
+    #[test]
+    fn point_to_point_icp_aligns_a_displaced_copy() {
+        let target = random_points(200, 9);
+        let truth = AffineTransform2d::rigid(0.08, Vector2d::new(1.5, -1.0));
+        let source: Vec<Point2d<f64>> = target.iter().map(|p| truth.inverse().unwrap().apply(p)).collect();
+        let options = IcpOptions::new(IcpMethod::PointToPoint);
+        let result = icp(&source, &target, &AffineTransform2d::identity(), &options).unwrap();
+        assert!(result.converged && result.rms < 1e-9);
+        assert_eq!(result.correspondences, 200);
+        assert!((result.transform.rotation_angle() - 0.08).abs() < 1e-9);
+        assert_point_close(&result.transform.apply(&source[0]), &target[0], 1e-9);
+        let first = result.iterations[0];
+        assert!(first.rms > 0.1 && first.correspondences == 200 && first.translation_step > 0.0);
+        assert!(result.iterations.windows(2).all(|pair| pair[1].rms <= pair[0].rms));
+    }
+    #[test]
+    fn point_to_line_icp_slides_scans_along_walls() {
+        // The scan samples the walls at other places than the map, so no point has an exact partner
+        let target = room(0.1, 0.0);
+        let truth = AffineTransform2d::rigid(-0.04, Vector2d::new(0.3, 0.2));
+        let inverse = truth.inverse().unwrap();
+        let source: Vec<Point2d<f64>> = room(0.25, 0.07).iter().map(|p| inverse.apply(p)).collect();
+        let mut options = IcpOptions::new(IcpMethod::PointToLine);
+        options.max_correspondence_distance = 1.0;
+        let line = icp(&source, &target, &AffineTransform2d::identity(), &options).unwrap();
+        assert!(line.converged && line.rms < 1e-3);
+        assert!((line.transform.rotation_angle() + 0.04).abs() < 1e-3);
+        assert!((line.transform.translation.x - 0.3).abs() < 1e-2 && (line.transform.translation.y - 0.2).abs() < 1e-2);
+        options.method = IcpMethod::PointToPoint;
+        let point = icp(&source, &target, &AffineTransform2d::identity(), &options).unwrap();
+        assert!(line.iterations.len() < point.iterations.len());
+    }
+    #[test]
+    fn icp_ignores_pairs_beyond_the_correspondence_distance() {
+        let target = room(0.1, 0.0);
+        let mut source = room(0.2, 0.05);
+        source.extend([Point2d::new(50.0, 50.0), Point2d::new(-40.0, 3.0)]);
+        let mut options = IcpOptions::new(IcpMethod::PointToPoint);
+        options.max_correspondence_distance = 0.5;
+        let result = icp(&source, &target, &AffineTransform2d::identity(), &options).unwrap();
+        assert_eq!(result.correspondences, source.len() - 2);
+        assert!(result.rms < 0.05);
+        let far = AffineTransform2d::rigid(0.0, Vector2d::new(100.0, 0.0));
+        assert!(icp(&source, &target, &far, &options).is_none());
+        assert!(icp(&source, &[], &AffineTransform2d::identity(), &options).is_none());
+    }
+    #[test]
+    fn icp_undoes_a_step_that_increases_the_error() {
+        // Near the corners the linearized point-to-line step overshoots once the scan is close
+        let target = room(0.1, 0.0);
+        let offset = AffineTransform2d::rigid(0.05, Vector2d::new(0.0, 0.3));
+        let source: Vec<Point2d<f64>> = room(0.4, 0.05).iter().map(|p| offset.apply(p)).collect();
+        let options = IcpOptions::new(IcpMethod::PointToLine);
+        let result = icp(&source, &target, &AffineTransform2d::identity(), &options).unwrap();
+        assert!(!result.converged);
+        // The result is the transform before the bad step, whose error the last iteration measured
+        let last = result.iterations.last().unwrap();
+        assert_eq!(result.rms, last.rms);
+        assert!(result.iterations.windows(2).all(|pair| pair[1].rms <= pair[0].rms));
+    }
+}
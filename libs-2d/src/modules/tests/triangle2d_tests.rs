@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use point2d::Point2d;
+    use predicates2d::Orientation;
+    use triangle2d::{AngleKind, SideKind, Triangle2d};
+
+    fn scalene() -> Triangle2d<f64> {
+        Triangle2d::new(Point2d::new(1.0, 1.0), Point2d::new(7.0, 2.0), Point2d::new(3.0, 6.0))
+    }
+    fn assert_point_close(actual: &Point2d<f64>, expected: &Point2d<f64>, tolerance: f64) {
+        assert!(actual.distance_to(expected) < tolerance, "{actual:?} is not close to {expected:?}");
+    }
+    #[test]
+    fn area_orientation_and_barycentric_coordinates() {
+        let triangle = scalene();
+        assert_eq!(triangle.signed_area(), 14.0);
+        assert_eq!(triangle.orientation(), Orientation::CounterClockwise);
+        let reversed = Triangle2d::new(triangle.a, triangle.c, triangle.b);
+        assert_eq!((reversed.signed_area(), reversed.area()), (-14.0, 14.0));
+        assert_eq!(reversed.orientation(), Orientation::Clockwise);
+        for weights in [[1.0, 0.0, 0.0], [0.2, 0.3, 0.5], [-0.5, 1.0, 0.5]] {
+            let point = triangle.point_at(weights);
+            for (tri, expected) in [(triangle, weights), (reversed, [weights[0], weights[2], weights[1]])] {
+                let found = tri.barycentric(&point).unwrap();
+                assert!(found.iter().zip(expected).all(|(f, e)| (f - e).abs() < 1e-12));
+            }
+        }
+        // Interpolation reproduces any linear function
+        let f = |p: &Point2d<f64>| 2.0 * p.x() - 3.0 * p.y() + 1.0;
+        let values = [f(&triangle.a), f(&triangle.b), f(&triangle.c)];
+        let inside = Point2d::new(3.5, 3.0);
+        assert!((triangle.interpolate(&inside, values).unwrap() - f(&inside)).abs() < 1e-12);
+        assert!(triangle.contains(&inside) && reversed.contains(&inside));
+        assert!(triangle.contains(&triangle.b) && triangle.contains(&Point2d::new(4.0, 1.5)));
+        assert!(!triangle.contains(&Point2d::new(6.0, 5.0)));
+        let flat = Triangle2d::new(Point2d::new(0.0, 0.0), Point2d::new(1.0, 1.0), Point2d::new(3.0, 3.0));
+        assert!(flat.is_degenerate() && flat.orientation() == Orientation::Collinear);
+        assert!(flat.barycentric(&Point2d::new(1.0, 1.0)).is_none() && !flat.contains(&Point2d::new(1.0, 1.0)));
+    }
+    #[test]
+    fn classic_centers_have_their_defining_properties() {
+        let triangle = scalene();
+        let [a, b, c] = triangle.vertices();
+        assert_point_close(&triangle.centroid(), &Point2d::new(11.0 / 3.0, 3.0), 1e-12);
+        let circumcenter = triangle.circumcenter().unwrap();
+        let radius = triangle.circumradius().unwrap();
+        for corner in [a, b, c] {
+            assert!((circumcenter.distance_to(&corner) - radius).abs() < 1e-12);
+        }
+        // The incircle touches every side
+        let incenter = triangle.incenter().unwrap();
+        for edge in triangle.edges() {
+            assert!((edge.distance_to_point(&incenter) - triangle.inradius()).abs() < 1e-12);
+        }
+        // Each altitude through the orthocenter is perpendicular to the opposite side
+        let orthocenter = triangle.orthocenter().unwrap();
+        for (corner, p, q) in [(a, b, c), (b, c, a), (c, a, b)] {
+            let dot = (orthocenter.x() - corner.x()) * (q.x() - p.x()) + (orthocenter.y() - corner.y()) * (q.y() - p.y());
+            assert!(dot.abs() < 1e-9);
+        }
+        // The right angle of a right triangle is its orthocenter, the hypotenuse midpoint its circumcenter
+        let right = Triangle2d::new(Point2d::new(0.0, 0.0), Point2d::new(4.0, 0.0), Point2d::new(0.0, 3.0));
+        assert_point_close(&right.orthocenter().unwrap(), &Point2d::new(0.0, 0.0), 1e-12);
+        assert_point_close(&right.circumcenter().unwrap(), &Point2d::new(2.0, 1.5), 1e-12);
+        assert_eq!(right.inradius(), 1.0);
+        let flat = Triangle2d::new(Point2d::new(0.0, 0.0), Point2d::new(1.0, 0.0), Point2d::new(2.0, 0.0));
+        assert!(flat.circumcenter().is_none() && flat.orthocenter().is_none() && flat.incenter().is_none());
+    }
+    #[test]
+    fn centers_of_a_sliver_far_from_the_origin() {
+        let corners = [Point2d::new(1e6, 1e6), Point2d::new(1e6 + 1.0, 1e6), Point2d::new(1e6 + 0.5, 1e6 + 1e-3)];
+        let triangle: Triangle2d<f64> = Triangle2d::new(corners[0], corners[1], corners[2]);
+        // Worked out exactly from the rounded corners; summing coordinates near 1e6 was off by 2e-6
+        assert_point_close(&triangle.circumcenter().unwrap(), &Point2d::new(1e6 + 0.5, 999875.0005059371), 1e-8);
+        assert!((triangle.circumradius().unwrap() - 125.0004940628).abs() < 1e-8);
+        assert_point_close(&triangle.orthocenter().unwrap(), &Point2d::new(1e6 + 0.5, 1000249.9999881256), 1e-8);
+    }
+    #[test]
+    fn angles_and_classification() {
+        let triangle = scalene();
+        let angles = triangle.angles();
+        assert!((angles.iter().sum::<f64>() - PI).abs() < 1e-12);
+        assert_eq!(triangle.angle_kind(), AngleKind::Acute);
+        assert_eq!(triangle.side_kind(), SideKind::Scalene);
+        let right = Triangle2d::new(Point2d::new(1.0, 1.0), Point2d::new(1.0, 4.0), Point2d::new(-3.0, 1.0));
+        assert!((right.angles()[0] - PI / 2.0).abs() < 1e-15);
+        assert_eq!(right.angle_kind(), AngleKind::Right);
+        let obtuse = Triangle2d::new(Point2d::new(0.0, 0.0), Point2d::new(10.0, 0.0), Point2d::new(5.0, 1.0));
+        assert_eq!((obtuse.angle_kind(), obtuse.side_kind()), (AngleKind::Obtuse, SideKind::Isosceles));
+        let height = 3.0f64.sqrt() / 2.0;
+        let equilateral = Triangle2d::new(Point2d::new(0.0, 0.0), Point2d::new(1.0, 0.0), Point2d::new(0.5, height));
+        assert_eq!((equilateral.angle_kind(), equilateral.side_kind()), (AngleKind::Acute, SideKind::Equilateral));
+        assert!(equilateral.angles().iter().all(|angle| (angle - PI / 3.0).abs() < 1e-12));
+        // A sliver has a tiny angle that acos of the normalized dot product would lose
+        let sliver = Triangle2d::new(Point2d::new(0.0f64, 0.0), Point2d::new(1.0, 1e-9), Point2d::new(1.0, -1e-9));
+        assert!((sliver.angles()[0] - 2e-9).abs() < 1e-20);
+    }
+}
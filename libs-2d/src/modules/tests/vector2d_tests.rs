@@ -89,4 +89,56 @@ mod tests {
         assert_eq!(vector.x, 0);
         assert_eq!(vector.y, 0);
     }
+
+    #[test]
+    fn exact_products_with_rational_components() {
+        use vector2d::BigRational;
+        let third = |n: i64| BigRational::new(n.into(), 3.into());
+        let a = Vector2d { x: third(1), y: third(2) };
+        let b = Vector2d { x: third(4), y: third(-1) };
+        assert_eq!(a.dot(&b), BigRational::new(2.into(), 9.into()));
+        assert_eq!(a.cross(&b), BigRational::new((-1).into(), 1.into()));
+        let projected = a.project_onto(&b);
+        // The rejection is exactly perpendicular to b, which floats only get approximately
+        assert!((&a - &projected).dot(&b).is_zero());
+        assert_eq!(Vector2d::from_f64(&Vector2d::new(0.1, -2.5)).unwrap().to_f64(), Vector2d::new(0.1, -2.5));
+        assert!(Vector2d::from_f64(&Vector2d::new(f64::NAN, 0.0)).is_none());
+    }
+
+    #[test]
+    fn unit_vectors_are_only_made_by_normalizing() {
+        use vector2d::UnitVector2d;
+        let unit = UnitVector2d::new(Vector2d::new(3.0, -4.0)).unwrap();
+        assert_eq!((unit.x(), unit.y()), (0.6, -0.8));
+        assert!(UnitVector2d::new(Vector2d::new(0.0, 0.0)).is_none());
+        assert!(UnitVector2d::new(Vector2d::new(f64::INFINITY, 1.0)).is_none());
+        // Normalizing is the only way to get one, and fails where there is no direction
+        assert_eq!(Vector2d::new(3.0, -4.0).normalized(), Some(unit));
+        assert_eq!(Vector2d::new(3.0, -4.0).unit_vector(), Some(unit));
+        assert!(Vector2d::new(0.0, 0.0).normalized().is_none());
+        let mut zero = Vector2d::new(0.0, 0.0);
+        zero.normalize();
+        assert_eq!(zero, Vector2d::new(0.0, 0.0));
+        assert_eq!(Vector2d::new(6.0, 8.0).clamp_magnitude(5.0), Vector2d::new(3.0, 4.0));
+        // Components that would overflow when squared still normalize
+        let huge = Vector2d::new(1e300, 1e300).direction().unwrap();
+        assert!((huge.x() - 0.5f64.sqrt()).abs() < 1e-15);
+        assert_eq!(Vector2d::from(unit.perpendicular()), Vector2d::new(0.8, 0.6));
+        assert_eq!((-unit).to_vector(), Vector2d::new(-0.6, 0.8));
+        assert!((UnitVector2d::from_angle(1.0f64).rotate(0.5).angle() - 1.5).abs() < 1e-15);
+        // A ball falling onto a floor bounces straight back up
+        let floor = Vector2d::new(0.0, 2.0).direction().unwrap();
+        assert_eq!(Vector2d::new(1.0, -3.0).reflect(&floor), Vector2d::new(1.0, 3.0));
+        assert_eq!(floor.scale(5.0), Vector2d::new(0.0, 5.0));
+    }
+
+    #[test]
+    fn sizes_treat_negative_extents_as_empty_and_scale() {
+        use vector2d::Size2d;
+        let size = Size2d::new(4.0, 2.5);
+        assert_eq!(size.area(), 10.0);
+        assert_eq!(Size2d::from_vector(size.to_vector()), size);
+        assert!(size.fits_in(&size.scale(1.5)) && !size.scale(1.5).fits_in(&size));
+        assert!(Size2d::new(0, 3).is_empty() && Size2d::new(-2, 3).area() == 0);
+    }
 }
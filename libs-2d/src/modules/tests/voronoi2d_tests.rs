@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+    use point2d::Point2d;
+    use voronoi2d::Voronoi2d;
+
+    fn random_points(count: usize, seed: u64) -> Vec<Point2d<f64>> {
+        let mut state = seed;
+        let mut next = move || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 53) as f64
+        };
+        (0..count).map(|_| Point2d::new(next() * 10.0, next() * 10.0)).collect()
+    }
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "expected {} but got {}", expected, actual);
+    }
+
+    #[test]
+    fn two_sites_split_the_box() {
+        let sites = [Point2d::new(1.0, 1.0), Point2d::new(3.0, 1.0)];
+        let diagram = Voronoi2d::new(&sites, Point2d::new(0.0, 0.0), Point2d::new(4.0, 2.0)).unwrap();
+        assert_close(diagram.cell(0).area(), 4.0);
+        assert_close(diagram.cell(1).area(), 4.0);
+        assert!(diagram.cell(0).is_ccw());
+        assert_eq!(diagram.neighbors(0), &[1]);
+        assert_eq!(diagram.neighbors(1), &[0]);
+        let edge = diagram.edges()[0];
+        assert_eq!(edge.sites, [0, 1]);
+        assert_eq!((edge.start, edge.end), (Point2d::new(2.0, 0.0), Point2d::new(2.0, 2.0)));
+    }
+    #[test]
+    fn grid_sites_give_square_cells_without_diagonal_neighbors() {
+        let sites: Vec<Point2d<f64>> = (0..9).map(|i| Point2d::new((i % 3) as f64 + 0.5, (i / 3) as f64 + 0.5)).collect();
+        let diagram = Voronoi2d::new(&sites, Point2d::new(0.0, 0.0), Point2d::new(3.0, 3.0)).unwrap();
+        for cell in diagram.cells() {
+            assert_close(cell.area(), 1.0);
+            assert_eq!(cell.exterior().len(), 4);
+        }
+        // The four cells around a grid point meet in a single point, which is not an edge
+        assert_eq!(diagram.neighbors(4), &[1, 3, 5, 7]);
+        assert_eq!(diagram.neighbors(0), &[1, 3]);
+        assert_eq!(diagram.edges().len(), 12);
+    }
+    #[test]
+    fn random_sites_partition_the_box() {
+        let sites = random_points(200, 11);
+        let diagram = Voronoi2d::new(&sites, Point2d::new(0.0, 0.0), Point2d::new(10.0, 10.0)).unwrap();
+        let total: f64 = diagram.cells().iter().map(|cell| cell.area()).sum();
+        assert_close(total, 100.0);
+        for (i, site) in sites.iter().enumerate() {
+            assert!(diagram.cell(i).contains(site));
+            for &j in diagram.neighbors(i) {
+                assert!(diagram.neighbors(j).contains(&i));
+            }
+        }
+        let adjacency: usize = (0..sites.len()).map(|i| diagram.neighbors(i).len()).sum();
+        assert_eq!(diagram.edges().len() * 2, adjacency);
+        for edge in diagram.edges() {
+            let [a, b] = edge.sites.map(|i| sites[i]);
+            // Edge points are equally far from both sites
+            assert_close(edge.start.distance_to(&a), edge.start.distance_to(&b));
+            assert_close(edge.end.distance_to(&a), edge.end.distance_to(&b));
+        }
+    }
+    #[test]
+    fn collinear_sites_give_strips() {
+        let sites: Vec<Point2d<f64>> = (0..4).map(|i| Point2d::new(i as f64 + 0.5, 1.0)).collect();
+        let diagram = Voronoi2d::new(&sites, Point2d::new(0.0, 0.0), Point2d::new(4.0, 2.0)).unwrap();
+        for cell in diagram.cells() {
+            assert_close(cell.area(), 2.0);
+        }
+        assert_eq!(diagram.neighbors(1), &[0, 2]);
+        assert_eq!(diagram.edges().len(), 3);
+    }
+    #[test]
+    fn repeated_and_outside_sites() {
+        let sites = [Point2d::new(1.0, 1.0), Point2d::new(3.0, 1.0), Point2d::new(1.0, 1.0), Point2d::new(9.0, 1.0)];
+        let diagram = Voronoi2d::new(&sites, Point2d::new(0.0, 0.0), Point2d::new(4.0, 2.0)).unwrap();
+        assert!(diagram.cell(2).exterior().is_empty());
+        assert!(diagram.cell(3).exterior().is_empty());
+        assert_close(diagram.cell(0).area() + diagram.cell(1).area(), 8.0);
+        assert_eq!(diagram.neighbors(1), &[0]);
+        assert!(Voronoi2d::new(&sites, Point2d::new(0.0, 0.0), Point2d::new(0.0, 2.0)).is_none());
+    }
+}
@@ -0,0 +1,64 @@
+use matrix2d::Matrix2;
+use num_traits::Float;
+use point2d::Point2d;
+use vector2d::Vector2d;
+
+use crate::Homography2d;
+
+/// The transform `p -> linear * p + translation`.
+///
+/// Rigid transforms have a rotation as `linear`, similarities a scaled rotation.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AffineTransform2d<T> {
+    pub linear: Matrix2<T>,
+    pub translation: Vector2d<T>,
+}
+
+impl<T: Float> AffineTransform2d<T> {
+    pub fn new(linear: Matrix2<T>, translation: Vector2d<T>) -> Self {
+        AffineTransform2d { linear, translation }
+    }
+    pub fn identity() -> Self {
+        AffineTransform2d::new(Matrix2::identity(), Vector2d::new(T::zero(), T::zero()))
+    }
+    /// Returns the rotation by `angle` radians about the origin, scaled by `scale`, followed
+    /// by `translation`.
+    pub fn similarity(angle: T, scale: T, translation: Vector2d<T>) -> Self {
+        AffineTransform2d::new(Matrix2::rotation(angle) * scale, translation)
+    }
+    pub fn rigid(angle: T, translation: Vector2d<T>) -> Self {
+        Self::similarity(angle, T::one(), translation)
+    }
+    pub fn apply(&self, point: &Point2d<T>) -> Point2d<T> {
+        let image = self.linear.mul_vector(&point.0);
+        Point2d::new(image.x + self.translation.x, image.y + self.translation.y)
+    }
+    /// Returns the inverse transform, or None if `linear` is singular.
+    pub fn inverse(&self) -> Option<Self> {
+        let linear = self.linear.inverse()?;
+        let translation = linear.mul_vector(&self.translation);
+        Some(AffineTransform2d::new(linear, Vector2d::new(-translation.x, -translation.y)))
+    }
+    /// Returns the transform that applies `other` first and then `self`.
+    pub fn compose(&self, other: &Self) -> Self {
+        let translation = self.linear.mul_vector(&other.translation);
+        AffineTransform2d::new(
+            self.linear * other.linear,
+            Vector2d::new(translation.x + self.translation.x, translation.y + self.translation.y),
+        )
+    }
+    /// Returns the rotation angle of the first axis, which for a similarity is its angle.
+    pub fn rotation_angle(&self) -> T {
+        self.linear.m21.atan2(self.linear.m11)
+    }
+    /// Returns the scale of areas, the absolute determinant of `linear`; its square root is
+    /// the scale factor of a similarity.
+    pub fn area_scale(&self) -> T {
+        self.linear.determinant().abs()
+    }
+    pub fn to_homography(&self) -> Homography2d<T> {
+        let (m, t) = (&self.linear, &self.translation);
+        let (o, l) = (T::zero(), T::one());
+        Homography2d::new([[m.m11, m.m12, t.x], [m.m21, m.m22, t.y], [o, o, l]])
+    }
+}
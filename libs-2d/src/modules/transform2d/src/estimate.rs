@@ -0,0 +1,242 @@
+use matrix2d::Matrix2;
+use num_traits::Float;
+use point2d::Point2d;
+use vector2d::Vector2d;
+
+use crate::AffineTransform2d;
+
+/// The families of transform that can be estimated from point pairs.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TransformKind {
+    /// Rotation and translation.
+    Rigid,
+    /// Rotation, uniform scale and translation.
+    Similarity,
+    /// Any linear map and translation.
+    Affine,
+}
+
+impl TransformKind {
+    /// Returns the number of pairs that determine a transform of this kind.
+    pub fn minimal_pairs(&self) -> usize {
+        match self {
+            TransformKind::Rigid | TransformKind::Similarity => 2,
+            TransformKind::Affine => 3,
+        }
+    }
+}
+
+/// A transform fitted to point pairs, with the distance from each mapped point to its
+/// partner and the root mean square of those distances.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransformFit<T> {
+    pub transform: AffineTransform2d<T>,
+    pub residuals: Vec<T>,
+    pub rms: T,
+}
+
+/// The result of `ransac`: the transform refitted to the inliers, the indices of the
+/// inliers, the residual of every pair and the root mean square over the inliers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RansacFit<T> {
+    pub transform: AffineTransform2d<T>,
+    pub inliers: Vec<usize>,
+    pub residuals: Vec<T>,
+    pub rms: T,
+}
+
+/// Settings for `ransac`. The same seed always gives the same result.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RansacOptions<T> {
+    /// Largest residual of a pair that counts as an inlier.
+    pub threshold: T,
+    /// Number of minimal samples tried.
+    pub iterations: usize,
+    pub seed: u64,
+}
+
+impl<T> RansacOptions<T> {
+    pub fn new(threshold: T) -> Self {
+        RansacOptions { threshold, iterations: 500, seed: 0x853c_49e6_748f_ea9b }
+    }
+}
+
+fn centroid<T: Float>(points: &[Point2d<T>]) -> Point2d<T> {
+    let count = T::from(points.len()).unwrap();
+    let (sx, sy) = points.iter().fold((T::zero(), T::zero()), |(sx, sy), p| (sx + p.x(), sy + p.y()));
+    Point2d::new(sx / count, sy / count)
+}
+
+fn offset<T: Float>(point: &Point2d<T>, origin: &Point2d<T>) -> Vector2d<T> {
+    Vector2d::new(point.x() - origin.x(), point.y() - origin.y())
+}
+
+fn residuals<T: Float>(transform: &AffineTransform2d<T>, from: &[Point2d<T>], to: &[Point2d<T>]) -> Vec<T> {
+    from.iter().zip(to).map(|(p, q)| transform.apply(p).distance_to(q)).collect()
+}
+
+fn rms<T: Float>(residuals: impl Iterator<Item = T>) -> T {
+    let (sum, count) = residuals.fold((T::zero(), 0usize), |(sum, count), r| (sum + r * r, count + 1));
+    (sum / T::from(count.max(1)).unwrap()).sqrt()
+}
+
+fn fit<T: Float>(transform: AffineTransform2d<T>, from: &[Point2d<T>], to: &[Point2d<T>]) -> TransformFit<T> {
+    let residuals = residuals(&transform, from, to);
+    let rms = rms(residuals.iter().copied());
+    TransformFit { transform, residuals, rms }
+}
+
+/// Returns the translation taking `linear` applied to the centroid of `from` onto the
+/// centroid of `to`.
+fn with_translation<T: Float>(
+    linear: Matrix2<T>,
+    from_centroid: &Point2d<T>,
+    to_centroid: &Point2d<T>,
+) -> AffineTransform2d<T> {
+    let moved = linear.mul_vector(&from_centroid.0);
+    AffineTransform2d::new(linear, Vector2d::new(to_centroid.x() - moved.x, to_centroid.y() - moved.y))
+}
+
+/// Fits a rotation, scaled if `with_scale`, by the planar case of Umeyama's method: the best
+/// angle is that of the summed dot and cross products of the centred pairs.
+fn rotation_fit<T: Float>(from: &[Point2d<T>], to: &[Point2d<T>], with_scale: bool) -> Option<TransformFit<T>> {
+    if from.len() < 2 || from.len() != to.len() {
+        return None;
+    }
+    let (from_centroid, to_centroid) = (centroid(from), centroid(to));
+    let (mut dot, mut cross, mut spread) = (T::zero(), T::zero(), T::zero());
+    for (p, q) in from.iter().zip(to) {
+        let (u, v) = (offset(p, &from_centroid), offset(q, &to_centroid));
+        dot = dot + u.dot(&v);
+        cross = cross + u.cross(&v);
+        spread = spread + u.length_squared();
+    }
+    if spread.is_zero() {
+        return None; // Every source point is the same
+    }
+    let scale = if with_scale { dot.hypot(cross) / spread } else { T::one() };
+    let linear = Matrix2::rotation(cross.atan2(dot)) * scale;
+    Some(fit(with_translation(linear, &from_centroid, &to_centroid), from, to))
+}
+
+/// Returns the rotation and translation minimizing the sum of squared distances between the
+/// mapped `from` points and the `to` points (Kabsch), or None if there are fewer than two
+/// pairs, the slices differ in length or all `from` points coincide.
+pub fn estimate_rigid<T: Float>(from: &[Point2d<T>], to: &[Point2d<T>]) -> Option<TransformFit<T>> {
+    rotation_fit(from, to, false)
+}
+
+/// Returns the best rotation, uniform scale and translation (Umeyama), with the same
+/// requirements as `estimate_rigid`.
+pub fn estimate_similarity<T: Float>(from: &[Point2d<T>], to: &[Point2d<T>]) -> Option<TransformFit<T>> {
+    rotation_fit(from, to, true)
+}
+
+/// Returns the best affine transform in the least-squares sense, or None if there are fewer
+/// than three pairs, the slices differ in length or the `from` points are (nearly) collinear.
+pub fn estimate_affine<T: Float>(from: &[Point2d<T>], to: &[Point2d<T>]) -> Option<TransformFit<T>> {
+    if from.len() < 3 || from.len() != to.len() {
+        return None;
+    }
+    let (from_centroid, to_centroid) = (centroid(from), centroid(to));
+    // linear = (sum v u^T) (sum u u^T)^-1 over the centred pairs (u, v)
+    let mut source = Matrix2::zero();
+    let mut cross = Matrix2::zero();
+    for (p, q) in from.iter().zip(to) {
+        let (u, v) = (offset(p, &from_centroid), offset(q, &to_centroid));
+        source = source + Matrix2::new(u.x * u.x, u.x * u.y, u.y * u.x, u.y * u.y);
+        cross = cross + Matrix2::new(v.x * u.x, v.x * u.y, v.y * u.x, v.y * u.y);
+    }
+    // Collinear points leave only rounding noise in the determinant
+    let trace = source.trace();
+    if source.determinant() <= T::epsilon().sqrt() * trace * trace {
+        return None;
+    }
+    let linear = cross * source.inverse()?;
+    Some(fit(with_translation(linear, &from_centroid, &to_centroid), from, to))
+}
+
+/// Returns the best transform of the given kind.
+pub fn estimate_transform<T: Float>(kind: TransformKind, from: &[Point2d<T>], to: &[Point2d<T>]) -> Option<TransformFit<T>> {
+    match kind {
+        TransformKind::Rigid => estimate_rigid(from, to),
+        TransformKind::Similarity => estimate_similarity(from, to),
+        TransformKind::Affine => estimate_affine(from, to),
+    }
+}
+
+/// Estimates a transform of the given kind that is robust to mismatched pairs.
+///
+/// Each iteration fits a transform to a random minimal sample and counts the pairs it maps
+/// within `threshold`; the transform with the most inliers, ties broken by their squared
+/// residuals, is refitted to its inliers until they no longer change. If the inliers give no
+/// transform, as nearly collinear ones may not, the sample's own transform is kept. Returns
+/// None if no sample gives a transform.
+pub fn ransac<T: Float>(
+    kind: TransformKind,
+    from: &[Point2d<T>],
+    to: &[Point2d<T>],
+    options: &RansacOptions<T>,
+) -> Option<RansacFit<T>> {
+    let count = from.len();
+    let size = kind.minimal_pairs();
+    if count < size || count != to.len() {
+        return None;
+    }
+    let mut state = options.seed;
+    let mut next = move || {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (state >> 33) as usize % count
+    };
+    let inliers_of = |transform: &AffineTransform2d<T>| -> (Vec<usize>, T) {
+        let residuals = residuals(transform, from, to);
+        let inliers: Vec<usize> = (0..count).filter(|&i| residuals[i] <= options.threshold).collect();
+        let score = inliers.iter().fold(T::zero(), |sum, &i| sum + residuals[i] * residuals[i]);
+        (inliers, score)
+    };
+    let mut best: Option<(Vec<usize>, T, AffineTransform2d<T>)> = None;
+    let (mut sample_from, mut sample_to) = (Vec::with_capacity(size), Vec::with_capacity(size));
+    let mut sample: Vec<usize> = Vec::with_capacity(size);
+    for _ in 0..options.iterations {
+        sample.clear();
+        while sample.len() < size {
+            let index = next();
+            if !sample.contains(&index) {
+                sample.push(index);
+            }
+        }
+        sample_from.clear();
+        sample_to.clear();
+        sample_from.extend(sample.iter().map(|&i| from[i]));
+        sample_to.extend(sample.iter().map(|&i| to[i]));
+        let Some(candidate) = estimate_transform(kind, &sample_from, &sample_to) else {
+            continue;
+        };
+        let (inliers, score) = inliers_of(&candidate.transform);
+        let better = best.as_ref().is_none_or(|(most, lowest, _)| {
+            inliers.len() > most.len() || (inliers.len() == most.len() && score < *lowest)
+        });
+        if better {
+            best = Some((inliers, score, candidate.transform));
+        }
+    }
+    let (mut inliers, _, mut transform) = best?;
+    for _ in 0..10 {
+        let selected_from: Vec<Point2d<T>> = inliers.iter().map(|&i| from[i]).collect();
+        let selected_to: Vec<Point2d<T>> = inliers.iter().map(|&i| to[i]).collect();
+        let Some(refit) = estimate_transform(kind, &selected_from, &selected_to) else {
+            break;
+        };
+        transform = refit.transform;
+        let (refit_inliers, _) = inliers_of(&refit.transform);
+        if refit_inliers == inliers || refit_inliers.len() < size {
+            break;
+        }
+        inliers = refit_inliers;
+    }
+    // Report the pairs within the threshold of the final transform
+    let (inliers, _) = inliers_of(&transform);
+    let residuals = residuals(&transform, from, to);
+    let rms = rms(inliers.iter().map(|&i| residuals[i]));
+    Some(RansacFit { transform, inliers, residuals, rms })
+}
@@ -0,0 +1,155 @@
+use matrix2d::linalg::smallest_eigenvector;
+use num_traits::Float;
+use point2d::Point2d;
+
+use crate::linalg::normalization;
+
+/// A projective transform of the plane: a 3x3 matrix acting on homogeneous coordinates
+/// `(x, y, 1)`, followed by the perspective divide.
+///
+/// The matrix is only defined up to scale; estimated transforms are scaled so that the
+/// bottom right entry is one whenever it is not zero.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Homography2d<T> {
+    pub matrix: [[T; 3]; 3],
+}
+
+impl<T: Float> Homography2d<T> {
+    pub fn new(matrix: [[T; 3]; 3]) -> Self {
+        Homography2d { matrix }
+    }
+    pub fn identity() -> Self {
+        let (o, l) = (T::zero(), T::one());
+        Homography2d::new([[l, o, o], [o, l, o], [o, o, l]])
+    }
+    /// Returns the image of `point`, or None if it is mapped to infinity.
+    pub fn apply(&self, point: &Point2d<T>) -> Option<Point2d<T>> {
+        let m = &self.matrix;
+        let (x, y) = (point.x(), point.y());
+        let w = m[2][0] * x + m[2][1] * y + m[2][2];
+        let image = Point2d::new((m[0][0] * x + m[0][1] * y + m[0][2]) / w, (m[1][0] * x + m[1][1] * y + m[1][2]) / w);
+        (!w.is_zero() && image.x().is_finite() && image.y().is_finite()).then_some(image)
+    }
+    pub fn determinant(&self) -> T {
+        let m = &self.matrix;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1]) - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+    /// Returns the inverse transform, or None if the matrix is singular.
+    pub fn inverse(&self) -> Option<Self> {
+        let m = &self.matrix;
+        let determinant = self.determinant();
+        if determinant.is_zero() || !determinant.is_finite() {
+            return None;
+        }
+        // The adjugate divided by the determinant
+        let cofactor = |r1: usize, r2: usize, c1: usize, c2: usize| m[r1][c1] * m[r2][c2] - m[r1][c2] * m[r2][c1];
+        let adjugate = [
+            [cofactor(1, 2, 1, 2), -cofactor(0, 2, 1, 2), cofactor(0, 1, 1, 2)],
+            [-cofactor(1, 2, 0, 2), cofactor(0, 2, 0, 2), -cofactor(0, 1, 0, 2)],
+            [cofactor(1, 2, 0, 1), -cofactor(0, 2, 0, 1), cofactor(0, 1, 0, 1)],
+        ];
+        Some(Homography2d::new(adjugate.map(|row| row.map(|v| v / determinant))).normalized())
+    }
+    /// Returns the transform that applies `other` first and then `self`.
+    pub fn compose(&self, other: &Self) -> Self {
+        let (a, b) = (&self.matrix, &other.matrix);
+        let mut matrix = [[T::zero(); 3]; 3];
+        for (r, row) in matrix.iter_mut().enumerate() {
+            for (c, value) in row.iter_mut().enumerate() {
+                *value = a[r][0] * b[0][c] + a[r][1] * b[1][c] + a[r][2] * b[2][c];
+            }
+        }
+        Homography2d::new(matrix).normalized()
+    }
+    /// Returns the same transform scaled so that the bottom right entry is one, or to unit
+    /// norm if that entry is zero.
+    pub fn normalized(&self) -> Self {
+        let corner = self.matrix[2][2];
+        let scale = if corner.is_zero() {
+            self.matrix.iter().flatten().fold(T::zero(), |sum, &v| sum + v * v).sqrt()
+        } else {
+            corner
+        };
+        if scale.is_zero() {
+            return *self;
+        }
+        Homography2d::new(self.matrix.map(|row| row.map(|v| v / scale)))
+    }
+    /// Returns the transform taking the corners of the unit square, in the order (0, 0),
+    /// (1, 0), (1, 1), (0, 1), to `quad`, or None if three of its corners are collinear.
+    fn from_unit_square(quad: &[Point2d<T>; 4]) -> Option<Self> {
+        let [p0, p1, p2, p3] = quad;
+        let sx = p0.x() - p1.x() + p2.x() - p3.x();
+        let sy = p0.y() - p1.y() + p2.y() - p3.y();
+        let (dx1, dy1) = (p1.x() - p2.x(), p1.y() - p2.y());
+        let (dx2, dy2) = (p3.x() - p2.x(), p3.y() - p2.y());
+        let denominator = dx1 * dy2 - dx2 * dy1;
+        // A parallelogram needs no perspective terms
+        let (g, h) = if sx.is_zero() && sy.is_zero() {
+            (T::zero(), T::zero())
+        } else if denominator.is_zero() {
+            return None;
+        } else {
+            ((sx * dy2 - dx2 * sy) / denominator, (dx1 * sy - sx * dy1) / denominator)
+        };
+        let matrix = [
+            [p1.x() - p0.x() + g * p1.x(), p3.x() - p0.x() + h * p3.x(), p0.x()],
+            [p1.y() - p0.y() + g * p1.y(), p3.y() - p0.y() + h * p3.y(), p0.y()],
+            [g, h, T::one()],
+        ];
+        let homography = Homography2d::new(matrix);
+        (!homography.determinant().is_zero()).then_some(homography)
+    }
+    /// Returns the transform mapping each of the four points of `from` exactly onto the
+    /// matching point of `to`, or None if three points of either set are collinear.
+    pub fn from_four_points(from: &[Point2d<T>; 4], to: &[Point2d<T>; 4]) -> Option<Self> {
+        let source = Self::from_unit_square(from)?.inverse()?;
+        Some(Self::from_unit_square(to)?.compose(&source))
+    }
+    /// Returns the transform that best maps `from` onto `to` in the least-squares sense of
+    /// the direct linear transform, or None if there are fewer than four pairs, the slices
+    /// differ in length or the points are degenerate.
+    ///
+    /// Both point sets are first centred and scaled to a mean distance of `sqrt(2)`, which
+    /// keeps the equations well conditioned (Hartley's normalization).
+    pub fn estimate(from: &[Point2d<T>], to: &[Point2d<T>]) -> Option<Self> {
+        if from.len() < 4 || from.len() != to.len() {
+            return None;
+        }
+        let (from_centroid, from_scale) = normalization(from)?;
+        let (to_centroid, to_scale) = normalization(to)?;
+        // Each pair gives two rows of A h = 0; accumulate the normal matrix A^T A
+        let mut normal = [[T::zero(); 9]; 9];
+        for (p, q) in from.iter().zip(to) {
+            let (x, y) = ((p.x() - from_centroid.x()) * from_scale, (p.y() - from_centroid.y()) * from_scale);
+            let (u, v) = ((q.x() - to_centroid.x()) * to_scale, (q.y() - to_centroid.y()) * to_scale);
+            let (o, l) = (T::zero(), T::one());
+            let rows = [[-x, -y, -l, o, o, o, u * x, u * y, u], [o, o, o, -x, -y, -l, v * x, v * y, v]];
+            for row in &rows {
+                for i in 0..9 {
+                    for j in 0..9 {
+                        normal[i][j] = normal[i][j] + row[i] * row[j];
+                    }
+                }
+            }
+        }
+        let h = smallest_eigenvector(normal);
+        let normalized = Homography2d::new([[h[0], h[1], h[2]], [h[3], h[4], h[5]], [h[6], h[7], h[8]]]);
+        // Undo the normalizations: H = T_to^-1 * H_n * T_from
+        let (o, l) = (T::zero(), T::one());
+        let from_transform = Homography2d::new([
+            [from_scale, o, -from_scale * from_centroid.x()],
+            [o, from_scale, -from_scale * from_centroid.y()],
+            [o, o, l],
+        ]);
+        let to_inverse = Homography2d::new([
+            [to_scale.recip(), o, to_centroid.x()],
+            [o, to_scale.recip(), to_centroid.y()],
+            [o, o, l],
+        ]);
+        let homography = to_inverse.compose(&normalized.compose(&from_transform));
+        (!homography.determinant().is_zero() && homography.matrix.iter().flatten().all(|v| v.is_finite()))
+            .then_some(homography)
+    }
+}
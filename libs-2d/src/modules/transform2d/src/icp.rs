@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+
+use line2d::Line2d;
+use matrix2d::linalg::solve3;
+use num_traits::Float;
+use point2d::Point2d;
+use vector2d::Vector2d;
+
+use crate::{estimate_rigid, AffineTransform2d};
+
+/// The error minimized by `icp`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum IcpMethod {
+    /// The distance from each source point to its closest target point.
+    PointToPoint,
+    /// The distance from each source point to the line through its closest target point and
+    /// that point's nearest neighbour, which lets points slide along walls and converges in
+    /// far fewer iterations on scans of surfaces.
+    PointToLine,
+}
+
+/// Settings for `icp`. Iteration stops when a step moves less than both step tolerances, the
+/// root mean square error improves by less than `error_tolerance` times its value, or after
+/// `max_iterations`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct IcpOptions<T> {
+    pub method: IcpMethod,
+    pub max_iterations: usize,
+    /// Pairs further apart than this are ignored. It also sets the cell size of the grid used
+    /// to find closest points, so a finite value is much faster on large clouds.
+    pub max_correspondence_distance: T,
+    pub translation_tolerance: T,
+    /// In radians.
+    pub rotation_tolerance: T,
+    pub error_tolerance: T,
+}
+
+impl<T: Float> IcpOptions<T> {
+    pub fn new(method: IcpMethod) -> Self {
+        IcpOptions {
+            method,
+            max_iterations: 50,
+            max_correspondence_distance: T::infinity(),
+            translation_tolerance: T::epsilon().sqrt(),
+            rotation_tolerance: T::epsilon().sqrt(),
+            error_tolerance: T::epsilon().sqrt(),
+        }
+    }
+}
+
+/// Statistics of one `icp` iteration: the pairs found for the current transform, their root
+/// mean square error, and the size of the step then taken.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct IcpIteration<T> {
+    pub correspondences: usize,
+    pub rms: T,
+    pub translation_step: T,
+    pub rotation_step: T,
+}
+
+/// The result of `icp`. `rms` and `correspondences` are measured at the final transform.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IcpResult<T> {
+    pub transform: AffineTransform2d<T>,
+    pub iterations: Vec<IcpIteration<T>>,
+    pub converged: bool,
+    pub correspondences: usize,
+    pub rms: T,
+}
+
+/// Finds the closest target point, bucketing the targets in square cells of the maximum
+/// search distance so that only the nine cells around a query need to be searched.
+struct Nearest<'a, T> {
+    points: &'a [Point2d<T>],
+    max_distance: T,
+    cells: Option<HashMap<(i64, i64), Vec<usize>>>,
+}
+
+impl<'a, T: Float> Nearest<'a, T> {
+    fn new(points: &'a [Point2d<T>], max_distance: T) -> Self {
+        let mut nearest = Nearest { points, max_distance, cells: None };
+        if max_distance.is_finite() && max_distance > T::zero() {
+            let mut cells: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+            for (i, point) in points.iter().enumerate() {
+                if let Some(cell) = nearest.cell(point) {
+                    cells.entry(cell).or_default().push(i);
+                }
+            }
+            nearest.cells = Some(cells);
+        }
+        nearest
+    }
+    fn cell(&self, point: &Point2d<T>) -> Option<(i64, i64)> {
+        let x = (point.x() / self.max_distance).floor().to_i64()?;
+        Some((x, (point.y() / self.max_distance).floor().to_i64()?))
+    }
+    /// Returns the index of the closest point other than `exclude` within the maximum
+    /// distance, and its distance.
+    fn find(&self, point: &Point2d<T>, exclude: Option<usize>) -> Option<(usize, T)> {
+        let mut best: Option<(usize, T)> = None;
+        let mut consider = |i: usize| {
+            let distance = self.points[i].distance_to(point);
+            if Some(i) != exclude && distance <= self.max_distance && best.is_none_or(|(_, d)| distance < d) {
+                best = Some((i, distance));
+            }
+        };
+        match &self.cells {
+            Some(cells) => {
+                let (x, y) = self.cell(point)?;
+                for dx in -1..=1 {
+                    for dy in -1..=1 {
+                        cells.get(&(x + dx, y + dy)).into_iter().flatten().for_each(|&i| consider(i));
+                    }
+                }
+            }
+            None => (0..self.points.len()).for_each(consider),
+        }
+        best
+    }
+}
+
+/// A mapped source point, its closest target point and, for point-to-line, the target normal.
+type Pair<T> = (Point2d<T>, Point2d<T>, Option<Vector2d<T>>);
+
+/// Registers `source` onto `target`: starting from `initial`, alternately pairs each mapped
+/// source point with its closest target point and moves by the rigid transform that best
+/// aligns the pairs.
+///
+/// Returns None if either cloud is empty or the first iteration finds too few pairs: two for
+/// point-to-point, three for point-to-line. A step that cannot be solved, such as
+/// point-to-line against a single straight wall, ends the iteration without convergence. So
+/// does a step that increases the error, which is undone.
+pub fn icp<T: Float>(
+    source: &[Point2d<T>],
+    target: &[Point2d<T>],
+    initial: &AffineTransform2d<T>,
+    options: &IcpOptions<T>,
+) -> Option<IcpResult<T>> {
+    let nearest = Nearest::new(target, options.max_correspondence_distance);
+    // The unit normal of the line through each target point and its nearest neighbour
+    let normals: Vec<Option<Vector2d<T>>> = match options.method {
+        IcpMethod::PointToPoint => Vec::new(),
+        IcpMethod::PointToLine => target
+            .iter()
+            .enumerate()
+            .map(|(i, point)| {
+                let (j, _) = nearest.find(point, Some(i))?;
+                let normal = Line2d::new(*point, target[j]).normal_vector();
+                let length = normal.x.hypot(normal.y);
+                (length > T::zero()).then(|| Vector2d::new(normal.x / length, normal.y / length))
+            })
+            .collect(),
+    };
+    let pairs = |transform: &AffineTransform2d<T>| -> Vec<Pair<T>> {
+        source
+            .iter()
+            .filter_map(|point| {
+                let mapped = transform.apply(point);
+                let (i, _) = nearest.find(&mapped, None)?;
+                match options.method {
+                    IcpMethod::PointToPoint => Some((mapped, target[i], None)),
+                    IcpMethod::PointToLine => Some((mapped, target[i], Some(normals[i]?))),
+                }
+            })
+            .collect()
+    };
+    let error = |pairs: &[Pair<T>]| {
+        let sum = pairs.iter().fold(T::zero(), |sum, (p, q, normal)| {
+            let residual = match normal {
+                Some(n) => n.x * (p.x() - q.x()) + n.y * (p.y() - q.y()),
+                None => p.distance_to(q),
+            };
+            sum + residual * residual
+        });
+        (sum / T::from(pairs.len().max(1)).unwrap()).sqrt()
+    };
+    let minimum = match options.method {
+        IcpMethod::PointToPoint => 2,
+        IcpMethod::PointToLine => 3,
+    };
+
+    let mut transform = *initial;
+    let mut iterations = Vec::new();
+    let mut converged = false;
+    // The error and transform before the last step, to undo a step that made things worse
+    let mut previous: Option<(T, AffineTransform2d<T>)> = None;
+    while iterations.len() < options.max_iterations {
+        let current = pairs(&transform);
+        if current.len() < minimum {
+            break;
+        }
+        let rms = error(&current);
+        if let Some((previous_rms, previous_transform)) = previous {
+            let decrease = previous_rms - rms;
+            if decrease < T::zero() {
+                transform = previous_transform;
+                break;
+            }
+            if decrease <= options.error_tolerance * previous_rms {
+                converged = true;
+                break;
+            }
+        }
+        previous = Some((rms, transform));
+        let step = match options.method {
+            IcpMethod::PointToPoint => {
+                let (mapped, targets): (Vec<_>, Vec<_>) = current.iter().map(|&(p, q, _)| (p, q)).unzip();
+                estimate_rigid(&mapped, &targets).map(|fit| fit.transform)
+            }
+            IcpMethod::PointToLine => {
+                // Linearize the rotation by a small angle a, R p ~ p + a (-p.y, p.x), and
+                // solve for (a, tx, ty) minimizing the sum of (n . (R p + t - q))^2
+                let (mut normal, mut rhs) = ([[T::zero(); 3]; 3], [T::zero(); 3]);
+                for (p, q, n) in &current {
+                    let n = n.unwrap();
+                    let row = [n.y * p.x() - n.x * p.y(), n.x, n.y];
+                    let b = n.x * (q.x() - p.x()) + n.y * (q.y() - p.y());
+                    for i in 0..3 {
+                        rhs[i] = rhs[i] + row[i] * b;
+                        for j in 0..3 {
+                            normal[i][j] = normal[i][j] + row[i] * row[j];
+                        }
+                    }
+                }
+                solve3(normal, rhs).map(|[angle, tx, ty]| AffineTransform2d::rigid(angle, Vector2d::new(tx, ty)))
+            }
+        };
+        let Some(step) = step else {
+            break;
+        };
+        transform = step.compose(&transform);
+        let translation_step = step.translation.x.hypot(step.translation.y);
+        let rotation_step = step.rotation_angle().abs();
+        iterations.push(IcpIteration { correspondences: current.len(), rms, translation_step, rotation_step });
+        if translation_step <= options.translation_tolerance && rotation_step <= options.rotation_tolerance {
+            converged = true;
+            break;
+        }
+    }
+    let last = pairs(&transform);
+    if iterations.is_empty() && last.len() < minimum {
+        return None;
+    }
+    Some(IcpResult { transform, iterations, converged, correspondences: last.len(), rms: error(&last) })
+}
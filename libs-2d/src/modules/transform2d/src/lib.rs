@@ -0,0 +1,12 @@
+mod affine;
+mod estimate;
+mod homography;
+mod icp;
+mod linalg;
+pub use affine::AffineTransform2d;
+pub use estimate::{
+    estimate_affine, estimate_rigid, estimate_similarity, estimate_transform, ransac, RansacFit, RansacOptions, TransformFit,
+    TransformKind,
+};
+pub use homography::Homography2d;
+pub use icp::{icp, IcpIteration, IcpMethod, IcpOptions, IcpResult};
@@ -0,0 +1,13 @@
+use num_traits::Float;
+use point2d::Point2d;
+
+/// Returns the centroid of the points and the scale that brings their mean distance from it
+/// to `sqrt(2)`, which conditions the equations of point-correspondence fits.
+pub(crate) fn normalization<T: Float>(points: &[Point2d<T>]) -> Option<(Point2d<T>, T)> {
+    let count = T::from(points.len())?;
+    let (sx, sy) = points.iter().fold((T::zero(), T::zero()), |(sx, sy), p| (sx + p.x(), sy + p.y()));
+    let centroid = Point2d::new(sx / count, sy / count);
+    let mean = points.iter().fold(T::zero(), |sum, p| sum + p.distance_to(&centroid)) / count;
+    let scale = T::from(2.0)?.sqrt() / mean;
+    (mean > T::zero() && scale.is_finite()).then_some((centroid, scale))
+}
@@ -0,0 +1,196 @@
+use line2d::Line2d;
+use num_traits::Float;
+use point2d::Point2d;
+use predicates2d::Orientation;
+
+/// A triangle with corners `a`, `b` and `c`, in either orientation.
+///
+/// Per-corner results such as `angles` and `side_lengths` are ordered `a`, `b`, `c`, each side
+/// being the one opposite its corner.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Triangle2d<T> {
+    pub a: Point2d<T>,
+    pub b: Point2d<T>,
+    pub c: Point2d<T>,
+}
+
+/// Classification of a triangle by its largest angle.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AngleKind {
+    Acute,
+    Right,
+    Obtuse,
+}
+
+/// Classification of a triangle by how many of its sides are equal.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SideKind {
+    Equilateral,
+    Isosceles,
+    Scalene,
+}
+
+impl<T: Float> Triangle2d<T> {
+    pub fn new(a: Point2d<T>, b: Point2d<T>, c: Point2d<T>) -> Self {
+        Triangle2d { a, b, c }
+    }
+    pub fn vertices(&self) -> [Point2d<T>; 3] {
+        [self.a, self.b, self.c]
+    }
+    /// Returns the sides `a - b`, `b - c` and `c - a`.
+    pub fn edges(&self) -> [Line2d<T>; 3] {
+        [Line2d::new(self.a, self.b), Line2d::new(self.b, self.c), Line2d::new(self.c, self.a)]
+    }
+    /// Returns the area, positive if the corners run counter-clockwise.
+    pub fn signed_area(&self) -> T {
+        let (ux, uy) = (self.b.x() - self.a.x(), self.b.y() - self.a.y());
+        let (vx, vy) = (self.c.x() - self.a.x(), self.c.y() - self.a.y());
+        (ux * vy - uy * vx) / (T::one() + T::one())
+    }
+    pub fn area(&self) -> T {
+        self.signed_area().abs()
+    }
+    /// Returns the turn direction of `a, b, c`. For `f64` corners,
+    /// `predicates2d::orientation` gives the exact answer.
+    pub fn orientation(&self) -> Orientation {
+        let area = self.signed_area();
+        if area > T::zero() {
+            Orientation::CounterClockwise
+        } else if area < T::zero() {
+            Orientation::Clockwise
+        } else {
+            Orientation::Collinear
+        }
+    }
+    pub fn is_degenerate(&self) -> bool {
+        self.signed_area().is_zero()
+    }
+    /// Returns the lengths of the sides opposite `a`, `b` and `c`.
+    pub fn side_lengths(&self) -> [T; 3] {
+        [self.b.distance_to(&self.c), self.c.distance_to(&self.a), self.a.distance_to(&self.b)]
+    }
+    pub fn perimeter(&self) -> T {
+        let [p, q, r] = self.side_lengths();
+        p + q + r
+    }
+    /// Returns the weights of `a`, `b` and `c` that sum to one and combine the corners into
+    /// `point`, or None if the triangle is degenerate.
+    ///
+    /// All weights are non-negative exactly when the point lies in the triangle.
+    pub fn barycentric(&self, point: &Point2d<T>) -> Option<[T; 3]> {
+        let area = self.signed_area();
+        if area.is_zero() {
+            return None;
+        }
+        // Each weight is the signed area of the triangle the point forms with the other two
+        // corners, relative to the whole
+        let wa = Triangle2d::new(*point, self.b, self.c).signed_area() / area;
+        let wb = Triangle2d::new(self.a, *point, self.c).signed_area() / area;
+        let weights = [wa, wb, T::one() - wa - wb];
+        weights.iter().all(|w| w.is_finite()).then_some(weights)
+    }
+    /// Returns the point with the given barycentric weights, which should sum to one.
+    pub fn point_at(&self, weights: [T; 3]) -> Point2d<T> {
+        let [wa, wb, wc] = weights;
+        Point2d::new(
+            wa * self.a.x() + wb * self.b.x() + wc * self.c.x(),
+            wa * self.a.y() + wb * self.b.y() + wc * self.c.y(),
+        )
+    }
+    /// Returns the linear interpolation at `point` of values given at `a`, `b` and `c`, or None
+    /// if the triangle is degenerate. Points outside the triangle are extrapolated.
+    pub fn interpolate(&self, point: &Point2d<T>, values: [T; 3]) -> Option<T> {
+        let [wa, wb, wc] = self.barycentric(point)?;
+        Some(wa * values[0] + wb * values[1] + wc * values[2])
+    }
+    /// Returns true if the point is inside the triangle or on its boundary. A degenerate
+    /// triangle contains no points.
+    pub fn contains(&self, point: &Point2d<T>) -> bool {
+        self.barycentric(point).is_some_and(|weights| weights.iter().all(|w| *w >= T::zero()))
+    }
+    pub fn centroid(&self) -> Point2d<T> {
+        let third = (T::one() + T::one() + T::one()).recip();
+        self.point_at([third, third, third])
+    }
+    /// Returns the center of the circle through the corners, or None if the triangle is
+    /// degenerate.
+    pub fn circumcenter(&self) -> Option<Point2d<T>> {
+        let (x, y) = self.circumcenter_offset()?;
+        Some(Point2d::new(self.a.x() + x, self.a.y() + y))
+    }
+    pub fn circumradius(&self) -> Option<T> {
+        self.circumcenter_offset().map(|(x, y)| x.hypot(y))
+    }
+    /// Returns the circumcenter less `a`. Working from `a` keeps the digits that coordinates
+    /// far from the origin would otherwise lose.
+    fn circumcenter_offset(&self) -> Option<(T, T)> {
+        let (u, v) = (self.b - self.a, self.c - self.a);
+        let d = (T::one() + T::one()) * (u.x() * v.y() - u.y() * v.x());
+        let (uu, vv) = (u.x() * u.x() + u.y() * u.y(), v.x() * v.x() + v.y() * v.y());
+        // |u|^2 perp(v) - |v|^2 perp(u), over d, with perp(w) = (w.y, -w.x)
+        let (x, y) = ((uu * v.y() - vv * u.y()) / d, (vv * u.x() - uu * v.x()) / d);
+        (!d.is_zero() && x.is_finite() && y.is_finite()).then_some((x, y))
+    }
+    /// Returns the center of the inscribed circle, or None if the triangle is degenerate.
+    pub fn incenter(&self) -> Option<Point2d<T>> {
+        if self.is_degenerate() {
+            return None;
+        }
+        self.normalized_point(self.side_lengths())
+    }
+    /// Returns the radius of the inscribed circle, zero for a degenerate triangle.
+    pub fn inradius(&self) -> T {
+        let semi_perimeter = self.perimeter() / (T::one() + T::one());
+        if semi_perimeter.is_zero() { T::zero() } else { self.area() / semi_perimeter }
+    }
+    /// Returns the meeting point of the altitudes, or None if the triangle is degenerate.
+    pub fn orthocenter(&self) -> Option<Point2d<T>> {
+        // The Euler line: H = A + B + C - 2 O, taken from A as the circumcenter is
+        let (x, y) = self.circumcenter_offset()?;
+        let (u, v) = (self.b - self.a, self.c - self.a);
+        let two = T::one() + T::one();
+        Some(Point2d::new(self.a.x() + u.x() + v.x() - two * x, self.a.y() + u.y() + v.y() - two * y))
+    }
+    /// Returns the point with weights proportional to `weights`, or None if they sum to zero.
+    fn normalized_point(&self, weights: [T; 3]) -> Option<Point2d<T>> {
+        let total = weights[0] + weights[1] + weights[2];
+        let point = self.point_at(weights.map(|w| w / total));
+        (!total.is_zero() && point.x().is_finite() && point.y().is_finite()).then_some(point)
+    }
+    /// Returns the interior angles at `a`, `b` and `c`, in radians.
+    pub fn angles(&self) -> [T; 3] {
+        let angle = |at: &Point2d<T>, p: &Point2d<T>, q: &Point2d<T>| {
+            let (ux, uy) = (p.x() - at.x(), p.y() - at.y());
+            let (vx, vy) = (q.x() - at.x(), q.y() - at.y());
+            // atan2 of the cross and dot products stays accurate for angles near 0 and pi
+            (ux * vy - uy * vx).abs().atan2(ux * vx + uy * vy)
+        };
+        [angle(&self.a, &self.b, &self.c), angle(&self.b, &self.c, &self.a), angle(&self.c, &self.a, &self.b)]
+    }
+    /// Classifies the triangle by its largest angle, comparing the squared sides by Pythagoras
+    /// within a relative tolerance of 1e-10.
+    pub fn angle_kind(&self) -> AngleKind {
+        let mut squares = self.side_lengths().map(|s| s * s);
+        squares.sort_by(|x, y| x.partial_cmp(y).unwrap_or(std::cmp::Ordering::Equal));
+        let excess = squares[2] - squares[0] - squares[1];
+        let tolerance = T::from(1e-10).unwrap() * squares[2];
+        if excess.abs() <= tolerance {
+            AngleKind::Right
+        } else if excess > T::zero() {
+            AngleKind::Obtuse
+        } else {
+            AngleKind::Acute
+        }
+    }
+    /// Classifies the triangle by its equal sides, within a relative tolerance of 1e-10.
+    pub fn side_kind(&self) -> SideKind {
+        let [p, q, r] = self.side_lengths();
+        let tolerance = T::from(1e-10).unwrap() * p.max(q).max(r);
+        let equal = |x: T, y: T| (x - y).abs() <= tolerance;
+        match (equal(p, q), equal(q, r), equal(r, p)) {
+            (true, true, _) => SideKind::Equilateral,
+            (false, false, false) => SideKind::Scalene,
+            _ => SideKind::Isosceles,
+        }
+    }
+}
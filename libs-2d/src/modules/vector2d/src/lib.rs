@@ -5,8 +5,17 @@ use std::ops::{Add, Sub, Mul, Div, Neg };
 use std::ops::{AddAssign, SubAssign, MulAssign, DivAssign};
 use std::cmp::PartialOrd;
 
-use num_traits::{Float, NumCast, Signed};
+use num_traits::{Float, Num, NumCast, Signed};
 use::num_traits::identities::Zero;
+
+#[cfg(feature = "rational")]
+mod rational;
+#[cfg(feature = "rational")]
+pub use rational::BigRational;
+mod size;
+pub use size::Size2d;
+mod unit;
+pub use unit::UnitVector2d;
 ///use num_traits::real::Real;
 ///use std::io::{self, Read};
 #[derive(Clone)]
@@ -173,12 +182,12 @@ impl<T: Copy + Clone + Zero + std::ops::Mul<Output = T> + std::ops::Sub<Output =
 }
 impl<T> Mul<T> for Vector2d<T>
 where
-    T: Copy + Clone + Mul<Output = T>,
+    T: Clone + Mul<Output = T>,
 {
     type Output = Vector2d<T>;
     fn mul(self, rhs: T) -> Self::Output {
         Vector2d {
-            x: self.x * rhs,
+            x: self.x * rhs.clone(),
             y: self.y * rhs,
         }
     }
@@ -186,61 +195,61 @@ where
 
 impl<T> Mul<T> for &Vector2d<T>
 where
-    T: Copy + Clone + Mul<Output = T>,
+    T: Clone + Mul<Output = T>,
 {
     type Output = Vector2d<T>;
     fn mul(self, rhs: T) -> Self::Output {
         Vector2d {
-            x: self.x * rhs,
-            y: self.y * rhs,
+            x: self.x.clone() * rhs.clone(),
+            y: self.y.clone() * rhs,
         }
     }
 }
 impl<T, O> Add<&Vector2d<T>> for &Vector2d<T>
 where
-    T: Add<T, Output=O> + Copy + Clone,
+    T: Add<T, Output=O> + Clone,
 {
     type Output = Vector2d<O>;
     fn add(self, rhs: &Vector2d<T>) -> Self::Output {
         Vector2d {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
+            x: self.x.clone() + rhs.x.clone(),
+            y: self.y.clone() + rhs.y.clone(),
         }
     }
 }
 impl<T, O> Sub<&Vector2d<T>> for &Vector2d<T>
 where
-    T: Sub<T, Output=O> + Copy + Clone,
+    T: Sub<T, Output=O> + Clone,
 {
     type Output = Vector2d<O>;
     fn sub(self, rhs: &Vector2d<T>) -> Self::Output {
         Vector2d {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
+            x: self.x.clone() - rhs.x.clone(),
+            y: self.y.clone() - rhs.y.clone(),
         }
     }
 }
 impl<T, O> Mul<&Vector2d<T>> for &Vector2d<T>
 where
-    T: Mul<T, Output=O> + Copy + Clone,
+    T: Mul<T, Output=O> + Clone,
 {
     type Output = Vector2d<O>;
     fn mul(self, rhs: &Vector2d<T>) -> Self::Output {
         Vector2d {
-            x: self.x * rhs.x,
-            y: self.y * rhs.y,
+            x: self.x.clone() * rhs.x.clone(),
+            y: self.y.clone() * rhs.y.clone(),
         }
     }
 }
 impl<T, O> Div<&Vector2d<T>> for &Vector2d<T>
 where
-    T: Div<T, Output=O> + Copy + Clone,
+    T: Div<T, Output=O> + Clone,
 {
     type Output = Vector2d<O>;
     fn div(self, rhs: &Vector2d<T>) -> Self::Output {
         Vector2d {
-            x: self.x / rhs.x,
-            y: self.y / rhs.y,
+            x: self.x.clone() / rhs.x.clone(),
+            y: self.y.clone() / rhs.y.clone(),
         }
     }
 }
@@ -299,7 +308,7 @@ impl<T: Copy + Clone + std::default::Default> Vector2d<T> {
 }
 // Implementing the Add and Sub traits for Vector2d
 impl<T, O> Add<Vector2d<T>> for Vector2d<T>
-where T: Add<T, Output=O> + Copy + Clone,
+where T: Add<T, Output=O>,
 {
     type Output = Vector2d<O>;
     fn add(self, rhs: Vector2d<T>) -> Self::Output {
@@ -319,7 +328,7 @@ where T: AddAssign,
 }
 
 impl<T, O> Sub<Vector2d<T>> for Vector2d<T>
-where T: Sub<T, Output=O> + Copy + Clone,
+where T: Sub<T, Output=O>,
 {
     type Output = Vector2d<O>;
     fn sub(self, rhs: Vector2d<T>) -> Self::Output {
@@ -338,7 +347,7 @@ where T: SubAssign,
     }
 }
 impl<T, O> Mul<Vector2d<T>> for Vector2d<T>
-where T: Mul<T, Output=O> + Copy + Clone,
+where T: Mul<T, Output=O>,
 {
     type Output = Vector2d<O>;
     fn mul(self, rhs: Vector2d<T>) -> Self::Output {
@@ -357,7 +366,7 @@ where T: MulAssign,
     }
 }
 impl<T, O> Div<Vector2d<T>> for Vector2d<T>
-where T: Div<T, Output=O> + Copy + Clone,
+where T: Div<T, Output=O>,
 {
     type Output = Vector2d<O>;
     fn div(self, rhs: Vector2d<T>) -> Self::Output {
@@ -427,48 +436,26 @@ impl<T: Float + Signed> Vector2d<T> {
     pub fn length(&self) -> T {
         (self.x * self.x + self.y * self.y).sqrt()
     }
-    pub fn length_squared(&self) -> T
-    where <T as Mul>::Output: Add
-    {
-        self.x * self.x + self.y * self.y
-    }
     pub fn angle(&self) -> T {
         self.y.atan2(self.x)
     }
     pub fn angle_degrees(&self) -> T {
         self.angle() * T::from(180.0 / std::f32::consts::PI).unwrap()
     }
-    pub fn normalized(&self) -> Self {
-        let len = self.length();
-        if len.is_zero() {
-            Self::null()
-        } else {
-            Self {
-                x: self.x / len,
-                y: self.y / len,
-            }
-        }
+    /// Returns the vector scaled to length one, or None if it is zero or not finite.
+    pub fn normalized(&self) -> Option<UnitVector2d<T>> {
+        UnitVector2d::new(*self)
     }
+    /// Scales the vector to length one, leaving a zero or non-finite vector as it is.
     pub fn normalize(&mut self) {
-        *self = self.normalized();
-    }
-    pub fn dot(&self, other: &Self) -> T {
-        self.x * other.x + self.y * other.y
-    }
-    pub fn cross(&self, other: &Self) -> T {
-        self.x * other.y - self.y * other.x
-    }
-    pub fn unit_vector(&self) -> Self {
-        let len = self.length();
-        if len.is_zero() {
-            Self::null()
-        } else {
-            Self {
-                x: self.x / len,
-                y: self.y / len,
-            }
+        if let Some(unit) = self.normalized() {
+            *self = unit.to_vector();
         }
     }
+    /// Returns the vector scaled to length one, or None if it is zero or not finite.
+    pub fn unit_vector(&self) -> Option<UnitVector2d<T>> {
+        self.normalized()
+    }
     pub fn distance_to(&self, other: &Self) -> T {
         let dx = self.x - other.x;
         let dy = self.y - other.y;
@@ -548,12 +535,10 @@ impl<T: Float + Signed> Vector2d<T> {
     /// If the vector's length is less than or equal to `max_length`, it remains unchanged.
     pub fn clamp_magnitude(self, max_length: T) -> Self {
         let len = self.length();
-        if len > max_length {
-            let norm = self.normalized();
-            Self {
-                x: norm.x * max_length,
-                y: norm.y * max_length,
-            }
+        if len > max_length
+            && let Some(norm) = self.normalized()
+        {
+            norm.scale(max_length)
         } else {
             self
         }
@@ -597,13 +582,18 @@ impl<T: Float + Signed> Vector2d<T> {
             y: self.x * sin_angle + self.y * cos_angle,
         }
     }
-    pub fn reflect(&self, normal: &Self) -> Self {
-        let dot_product = self.dot(normal);
+    /// Reflects the vector off a surface with the given normal.
+    pub fn reflect(&self, normal: &UnitVector2d<T>) -> Self {
+        let dot_product = normal.dot(self);
         Self {
-            x: self.x - T::from(2.0).unwrap() * dot_product * normal.x,
-            y: self.y - T::from(2.0).unwrap() * dot_product * normal.y,
+            x: self.x - T::from(2.0).unwrap() * dot_product * normal.x(),
+            y: self.y - T::from(2.0).unwrap() * dot_product * normal.y(),
         }
     }
+    /// Returns the direction of the vector, or None if it is zero or not finite.
+    pub fn direction(&self) -> Option<UnitVector2d<T>> {
+        UnitVector2d::new(*self)
+    }
     pub fn projection_length(&self, other: &Self) -> T {
         self.dot(other) / other.length()
     }
@@ -611,14 +601,6 @@ impl<T: Float + Signed> Vector2d<T> {
         let projection = self.project_onto(axis);
         projection * T::from(2.0).unwrap() - self.clone()
     }
-    pub fn project_onto(&self, other: &Self) -> Self {
-        let dot_product = self.dot(other);
-        let length_squared = other.length_squared();
-        Self {
-            x: other.x * dot_product / length_squared,
-            y: other.y * dot_product / length_squared,
-        }
-    }
     pub fn perpendicular_to(&self, other: &Self) -> Self {
         let dot_product = self.dot(other);
         let length_squared = other.length_squared();
@@ -665,6 +647,30 @@ impl<T: Float + Signed> Vector2d<T> {
         self.translate(vector.x.into(), vector.y.into());
     }
 }
+// Methods without square roots, so they stay exact for rational or integer components,
+// which are not necessarily Copy
+impl<T: Clone + Num> Vector2d<T> {
+    pub fn length_squared(&self) -> T {
+        self.dot(self)
+    }
+    pub fn dot(&self, other: &Self) -> T {
+        self.x.clone() * other.x.clone() + self.y.clone() * other.y.clone()
+    }
+    pub fn cross(&self, other: &Self) -> T {
+        self.x.clone() * other.y.clone() - self.y.clone() * other.x.clone()
+    }
+    /// Returns the projection of `self` onto the line through `other`.
+    ///
+    /// Exact component types have no NaN, so `other` must not be zero for them.
+    pub fn project_onto(&self, other: &Self) -> Self {
+        let dot_product = self.dot(other);
+        let length_squared = other.length_squared();
+        Self {
+            x: other.x.clone() * dot_product.clone() / length_squared.clone(),
+            y: other.y.clone() * dot_product / length_squared,
+        }
+    }
+}
 impl<T: Neg<Output = T> + Copy + num_traits::Zero> Vector2d<T> {
     pub fn perpendicular(&self) -> Self {
         Self {
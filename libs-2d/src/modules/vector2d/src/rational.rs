@@ -0,0 +1,15 @@
+pub use num_rational::BigRational;
+use num_traits::ToPrimitive;
+
+use crate::Vector2d;
+
+impl Vector2d<BigRational> {
+    /// Converts a float vector without rounding, or returns None if a component is not finite.
+    pub fn from_f64(vector: &Vector2d<f64>) -> Option<Self> {
+        Some(Vector2d { x: BigRational::from_float(vector.x)?, y: BigRational::from_float(vector.y)? })
+    }
+    /// Returns the nearest float vector.
+    pub fn to_f64(&self) -> Vector2d<f64> {
+        Vector2d { x: self.x.to_f64().unwrap_or(f64::NAN), y: self.y.to_f64().unwrap_or(f64::NAN) }
+    }
+}
@@ -0,0 +1,36 @@
+use num_traits::Num;
+
+use crate::Vector2d;
+
+/// A width and a height. A size without a positive width and height is empty.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Size2d<T> {
+    pub width: T,
+    pub height: T,
+}
+
+impl<T: Copy + Num + PartialOrd> Size2d<T> {
+    pub fn new(width: T, height: T) -> Self {
+        Size2d { width, height }
+    }
+    pub fn from_vector(vector: Vector2d<T>) -> Self {
+        Size2d::new(vector.x, vector.y)
+    }
+    pub fn to_vector(self) -> Vector2d<T> {
+        Vector2d::new(self.width, self.height)
+    }
+    /// Returns the area, zero if the size is empty.
+    pub fn area(&self) -> T {
+        if self.is_empty() { T::zero() } else { self.width * self.height }
+    }
+    pub fn is_empty(&self) -> bool {
+        !(self.width > T::zero() && self.height > T::zero())
+    }
+    pub fn scale(&self, factor: T) -> Self {
+        Size2d::new(self.width * factor, self.height * factor)
+    }
+    /// Returns true if a box of this size fits in one of `other` without turning.
+    pub fn fits_in(&self, other: &Size2d<T>) -> bool {
+        self.width <= other.width && self.height <= other.height
+    }
+}
@@ -0,0 +1,64 @@
+use std::ops::Neg;
+
+use num_traits::Float;
+
+use crate::Vector2d;
+
+/// A vector of length one. It can only be made by normalizing, so a function taking one
+/// needs no check that its argument was normalized.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct UnitVector2d<T>(Vector2d<T>);
+
+impl<T: Float> UnitVector2d<T> {
+    /// Returns the vector scaled to length one, or None if it is zero or not finite.
+    pub fn new(vector: Vector2d<T>) -> Option<Self> {
+        // hypot rather than length, which would overflow for very long vectors
+        let length = vector.x.hypot(vector.y);
+        if length.is_zero() || !length.is_finite() {
+            return None;
+        }
+        Some(UnitVector2d(Vector2d::new(vector.x / length, vector.y / length)))
+    }
+    /// Returns the unit vector at `angle` radians from the x axis.
+    pub fn from_angle(angle: T) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        UnitVector2d(Vector2d::new(cos, sin))
+    }
+    pub fn x(&self) -> T {
+        self.0.x
+    }
+    pub fn y(&self) -> T {
+        self.0.y
+    }
+    pub fn to_vector(self) -> Vector2d<T> {
+        self.0
+    }
+    pub fn angle(&self) -> T {
+        self.0.y.atan2(self.0.x)
+    }
+    pub fn dot(&self, other: &Vector2d<T>) -> T {
+        self.0.x * other.x + self.0.y * other.y
+    }
+    /// Returns the unit vector turned a quarter counter-clockwise.
+    pub fn perpendicular(&self) -> Self {
+        UnitVector2d(Vector2d::new(-self.0.y, self.0.x))
+    }
+    pub fn rotate(&self, angle: T) -> Self {
+        UnitVector2d::from_angle(self.angle() + angle)
+    }
+    /// Returns the vector of the given length in this direction.
+    pub fn scale(&self, length: T) -> Vector2d<T> {
+        Vector2d::new(self.0.x * length, self.0.y * length)
+    }
+}
+impl<T: Float> Neg for UnitVector2d<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        UnitVector2d(Vector2d::new(-self.0.x, -self.0.y))
+    }
+}
+impl<T> From<UnitVector2d<T>> for Vector2d<T> {
+    fn from(unit: UnitVector2d<T>) -> Self {
+        unit.0
+    }
+}
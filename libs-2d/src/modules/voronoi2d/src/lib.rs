@@ -0,0 +1,164 @@
+use std::collections::{HashMap, HashSet};
+
+use delaunay2d::TriangleMesh;
+use point2d::Point2d;
+use polygon2d::Polygon2d;
+
+/// The boundary shared by the cells of two sites.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct VoronoiEdge {
+    /// The two sites, lower index first.
+    pub sites: [usize; 2],
+    /// End points, in counter-clockwise order around the first site.
+    pub start: Point2d<f64>,
+    pub end: Point2d<f64>,
+}
+
+/// Voronoi diagram of a set of sites, clipped to a rectangle.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Voronoi2d {
+    sites: Vec<Point2d<f64>>,
+    cells: Vec<Polygon2d<f64>>,
+    edges: Vec<VoronoiEdge>,
+    neighbors: Vec<Vec<usize>>,
+}
+
+/// Polygon vertex together with the site whose bisector runs from it to the next vertex,
+/// or None where the edge lies on the bounding box.
+type Vertex = (Point2d<f64>, Option<usize>);
+
+/// Keeps the part of a convex polygon on the side of `site` of its bisector with `other`.
+fn clip(cell: &[Vertex], site: Point2d<f64>, other: Point2d<f64>, label: usize) -> Vec<Vertex> {
+    let (nx, ny) = (other.x() - site.x(), other.y() - site.y());
+    let (mx, my) = ((site.x() + other.x()) / 2.0, (site.y() + other.y()) / 2.0);
+    let side = |p: &Point2d<f64>| (p.x() - mx) * nx + (p.y() - my) * ny;
+    let mut clipped = Vec::with_capacity(cell.len() + 1);
+    for i in 0..cell.len() {
+        let (p, edge) = cell[i];
+        let q = cell[(i + 1) % cell.len()].0;
+        let (sp, sq) = (side(&p), side(&q));
+        let crossing = || {
+            let t = sp / (sp - sq);
+            Point2d::new(p.x() + (q.x() - p.x()) * t, p.y() + (q.y() - p.y()) * t)
+        };
+        match (sp <= 0.0, sq <= 0.0) {
+            (true, true) => clipped.push((p, edge)),
+            (true, false) => {
+                clipped.push((p, edge));
+                clipped.push((crossing(), Some(label)));
+            }
+            (false, true) => clipped.push((crossing(), edge)),
+            (false, false) => {}
+        }
+    }
+    clipped
+}
+
+/// Drops vertices that start an edge shorter than `tolerance`.
+fn drop_short_edges(cell: &mut Vec<Vertex>, tolerance: f64) {
+    let mut i = 0;
+    while cell.len() > 1 && i < cell.len() {
+        if cell[i].0.distance_to(&cell[(i + 1) % cell.len()].0) <= tolerance {
+            cell.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+}
+
+impl Voronoi2d {
+    /// Computes the Voronoi cells of `sites` inside the box from `min` to `max`.
+    ///
+    /// Each cell is intersected with the box, so sites outside the box may get an empty
+    /// cell. A site that repeats an earlier one also gets an empty cell. Returns None if
+    /// the box has no area.
+    pub fn new(sites: &[Point2d<f64>], min: Point2d<f64>, max: Point2d<f64>) -> Option<Voronoi2d> {
+        if !(min.x() < max.x() && min.y() < max.y()) {
+            return None;
+        }
+        let n = sites.len();
+        // First site with the same coordinates; adding zero folds -0.0 into 0.0
+        let mut first = HashMap::new();
+        let canonical: Vec<usize> = (0..n)
+            .map(|i| *first.entry(((sites[i].x() + 0.0).to_bits(), (sites[i].y() + 0.0).to_bits())).or_insert(i))
+            .collect();
+        // Cells are bounded by the bisectors with their Delaunay neighbors; without a
+        // triangulation all sites are collinear and every pair is tried
+        let mesh = TriangleMesh::delaunay(sites);
+        let mut candidates = vec![Vec::new(); n];
+        if mesh.is_empty() {
+            for i in (0..n).filter(|&i| canonical[i] == i) {
+                candidates[i] = (0..n).filter(|&j| j != i && canonical[j] == j).collect();
+            }
+        } else {
+            for [a, b] in mesh.edges() {
+                candidates[a].push(b);
+                candidates[b].push(a);
+            }
+        }
+        let tolerance = 1e-12 * min.distance_to(&max);
+        let corners = [min, Point2d::new(max.x(), min.y()), max, Point2d::new(min.x(), max.y())];
+        let mut diagram = Voronoi2d {
+            sites: sites.to_vec(),
+            cells: Vec::with_capacity(n),
+            edges: Vec::new(),
+            neighbors: vec![Vec::new(); n],
+        };
+        for i in 0..n {
+            if canonical[i] != i {
+                diagram.cells.push(Polygon2d::from_exterior(Vec::new()));
+                continue;
+            }
+            let mut cell: Vec<Vertex> = corners.iter().map(|&corner| (corner, None)).collect();
+            for &j in &candidates[i] {
+                cell = clip(&cell, sites[i], sites[j], j);
+                if cell.is_empty() {
+                    break;
+                }
+            }
+            drop_short_edges(&mut cell, tolerance);
+            if cell.len() < 3 {
+                cell.clear();
+            }
+            for k in 0..cell.len() {
+                let (start, label) = cell[k];
+                if let Some(j) = label {
+                    diagram.neighbors[i].push(j);
+                    if i < j {
+                        diagram.edges.push(VoronoiEdge { sites: [i, j], start, end: cell[(k + 1) % cell.len()].0 });
+                    }
+                }
+            }
+            diagram.cells.push(Polygon2d::from_exterior(cell.into_iter().map(|(p, _)| p).collect()));
+        }
+        // Keep adjacency symmetric where one side's shared edge was too short to keep
+        let mut dropped = HashSet::new();
+        for i in 0..n {
+            let (kept, lost): (Vec<usize>, Vec<usize>) =
+                diagram.neighbors[i].iter().partition(|&&j| diagram.neighbors[j].contains(&i));
+            diagram.neighbors[i] = kept;
+            dropped.extend(lost.into_iter().map(|j| [i, j]));
+            diagram.neighbors[i].sort_unstable();
+        }
+        diagram.edges.retain(|edge| !dropped.contains(&edge.sites));
+        Some(diagram)
+    }
+    pub fn sites(&self) -> &[Point2d<f64>] {
+        &self.sites
+    }
+    /// Returns the cells in site order, each with a counter-clockwise exterior.
+    pub fn cells(&self) -> &[Polygon2d<f64>] {
+        &self.cells
+    }
+    pub fn cell(&self, site: usize) -> &Polygon2d<f64> {
+        &self.cells[site]
+    }
+    /// Returns every boundary shared by two cells once.
+    pub fn edges(&self) -> &[VoronoiEdge] {
+        &self.edges
+    }
+    /// Returns the sites whose cells share an edge with the cell of `site`, in increasing order.
+    pub fn neighbors(&self, site: usize) -> &[usize] {
+        &self.neighbors[site]
+    }
+}